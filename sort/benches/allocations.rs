@@ -0,0 +1,59 @@
+//! Counts the allocations and the time of each sort, which is run by
+//! `cargo bench -p sort`.
+
+use sort::{harness::CountingAlloc, ParallelConfig, SliceSort};
+use std::time::Instant;
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+type Sort = fn(&mut [u64]);
+
+const SORTS: [(&str, Sort); 10] = [
+    ("quick_sort", |v| v.quick_sort()),
+    ("merge_sort", |v| v.merge_sort()),
+    ("tim_sort", |v| v.tim_sort()),
+    ("heap_sort", |v| v.heap_sort()),
+    ("lsd_radix_sort", |v| v.lsd_radix_sort()),
+    ("msd_radix_sort", |v| v.msd_radix_sort()),
+    ("par_quick_sort", |v| {
+        v.par_quick_sort(ParallelConfig::default())
+    }),
+    ("par_merge_sort", |v| {
+        v.par_merge_sort(ParallelConfig::default())
+    }),
+    ("std sort", |v| v.sort()),
+    ("std sort_unstable", |v| v.sort_unstable()),
+];
+
+fn inputs(len: usize) -> [(&'static str, Vec<u64>); 4] {
+    let len = len as u64;
+    [
+        ("random", (0..len).map(|_| rand::random()).collect()),
+        ("sorted", (0..len).collect()),
+        ("reversed", (0..len).rev().collect()),
+        ("sawtooth", (0..len).map(|v| v % 1_000).collect()),
+    ]
+}
+
+fn main() {
+    println!(
+        "{:<10} {:>10} {:<18} {:>8} {:>14} {:>12}",
+        "input", "len", "sort", "allocs", "bytes", "time"
+    );
+    for len in [1_000, 100_000, 10_000_000] {
+        for (input, vec) in inputs(len) {
+            for (name, sort) in SORTS {
+                let mut vec = vec.clone();
+                let (allocs, bytes) = CountingAlloc::stats();
+                let now = Instant::now();
+                sort(&mut vec);
+                let time = now.elapsed();
+                let stats = CountingAlloc::stats();
+                let (allocs, bytes) = (stats.0 - allocs, stats.1 - bytes);
+                assert!(vec.is_sorted());
+                println!("{input:<10} {len:>10} {name:<18} {allocs:>8} {bytes:>14} {time:>12.2?}");
+            }
+        }
+    }
+}
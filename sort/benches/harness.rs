@@ -0,0 +1,38 @@
+//! Checks every comparison sort on the standard distributions, and reports
+//! the comparisons, swaps, allocations and time, which is run by
+//! `cargo bench -p sort --bench harness`.
+
+use sort::harness::{self, CountingAlloc, Distribution, ALGORITHMS};
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn main() {
+    println!(
+        "{:<18} {:>9} {:<18} {:>12} {:>12} {:>8} {:>12} {:>12}  result",
+        "input", "len", "sort", "comparisons", "swaps", "allocs", "bytes", "time"
+    );
+    for len in [1_000, 100_000, 1_000_000] {
+        for distribution in Distribution::STANDARD {
+            for algorithm in &ALGORITHMS {
+                let report = harness::run(algorithm, distribution, len, 42);
+                let swaps = report.swaps.map_or("-".to_string(), |v| v.to_string());
+                let result = match report.result {
+                    Ok(()) => "ok".to_string(),
+                    Err(failure) => failure.to_string(),
+                };
+                println!(
+                    "{:<18} {:>9} {:<18} {:>12} {:>12} {:>8} {:>12} {:>12.2?}  {result}",
+                    distribution.to_string(),
+                    len,
+                    report.algorithm,
+                    report.comparisons,
+                    swaps,
+                    report.allocations,
+                    report.bytes,
+                    report.time,
+                );
+            }
+        }
+    }
+}
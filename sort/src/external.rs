@@ -0,0 +1,248 @@
+//! External merge sort, for inputs larger than the memory.
+//!
+//! The input is read into chunks within the memory budget, each chunk is
+//! sorted and spilled to a temporary file as a run, and then the runs are
+//! merged by a tournament tree into the output. If there are more runs than
+//! the fan-in, they are merged in several passes.
+
+mod codec;
+
+pub use codec::{Codec, FixedWidth, LengthPrefixed, Lines};
+
+use crate::SliceSort;
+use std::{
+    cmp::Ordering,
+    env,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Seek, Write},
+    mem,
+    path::PathBuf,
+};
+
+/// Options of the external sort.
+///
+/// `memory` is the budget in bytes of the records held in memory, as
+/// measured by [`Codec::size_of`], which also bounds the buffers of the
+/// merging. At most `fan_in` runs are merged at once, and the temporary
+/// files are created in `temp_dir` and removed automatically.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalConfig {
+    pub memory: usize,
+    pub fan_in: usize,
+    pub temp_dir: PathBuf,
+}
+
+impl Default for ExternalConfig {
+    /// A budget of 64 MiB, a fan-in of 16 and the system temporary directory.
+    fn default() -> Self {
+        Self {
+            memory: 64 << 20,
+            fan_in: 16,
+            temp_dir: env::temp_dir(),
+        }
+    }
+}
+
+/// Sorts the records from `input` into `output`, see [`external_sort_by`].
+pub fn external_sort<C, R, W>(
+    input: R,
+    output: W,
+    codec: &C,
+    config: &ExternalConfig,
+) -> io::Result<()>
+where
+    C: Codec,
+    C::Record: Ord,
+    R: Read,
+    W: Write,
+{
+    external_sort_by(input, output, codec, config, C::Record::cmp)
+}
+
+/// Sorts the records from `input` into `output` with a comparator, the sort
+/// is stable.
+///
+/// # Examples
+///
+/// ```
+/// use sort::external::{self, ExternalConfig, Lines};
+///
+/// let input = "delta\nalpha\ncharlie\nbravo\n";
+/// let mut output = Vec::new();
+/// external::external_sort(input.as_bytes(), &mut output, &Lines, &ExternalConfig::default())?;
+/// assert_eq!(output, b"alpha\nbravo\ncharlie\ndelta\n");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn external_sort_by<C, R, W, F>(
+    input: R,
+    output: W,
+    codec: &C,
+    config: &ExternalConfig,
+    mut compare: F,
+) -> io::Result<()>
+where
+    C: Codec,
+    R: Read,
+    W: Write,
+    F: FnMut(&C::Record, &C::Record) -> Ordering,
+{
+    let mut input = BufReader::new(input);
+    let (mut runs, mut chunk, mut used) = (Vec::new(), Vec::new(), 0);
+    while let Some(record) = codec.read(&mut input)? {
+        used += codec.size_of(&record);
+        chunk.push(record);
+        if used >= config.memory {
+            runs.push(spill(&mut chunk, codec, config, &mut compare)?);
+            used = 0;
+        }
+    }
+    if runs.is_empty() {
+        chunk.tim_sort_by(&mut compare);
+        let mut output = BufWriter::new(output);
+        for record in &chunk {
+            codec.write(&mut output, record)?;
+        }
+        return output.flush();
+    } else if !chunk.is_empty() {
+        runs.push(spill(&mut chunk, codec, config, &mut compare)?);
+    }
+    drop(chunk);
+    let fan_in = config.fan_in.max(2);
+    while runs.len() > fan_in {
+        let mut merged = Vec::with_capacity(runs.len().div_ceil(fan_in));
+        let mut rest = runs.into_iter().peekable();
+        while rest.peek().is_some() {
+            let group = rest.by_ref().take(fan_in).collect::<Vec<_>>();
+            let mut file = tempfile::tempfile_in(&config.temp_dir)?;
+            merge(group, &mut file, codec, config, &mut compare)?;
+            file.rewind()?;
+            merged.push(file);
+        }
+        runs = merged;
+    }
+    merge(runs, output, codec, config, &mut compare)
+}
+
+/// Sorts `chunk` and moves it into a temporary file.
+fn spill<C, F>(
+    chunk: &mut Vec<C::Record>,
+    codec: &C,
+    config: &ExternalConfig,
+    compare: &mut F,
+) -> io::Result<File>
+where
+    C: Codec,
+    F: FnMut(&C::Record, &C::Record) -> Ordering,
+{
+    chunk.tim_sort_by(&mut *compare);
+    let mut file = BufWriter::new(tempfile::tempfile_in(&config.temp_dir)?);
+    for record in chunk.drain(..) {
+        codec.write(&mut file, &record)?;
+    }
+    let mut file = file.into_inner().map_err(io::IntoInnerError::into_error)?;
+    file.rewind()?;
+    Ok(file)
+}
+
+/// Merges the sorted `runs` into `output`, the memory budget is shared by
+/// the buffers of all the files.
+fn merge<C, W, F>(
+    runs: Vec<File>,
+    output: W,
+    codec: &C,
+    config: &ExternalConfig,
+    compare: &mut F,
+) -> io::Result<()>
+where
+    C: Codec,
+    W: Write,
+    F: FnMut(&C::Record, &C::Record) -> Ordering,
+{
+    let capacity = (config.memory / (runs.len() + 1)).max(1 << 12);
+    let mut readers = runs
+        .into_iter()
+        .map(|run| BufReader::with_capacity(capacity, run))
+        .collect::<Vec<_>>();
+    let heads = readers
+        .iter_mut()
+        .map(|reader| codec.read(reader))
+        .collect::<io::Result<_>>()?;
+    let mut tree = Tournament::new(heads, compare);
+    let mut output = BufWriter::with_capacity(capacity, output);
+    while let Some(idx) = tree.winner() {
+        let next = codec.read(&mut readers[idx])?;
+        let record = tree.replace(idx, next, compare);
+        codec.write(&mut output, &record)?;
+    }
+    output.flush()
+}
+
+/// A winner tree over the heads of the runs.
+///
+/// `tree[node]` is the index of the least head under `node`, where the
+/// children of `node` are `2 * node` and `2 * node + 1`, and the node
+/// `k + idx` is the head `idx` itself. An exhausted run always loses, and a
+/// tie goes to the former run, so the merging is stable.
+struct Tournament<T> {
+    heads: Vec<Option<T>>,
+    tree: Vec<usize>,
+}
+
+impl<T> Tournament<T> {
+    fn new<F>(heads: Vec<Option<T>>, compare: &mut F) -> Self
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let len = heads.len();
+        let mut this = Self {
+            heads,
+            tree: vec![0; len],
+        };
+        for node in (1..len).rev() {
+            this.tree[node] = this.play(node, compare);
+        }
+        this
+    }
+
+    /// The index of the run with the least head, if any run remains.
+    fn winner(&self) -> Option<usize> {
+        let idx = match self.heads.len() {
+            0 => return None,
+            1 => 0,
+            _ => self.tree[1],
+        };
+        self.heads[idx].as_ref().map(|_| idx)
+    }
+
+    /// Replaces the head of the run `idx`, and returns the old one.
+    fn replace<F>(&mut self, idx: usize, head: Option<T>, compare: &mut F) -> T
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let old = mem::replace(&mut self.heads[idx], head).expect("exhausted run");
+        let mut node = (idx + self.heads.len()) / 2;
+        while node > 0 {
+            self.tree[node] = self.play(node, compare);
+            node /= 2;
+        }
+        old
+    }
+
+    /// The winner between the two children of `node`.
+    fn play<F>(&self, node: usize, compare: &mut F) -> usize
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let leaf = |node: usize| match node.checked_sub(self.heads.len()) {
+            Some(idx) => idx,
+            None => self.tree[node],
+        };
+        let (a, b) = (leaf(2 * node), leaf(2 * node + 1));
+        let (a, b) = (a.min(b), a.max(b));
+        match (&self.heads[a], &self.heads[b]) {
+            (Some(x), Some(y)) if compare(y, x) == Ordering::Less => b,
+            (None, Some(_)) => b,
+            _ => a,
+        }
+    }
+}
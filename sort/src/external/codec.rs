@@ -0,0 +1,136 @@
+use std::{
+    io::{self, BufRead, ErrorKind, Read, Write},
+    mem,
+};
+
+/// How records are read from and written to a byte stream.
+pub trait Codec {
+    type Record;
+
+    /// Reads the next record, or `None` at the end of the stream.
+    fn read<R: BufRead>(&self, reader: &mut R) -> io::Result<Option<Self::Record>>;
+
+    /// Writes a record, so that reading it back gives the same record.
+    fn write<W: Write>(&self, writer: &mut W, record: &Self::Record) -> io::Result<()>;
+
+    /// The memory taken by a record, which counts against the budget.
+    fn size_of(&self, _record: &Self::Record) -> usize {
+        mem::size_of::<Self::Record>()
+    }
+}
+
+/// Newline-delimited records, without the trailing `\n`.
+///
+/// A missing `\n` after the last line is added when written back.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Lines;
+
+impl Codec for Lines {
+    type Record = Vec<u8>;
+
+    fn read<R: BufRead>(&self, reader: &mut R) -> io::Result<Option<Self::Record>> {
+        let mut line = Vec::new();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            return Ok(None);
+        }
+        if line.last() == Some(&b'\n') {
+            line.pop();
+        }
+        Ok(Some(line))
+    }
+
+    fn write<W: Write>(&self, writer: &mut W, record: &Self::Record) -> io::Result<()> {
+        writer.write_all(record)?;
+        writer.write_all(b"\n")
+    }
+
+    fn size_of(&self, record: &Self::Record) -> usize {
+        mem::size_of::<Self::Record>() + record.capacity()
+    }
+}
+
+/// Binary records of a fixed number of bytes.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedWidth {
+    width: usize,
+}
+
+impl FixedWidth {
+    /// Panics if `width` is zero.
+    pub fn new(width: usize) -> Self {
+        assert!(width > 0, "record width must be positive");
+        Self { width }
+    }
+}
+
+impl Codec for FixedWidth {
+    type Record = Vec<u8>;
+
+    fn read<R: BufRead>(&self, reader: &mut R) -> io::Result<Option<Self::Record>> {
+        let mut record = vec![0; self.width];
+        read_record(reader, &mut record).map(|found| found.then_some(record))
+    }
+
+    fn write<W: Write>(&self, writer: &mut W, record: &Self::Record) -> io::Result<()> {
+        if record.len() != self.width {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                "record width mismatch",
+            ));
+        }
+        writer.write_all(record)
+    }
+
+    fn size_of(&self, record: &Self::Record) -> usize {
+        mem::size_of::<Self::Record>() + record.capacity()
+    }
+}
+
+/// Binary records, each of which is prefixed by its length as a
+/// little-endian `u32`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LengthPrefixed;
+
+impl Codec for LengthPrefixed {
+    type Record = Vec<u8>;
+
+    fn read<R: BufRead>(&self, reader: &mut R) -> io::Result<Option<Self::Record>> {
+        let mut len = [0; 4];
+        if !read_record(reader, &mut len)? {
+            return Ok(None);
+        }
+        let mut record = vec![0; u32::from_le_bytes(len) as usize];
+        reader.read_exact(&mut record)?;
+        Ok(Some(record))
+    }
+
+    fn write<W: Write>(&self, writer: &mut W, record: &Self::Record) -> io::Result<()> {
+        let len = u32::try_from(record.len())
+            .map_err(|_| io::Error::new(ErrorKind::InvalidInput, "record too long"))?;
+        writer.write_all(&len.to_le_bytes())?;
+        writer.write_all(record)
+    }
+
+    fn size_of(&self, record: &Self::Record) -> usize {
+        mem::size_of::<Self::Record>() + record.capacity()
+    }
+}
+
+/// Fills `buf`, returns `false` at the end of the stream, or an error if
+/// the stream ends in the middle.
+fn read_record<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<bool> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(err) if err.kind() == ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    match filled {
+        0 => Ok(false),
+        n if n == buf.len() => Ok(true),
+        _ => Err(io::Error::new(ErrorKind::UnexpectedEof, "truncated record")),
+    }
+}
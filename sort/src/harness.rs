@@ -0,0 +1,356 @@
+//! Input generators and a harness which checks the sorts on them and
+//! measures the cost, see `benches/harness.rs`.
+//!
+//! Every algorithm sorts [`Item`]s by their keys through the same counting
+//! comparator, so the numbers are comparable between algorithms.
+//!
+//! The swaps can't be seen from the elements, as Rust moves them by bitwise
+//! copies, so the sorts of this crate count their own swaps, and the
+//! elements copied by their merges, on the running thread. std's sorts,
+//! which are measured for reference, report none.
+
+use crate::SliceSort;
+use rand::{rngs::StdRng, RngExt, SeedableRng};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    sync::atomic::{self, AtomicUsize},
+    time::{Duration, Instant},
+};
+
+/// An element to be sorted by `key`, which remembers its `index` in the
+/// input, so that the permutation and the stability can be checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Item {
+    pub key: u64,
+    pub index: usize,
+}
+
+/// A distribution of the keys of an input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Distribution {
+    /// Uniformly random keys.
+    Random,
+    /// `0, 1, 2, ...`
+    Sorted,
+    /// `n - 1, n - 2, ..., 0`
+    Reversed,
+    /// Ascending to the middle and then descending, `0, 1, 2, 1, 0`.
+    OrganPipe,
+    /// Ascending runs of `period` keys, `0, 1, 2, 0, 1, 2, ...`
+    Sawtooth { period: usize },
+    /// Random keys in `0..values`, so there are lots of duplicates.
+    FewUnique { values: u64 },
+    /// The same key everywhere.
+    AllEqual,
+    /// Sorted keys after `swaps` random pairs are swapped.
+    MostlySorted { swaps: usize },
+    /// McIlroy's "killer adversary" against the algorithm being measured,
+    /// see [`antiqsort`].
+    AntiQsort,
+}
+
+impl Distribution {
+    /// The distributions every algorithm is measured on.
+    pub const STANDARD: [Self; 9] = [
+        Self::Random,
+        Self::Sorted,
+        Self::Reversed,
+        Self::OrganPipe,
+        Self::Sawtooth { period: 1_000 },
+        Self::FewUnique { values: 16 },
+        Self::AllEqual,
+        Self::MostlySorted { swaps: 16 },
+        Self::AntiQsort,
+    ];
+
+    /// Generates `len` keys, the same ones for the same `seed`.
+    ///
+    /// As the killer adversary depends on the algorithm, [`Self::AntiQsort`]
+    /// generates the input against [`SliceSort::quick_sort_by`] here.
+    pub fn generate(self, len: usize, seed: u64) -> Vec<u64> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let n = len as u64;
+        match self {
+            Self::Random => (0..len).map(|_| rng.random()).collect(),
+            Self::Sorted => (0..n).collect(),
+            Self::Reversed => (0..n).rev().collect(),
+            Self::OrganPipe => (0..n).map(|v| v.min(n - 1 - v)).collect(),
+            Self::Sawtooth { period } => (0..n).map(|v| v % period.max(1) as u64).collect(),
+            Self::FewUnique { values } => (0..len)
+                .map(|_| rng.random_range(0..values.max(1)))
+                .collect(),
+            Self::AllEqual => vec![0; len],
+            Self::MostlySorted { swaps } => {
+                let mut vec = (0..n).collect::<Vec<_>>();
+                for _ in 0..swaps.min(len) {
+                    vec.swap(rng.random_range(0..len), rng.random_range(0..len));
+                }
+                vec
+            }
+            Self::AntiQsort => antiqsort(len, &ALGORITHMS[0]),
+        }
+    }
+}
+
+impl Display for Distribution {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Self::Random => write!(f, "random"),
+            Self::Sorted => write!(f, "sorted"),
+            Self::Reversed => write!(f, "reversed"),
+            Self::OrganPipe => write!(f, "organ-pipe"),
+            Self::Sawtooth { period } => write!(f, "sawtooth({period})"),
+            Self::FewUnique { values } => write!(f, "few-unique({values})"),
+            Self::AllEqual => write!(f, "all-equal"),
+            Self::MostlySorted { swaps } => write!(f, "mostly-sorted({swaps})"),
+            Self::AntiQsort => write!(f, "antiqsort"),
+        }
+    }
+}
+
+/// Generates McIlroy's "killer adversary" input of `len` keys against
+/// `algorithm`.
+///
+/// The algorithm sorts items whose keys are undecided ("gas") at first.
+/// When two gas items are compared, one of them is frozen to the next
+/// smallest key, preferring the one which was compared with a frozen item
+/// lately, as it's likely to be the pivot. The answers are consistent with
+/// the final keys, so sorting them again takes the same path, which is
+/// quadratic for a naive quick sort.
+pub fn antiqsort(len: usize, algorithm: &Algorithm) -> Vec<u64> {
+    let gas = len as u64;
+    let mut keys = vec![gas; len];
+    let (mut solid, mut candidate) = (0, 0);
+    let mut items = (0..len)
+        .map(|index| Item { key: 0, index })
+        .collect::<Vec<_>>();
+    (algorithm.sort)(&mut items, &mut |a, b| {
+        let (x, y) = (a.index, b.index);
+        if keys[x] == gas && keys[y] == gas {
+            keys[if x == candidate { x } else { y }] = solid;
+            solid += 1;
+        }
+        if keys[x] == gas {
+            candidate = x;
+        } else if keys[y] == gas {
+            candidate = y;
+        }
+        keys[x].cmp(&keys[y])
+    });
+    keys
+}
+
+thread_local! {
+    /// The swaps counted on this thread so far.
+    static SWAPS: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Counts `n` swaps on this thread for [`Report::swaps`], an element copied
+/// by a merge counts as one.
+#[inline]
+pub(crate) fn count_swaps(n: usize) {
+    SWAPS.with(|swaps| swaps.set(swaps.get() + n as u64));
+}
+
+/// Swaps `v[a]` and `v[b]`, and counts it.
+#[inline]
+pub(crate) fn swap<T>(v: &mut [T], a: usize, b: usize) {
+    v.swap(a, b);
+    count_swaps(1);
+}
+
+/// Sorts the items with the comparator.
+pub type SortFn = fn(&mut [Item], &mut dyn FnMut(&Item, &Item) -> Ordering);
+
+/// A comparison sort to be measured.
+#[derive(Clone, Copy)]
+pub struct Algorithm {
+    pub name: &'static str,
+    /// Whether the order of equal items must be kept.
+    pub stable: bool,
+    /// Whether it counts its swaps by the hooks of this crate.
+    pub counts_swaps: bool,
+    pub sort: SortFn,
+}
+
+/// The comparison sorts of this crate, and those of std for reference.
+pub const ALGORITHMS: [Algorithm; 6] = [
+    Algorithm {
+        name: "quick_sort",
+        stable: false,
+        counts_swaps: true,
+        sort: |v, compare| v.quick_sort_by(compare),
+    },
+    Algorithm {
+        name: "merge_sort",
+        stable: true,
+        counts_swaps: true,
+        sort: |v, compare| v.merge_sort_by(compare),
+    },
+    Algorithm {
+        name: "tim_sort",
+        stable: true,
+        counts_swaps: true,
+        sort: |v, compare| v.tim_sort_by(compare),
+    },
+    Algorithm {
+        name: "heap_sort",
+        stable: false,
+        counts_swaps: true,
+        sort: |v, compare| v.heap_sort_by(compare),
+    },
+    Algorithm {
+        name: "std sort",
+        stable: true,
+        counts_swaps: false,
+        sort: |v, compare| v.sort_by(compare),
+    },
+    Algorithm {
+        name: "std sort_unstable",
+        stable: false,
+        counts_swaps: false,
+        sort: |v, compare| v.sort_unstable_by(compare),
+    },
+];
+
+/// What's wrong with the output of a sort, the fields are its indices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Failure {
+    /// The item at `index` is less than the one before it.
+    Unsorted { index: usize },
+    /// The item at `index` is lost, duplicated or altered.
+    NotPermutation { index: usize },
+    /// The item at `index` is equal to the one before it, but came first.
+    Unstable { index: usize },
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Self::Unsorted { index } => write!(f, "item {index} is out of order"),
+            Self::NotPermutation { index } => write!(f, "item {index} is not from the input"),
+            Self::Unstable { index } => write!(f, "item {index} is swapped with an equal one"),
+        }
+    }
+}
+
+impl std::error::Error for Failure {}
+
+/// Checks that `output` is the items of `input` sorted by the keys, and
+/// that equal items keep their order if `stable`.
+pub fn verify(input: &[u64], output: &[Item], stable: bool) -> Result<(), Failure> {
+    let mut seen = vec![false; input.len()];
+    for (index, item) in output.iter().enumerate() {
+        if input.get(item.index) != Some(&item.key) || seen[item.index] {
+            return Err(Failure::NotPermutation { index });
+        }
+        seen[item.index] = true;
+    }
+    if output.len() != input.len() {
+        return Err(Failure::NotPermutation {
+            index: output.len(),
+        });
+    }
+    for (index, pair) in output.windows(2).enumerate() {
+        match pair[1].key.cmp(&pair[0].key) {
+            Ordering::Less => return Err(Failure::Unsorted { index: index + 1 }),
+            Ordering::Equal if stable && pair[1].index < pair[0].index => {
+                return Err(Failure::Unstable { index: index + 1 })
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// The result of an algorithm on an input.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub algorithm: &'static str,
+    pub distribution: Distribution,
+    pub len: usize,
+    pub comparisons: u64,
+    /// The swaps, where an element copied by a merge counts as one, if the
+    /// algorithm counts them.
+    pub swaps: Option<u64>,
+    /// The allocations and the bytes allocated, both are zero unless
+    /// [`CountingAlloc`] is the global allocator.
+    pub allocations: usize,
+    pub bytes: usize,
+    /// The time includes counting the comparisons.
+    pub time: Duration,
+    pub result: Result<(), Failure>,
+}
+
+/// Sorts `len` keys of `distribution` by `algorithm`, and reports.
+pub fn run(algorithm: &Algorithm, distribution: Distribution, len: usize, seed: u64) -> Report {
+    let input = match distribution {
+        Distribution::AntiQsort => antiqsort(len, algorithm),
+        _ => distribution.generate(len, seed),
+    };
+    let mut items = input
+        .iter()
+        .enumerate()
+        .map(|(index, &key)| Item { key, index })
+        .collect::<Vec<_>>();
+    let mut comparisons = 0;
+    let swaps = SWAPS.get();
+    let (allocations, bytes) = CountingAlloc::stats();
+    let now = Instant::now();
+    (algorithm.sort)(&mut items, &mut |a, b| {
+        comparisons += 1;
+        a.key.cmp(&b.key)
+    });
+    let time = now.elapsed();
+    let stats = CountingAlloc::stats();
+    Report {
+        algorithm: algorithm.name,
+        distribution,
+        len,
+        comparisons,
+        swaps: algorithm.counts_swaps.then(|| SWAPS.get() - swaps),
+        allocations: stats.0 - allocations,
+        bytes: stats.1 - bytes,
+        time,
+        result: verify(&input, &items, algorithm.stable),
+    }
+}
+
+/// The system allocator, which also counts the allocations and bytes.
+///
+/// It must be installed by `#[global_allocator]` in a binary to count.
+pub struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+
+impl CountingAlloc {
+    /// The allocations and the bytes allocated so far, by all threads.
+    pub fn stats() -> (usize, usize) {
+        (
+            ALLOCATIONS.load(atomic::Ordering::Relaxed),
+            BYTES.load(atomic::Ordering::Relaxed),
+        )
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, atomic::Ordering::Relaxed);
+        BYTES.fetch_add(layout.size(), atomic::Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, atomic::Ordering::Relaxed);
+        BYTES.fetch_add(new_size, atomic::Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
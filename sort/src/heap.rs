@@ -0,0 +1,304 @@
+//! Heap sort and a d-ary heap with handles.
+//!
+//! The heaps here are slices where the children of `v[i]` are
+//! `v[D * i + 1..=D * i + D]`. The sifting functions keep `v` a max-heap by
+//! `is_less`, a min-heap is the max-heap by the reversed order.
+
+use crate::harness;
+use std::fmt::{self, Debug, Formatter};
+
+/// The arity of the heap of [`crate::SliceSort::heap_sort`], four children
+/// per node make the heap shallower, and they share a cache line mostly.
+pub(crate) const SORT_ARITY: usize = 4;
+
+/// Moves `v[node]` up the max-heap `v` until it's not greater than its
+/// parent, and returns where it ends up.
+pub(crate) fn sift_up<T, F, const D: usize>(v: &mut [T], mut node: usize, is_less: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    while node > 0 {
+        let parent = (node - 1) / D;
+        if !is_less(&v[parent], &v[node]) {
+            break;
+        }
+        harness::swap(v, parent, node);
+        node = parent;
+    }
+    node
+}
+
+/// Moves `v[node]` down the max-heap `v` until it's not less than its
+/// children, and returns where it ends up.
+pub(crate) fn sift_down<T, F, const D: usize>(
+    v: &mut [T],
+    mut node: usize,
+    is_less: &mut F,
+) -> usize
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    loop {
+        let first = D * node + 1;
+        if first >= v.len() {
+            break;
+        }
+        let mut child = first;
+        for next in first + 1..(first + D).min(v.len()) {
+            if is_less(&v[child], &v[next]) {
+                child = next;
+            }
+        }
+        if !is_less(&v[node], &v[child]) {
+            break;
+        }
+        harness::swap(v, node, child);
+        node = child;
+    }
+    node
+}
+
+/// Turns `v` into a max-heap from the last parent up.
+fn build<T, F, const D: usize>(v: &mut [T], is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    if v.len() > 1 {
+        for node in (0..=(v.len() - 2) / D).rev() {
+            sift_down::<T, F, D>(v, node, is_less);
+        }
+    }
+}
+
+/// Sorts `v` by moving the greatest one of the max-heap `v[..end]` to the
+/// end, for `end` from `len` down to `1`.
+fn sort_heap<T, F, const D: usize>(v: &mut [T], is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    for end in (1..v.len()).rev() {
+        harness::swap(v, 0, end);
+        sift_down::<T, F, D>(&mut v[..end], 0, is_less);
+    }
+}
+
+/// Heap sort with `D` children per node, see [`crate::SliceSort::heap_sort`].
+///
+/// It guarantees O(n log n) without any extra memory, so it's also the
+/// fallback of the quick sort.
+pub(crate) fn heap_sort<T, F, const D: usize>(v: &mut [T], is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    build::<T, F, D>(v, is_less);
+    sort_heap::<T, F, D>(v, is_less);
+}
+
+/// Identifies an item of a [`DaryHeap`], to look it up or decrease it.
+///
+/// The slot of a popped item is reused by a later one, but under a new
+/// generation, so the handles of popped items never see the new ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Handle {
+    slot: usize,
+    generation: usize,
+}
+
+/// Where the item of a handle is.
+#[derive(Debug, Clone, Copy)]
+struct Slot {
+    /// The position in the heap, meaningless while the slot is free.
+    node: usize,
+    /// Bumped when the item is popped.
+    generation: usize,
+}
+
+/// A min-heap with `D` children per node, whose items can be decreased via
+/// their handles, e.g. for Dijkstra's algorithm.
+///
+/// A larger `D` makes pushing and decreasing faster, and popping slower.
+/// The slots of the handles are reused, so the memory follows the most
+/// items the heap has had at once, not the number of pushes.
+///
+/// # Examples
+///
+/// ```
+/// use sort::DaryHeap;
+///
+/// let mut heap = DaryHeap::<_, 4>::heapify(vec![(5, 'a'), (3, 'b'), (8, 'c')]);
+/// let d = heap.push((6, 'd'));
+/// heap.decrease_key(d, (1, 'd'));
+/// assert_eq!(heap.pop(), Some((1, 'd')));
+/// assert_eq!(heap.get(d), None);
+/// assert_eq!(heap.peek(), Some(&(3, 'b')));
+/// assert_eq!(heap.into_sorted_vec(), [(3, 'b'), (5, 'a'), (8, 'c')]);
+/// ```
+#[derive(Clone)]
+pub struct DaryHeap<T, const D: usize = 2> {
+    /// The heap of the items with their handles.
+    data: Vec<(Handle, T)>,
+    /// The slot of each handle.
+    slots: Vec<Slot>,
+    /// The slots of the popped items.
+    free: Vec<usize>,
+}
+
+impl<T: Ord, const D: usize> DaryHeap<T, D> {
+    /// Creates an empty heap.
+    pub fn new() -> Self {
+        const { assert!(D >= 2, "a heap needs at least two children per node") };
+        Self {
+            data: Vec::new(),
+            slots: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+
+    /// Creates a heap of the items of `vec` in O(n) time.
+    pub fn heapify(vec: Vec<T>) -> Self {
+        Self::heapify_with_handles(vec).0
+    }
+
+    /// Creates a heap of the items of `vec` in O(n) time, and returns the
+    /// handle of each item of `vec` as well.
+    pub fn heapify_with_handles(vec: Vec<T>) -> (Self, Vec<Handle>) {
+        let mut heap = Self::new();
+        let handles = (0..vec.len())
+            .map(|slot| Handle {
+                slot,
+                generation: 0,
+            })
+            .collect::<Vec<_>>();
+        heap.data = handles.iter().copied().zip(vec).collect();
+        build::<_, _, D>(&mut heap.data, &mut Self::is_greater);
+        heap.slots = vec![
+            Slot {
+                node: 0,
+                generation: 0
+            };
+            heap.data.len()
+        ];
+        for (i, &(handle, _)) in heap.data.iter().enumerate() {
+            heap.slots[handle.slot].node = i;
+        }
+        (heap, handles)
+    }
+
+    /// The order of the max-heap, which is the reversed one of the items.
+    fn is_greater(a: &(Handle, T), b: &(Handle, T)) -> bool {
+        b.1 < a.1
+    }
+
+    /// Updates the positions along the path from `node` up to `top`, after
+    /// a sifting between them.
+    fn relocate(&mut self, mut node: usize, top: usize) {
+        loop {
+            self.slots[self.data[node].0.slot].node = node;
+            if node == top {
+                break;
+            }
+            node = (node - 1) / D;
+        }
+    }
+
+    /// The position of the item of `handle`, unless it's popped.
+    fn node(&self, handle: Handle) -> Option<usize> {
+        let slot = self.slots.get(handle.slot)?;
+        (slot.generation == handle.generation).then_some(slot.node)
+    }
+
+    /// Pushes `item`, and returns its handle.
+    pub fn push(&mut self, item: T) -> Handle {
+        let node = self.data.len();
+        let slot = self.free.pop().unwrap_or_else(|| {
+            self.slots.push(Slot {
+                node,
+                generation: 0,
+            });
+            self.slots.len() - 1
+        });
+        let handle = Handle {
+            slot,
+            generation: self.slots[slot].generation,
+        };
+        self.data.push((handle, item));
+        self.slots[slot].node = node;
+        let top = sift_up::<_, _, D>(&mut self.data, node, &mut Self::is_greater);
+        self.relocate(node, top);
+        handle
+    }
+
+    /// The least item, if any.
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first().map(|(_, item)| item)
+    }
+
+    /// Removes and returns the least item, if any.
+    pub fn pop(&mut self) -> Option<T> {
+        let last = self.data.len().checked_sub(1)?;
+        self.data.swap(0, last);
+        let (handle, item) = self.data.pop()?;
+        self.slots[handle.slot].generation += 1;
+        self.free.push(handle.slot);
+        if !self.data.is_empty() {
+            let node = sift_down::<_, _, D>(&mut self.data, 0, &mut Self::is_greater);
+            self.relocate(node, 0);
+        }
+        Some(item)
+    }
+
+    /// The item of `handle`, unless it's popped.
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.node(handle).map(|node| &self.data[node].1)
+    }
+
+    /// Replaces the item of `handle` by the not greater `item`.
+    ///
+    /// Panics if the item is popped, or `item` is greater than it.
+    pub fn decrease_key(&mut self, handle: Handle, item: T) {
+        let node = self.node(handle).expect("the item is not in the heap");
+        assert!(item <= self.data[node].1, "the new item is greater");
+        self.data[node].1 = item;
+        let top = sift_up::<_, _, D>(&mut self.data, node, &mut Self::is_greater);
+        self.relocate(node, top);
+    }
+
+    /// Returns the items in ascending order.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        // The least one goes to the back each time.
+        sort_heap::<_, _, D>(&mut self.data, &mut Self::is_greater);
+        self.data.into_iter().rev().map(|(_, item)| item).collect()
+    }
+}
+
+impl<T, const D: usize> DaryHeap<T, D> {
+    /// The number of items in the heap.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Whether the heap has no item.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
+
+impl<T: Ord, const D: usize> Default for DaryHeap<T, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord, const D: usize> From<Vec<T>> for DaryHeap<T, D> {
+    fn from(vec: Vec<T>) -> Self {
+        Self::heapify(vec)
+    }
+}
+
+impl<T: Debug, const D: usize> Debug for DaryHeap<T, D> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_list()
+            .entries(self.data.iter().map(|(_, item)| item))
+            .finish()
+    }
+}
@@ -1,101 +1,130 @@
-#![feature(is_sorted)]
+pub mod external;
+pub mod harness;
+pub mod select;
+
+mod heap;
+mod merge;
+mod order;
+mod parallel;
+mod quick;
+mod radix;
+mod slice;
+mod tim;
+
+pub use heap::{DaryHeap, Handle};
+pub use order::{by_less, by_less_equal, OrderViolation};
+pub use parallel::ParallelConfig;
+pub use radix::RadixKey;
+pub use slice::SliceSort;
+use std::cmp::Ordering;
 
 pub struct Solution;
 
 impl Solution {
     /// Quick Sort Algorithm
     ///
-    /// We compare between the first index and the last index. If not compared,
-    /// swap the elements under indices and move a step. After a rotation, the
-    /// first index should equal the last index. Then we sort the left part and
-    /// the right part with same method. Noted that if the src too big it will
-    /// overflow the stack.
-    pub fn quick_sort<F, T>(src: &Vec<T>, cmp: F) -> Vec<T>
+    /// We take the median of three (or the median of three medians for long
+    /// slices) as the pivot, partition around it, then recurse into the
+    /// shorter part and loop on the longer one, so the stack depth is only
+    /// O(log n). Short parts are sorted by insertion, sorted or reversed
+    /// runs are detected and finished in linear time, and after too many
+    /// unbalanced partitions the rest falls back to heap sort, so the worst
+    /// case is O(n log n).
+    ///
+    /// This is a wrapper of [`SliceSort::quick_sort_by`] on a copy of `src`.
+    /// Predicates of the old `bool` form can be adapted by [`by_less`] or
+    /// [`by_less_equal`].
+    pub fn quick_sort<F, T>(src: &[T], compare: F) -> Vec<T>
     where
-        F: Copy + FnMut(&T, &T) -> bool,
+        F: FnMut(&T, &T) -> Ordering,
         T: Clone,
     {
-        fn sort<F, T>(dist: &mut Vec<T>, mut cmp: F, (left, right): (usize, usize))
-        where
-            F: Copy + FnMut(&T, &T) -> bool,
-            T: Clone,
-        {
-            let (mut idx1, mut idx2) = (left, right);
-            while idx1 < idx2 {
-                while idx1 < idx2 && cmp(&dist[idx1], &dist[idx2]) {
-                    idx1 += 1;
-                }
-                if idx1 < idx2 {
-                    dist.swap(idx1, idx2);
-                    idx2 -= 1;
-                }
-                while idx1 < idx2 && cmp(&dist[idx1], &dist[idx2]) {
-                    idx2 -= 1;
-                }
-                if idx1 < idx2 {
-                    dist.swap(idx1, idx2);
-                    idx1 += 1;
-                }
-            }
-            if left + 1 < idx2 {
-                sort(dist, cmp, (left, idx2 - 1));
-            }
-            if idx1 + 1 < right {
-                sort(dist, cmp, (idx1 + 1, right));
-            }
-        }
-        let mut dist = src.clone();
-        (src.len() > 0).then(|| sort(&mut dist, cmp, (0, src.len() - 1)));
+        let mut dist = src.to_vec();
+        dist.quick_sort_by(compare);
         dist
     }
 
     /// Merge Sort Algorithm
     ///
-    /// We split the origin to be sorted in two parts, and then
-    /// split each part in two part, and so on until the length
-    /// of each part isn't greater than 1. Then we take elements
-    /// in place for merging.
-    pub fn merge_sort<F, T>(src: &Vec<T>, cmp: F) -> Vec<T>
+    /// We split the slice in two halves and sort each of them, until a
+    /// part is short enough for insertion. Then the left half is copied out
+    /// to a buffer of half the length and merged back with the right half,
+    /// so the elements are moved rather than cloned, and the sort is stable.
+    ///
+    /// This is a wrapper of [`SliceSort::merge_sort_by`] on a copy of `src`,
+    /// which sorts it in place.
+    /// Predicates of the old `bool` form can be adapted by [`by_less`] or
+    /// [`by_less_equal`].
+    pub fn merge_sort<F, T>(src: &[T], compare: F) -> Vec<T>
     where
-        F: Copy + FnMut(&T, &T) -> bool,
+        F: FnMut(&T, &T) -> Ordering,
         T: Clone,
     {
-        fn sort<F, T>((part1, part2): (&[T], &[T]), mut cmp: F) -> Vec<T>
-        where
-            F: Copy + FnMut(&T, &T) -> bool,
-            T: Clone,
-        {
-            let sort = |part: &[T]| {
-                if part.len() > 1 {
-                    sort(part.split_at(part.len() >> 1), cmp)
-                } else {
-                    part.iter().map(|val| val.clone()).collect::<Vec<_>>()
-                }
-            };
-            let part1 = sort(part1);
-            let part2 = sort(part2);
-            let mut dist = Vec::with_capacity(part1.len() + part2.len());
-            let mut take = |src: &Vec<T>, idx: &mut usize| {
-                dist.push(src[*idx].clone());
-                *idx += 1;
-            };
-            let (mut idx1, mut idx2) = (0, 0);
-            while idx1 < part1.len() && idx2 < part2.len() {
-                if cmp(&part1[idx1], &part2[idx2]) {
-                    take(&part1, &mut idx1);
-                } else {
-                    take(&part2, &mut idx2);
-                }
-            }
-            while idx1 < part1.len() {
-                take(&part1, &mut idx1);
-            }
-            while idx2 < part2.len() {
-                take(&part2, &mut idx2);
-            }
-            dist
-        }
-        sort(src.split_at(src.len() >> 1), cmp)
+        let mut dist = src.to_vec();
+        dist.merge_sort_by(compare);
+        dist
+    }
+
+    /// TimSort Algorithm
+    ///
+    /// We scan for the natural runs, reverse the descending ones, and
+    /// extend the short ones by insertion. The runs are merged while they
+    /// are pushed to a stack, and merging switches to galloping (an
+    /// exponential search) when one run keeps winning. Only a buffer of
+    /// half the length is allocated, and the elements are never cloned.
+    ///
+    /// This is a wrapper of [`SliceSort::tim_sort_by`] on a copy of `src`.
+    pub fn tim_sort<F, T>(src: &[T], compare: F) -> Vec<T>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        T: Clone,
+    {
+        let mut dist = src.to_vec();
+        dist.tim_sort_by(compare);
+        dist
+    }
+
+    /// Heap Sort Algorithm
+    ///
+    /// We build a max-heap of four children per node in place, then swap
+    /// the greatest one to the end and sift the new root down, again and
+    /// again. It's O(n log n) in the worst case without any extra memory,
+    /// but not stable.
+    ///
+    /// This is a wrapper of [`SliceSort::heap_sort_by`] on a copy of `src`.
+    pub fn heap_sort<F, T>(src: &[T], compare: F) -> Vec<T>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        T: Clone,
+    {
+        let mut dist = src.to_vec();
+        dist.heap_sort_by(compare);
+        dist
+    }
+
+    /// Sorts `v` in place with a comparator, the sort is stable.
+    pub fn sort_by<F, T>(v: &mut [T], compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        v.merge_sort_by(compare);
+    }
+
+    /// Sorts `v` in place with a key extraction function, the sort is stable.
+    pub fn sort_by_key<K, F, T>(v: &mut [T], f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        v.merge_sort_by_key(f);
+    }
+
+    /// Sorts `v` in place with a comparator, the sort is unstable.
+    pub fn sort_unstable_by<F, T>(v: &mut [T], compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        v.quick_sort_by(compare);
     }
 }
 
@@ -0,0 +1,115 @@
+use crate::harness;
+use std::{mem::MaybeUninit, ptr};
+
+/// Runs shorter than this are sorted by insertion instead of merging.
+const INSERTION_THRESHOLD: usize = 20;
+
+/// In-place stable merge sort, see [`crate::Solution::merge_sort`].
+///
+/// The elements are moved by bitwise copies instead of cloning, so `T`
+/// doesn't need to be `Clone`. Only the left half is copied out for each
+/// merging, so a single buffer of `len / 2` elements is enough.
+pub(crate) fn merge_sort<T, F>(v: &mut [T], is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    fn sort<T, F>(v: &mut [T], buf: &mut [MaybeUninit<T>], is_less: &mut F)
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        let len = v.len();
+        if len <= INSERTION_THRESHOLD {
+            insertion_sort(v, is_less);
+            return;
+        }
+        let mid = len >> 1;
+        sort(&mut v[..mid], buf, is_less);
+        sort(&mut v[mid..], buf, is_less);
+        if is_less(&v[mid], &v[mid - 1]) {
+            // SAFETY: `mid <= len / 2` fits in the buffer.
+            unsafe { merge(v, mid, buf, is_less) };
+        }
+    }
+    if v.len() > 1 {
+        let mut buf = Vec::with_capacity(v.len() >> 1);
+        sort(v, buf.spare_capacity_mut(), is_less);
+    }
+}
+
+/// Stable insertion sort by adjacent swaps.
+pub(crate) fn insertion_sort<T, F>(v: &mut [T], is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    for i in 1..v.len() {
+        let mut j = i;
+        while j > 0 && is_less(&v[j], &v[j - 1]) {
+            harness::swap(v, j, j - 1);
+            j -= 1;
+        }
+    }
+}
+
+/// Merges the sorted runs `v[..mid]` and `v[mid..]` in place.
+///
+/// The left run is copied into `buf`, then the output is written from the
+/// front of `v`, which never catches up with the unread part of the right
+/// run. If `is_less` panics, the [`MergeHole`] copies the rest of the left
+/// run back, so `v` always ends up with every element exactly once.
+///
+/// # Safety
+///
+/// `buf` must be able to hold `mid` elements.
+pub(crate) unsafe fn merge<T, F>(
+    v: &mut [T],
+    mid: usize,
+    buf: &mut [MaybeUninit<T>],
+    is_less: &mut F,
+) where
+    F: FnMut(&T, &T) -> bool,
+{
+    debug_assert!(mid <= buf.len() && mid <= v.len());
+    let len = v.len();
+    let v = v.as_mut_ptr();
+    let buf = buf.as_mut_ptr() as *mut T;
+    unsafe {
+        ptr::copy_nonoverlapping(v, buf, mid);
+        harness::count_swaps(mid);
+        let mut hole = MergeHole {
+            start: buf,
+            end: buf.add(mid),
+            dest: v,
+        };
+        let (mut right, end) = (v.add(mid), v.add(len));
+        while hole.start < hole.end && right < end {
+            let src = if is_less(&*right, &*hole.start) {
+                right = right.add(1);
+                right.sub(1)
+            } else {
+                hole.start = hole.start.add(1);
+                hole.start.sub(1)
+            };
+            ptr::copy_nonoverlapping(src, hole.dest, 1);
+            harness::count_swaps(1);
+            hole.dest = hole.dest.add(1);
+        }
+    }
+}
+
+/// The rest of the buffered run, which is copied to `dest` when dropped.
+pub(crate) struct MergeHole<T> {
+    pub(crate) start: *mut T,
+    pub(crate) end: *mut T,
+    pub(crate) dest: *mut T,
+}
+
+impl<T> Drop for MergeHole<T> {
+    fn drop(&mut self) {
+        // SAFETY: `dest..dest + len` is exactly the gap left in the slice.
+        unsafe {
+            let len = self.end.offset_from(self.start) as usize;
+            ptr::copy_nonoverlapping(self.start, self.dest, len);
+            harness::count_swaps(len);
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use std::{
+    cell::Cell,
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+};
+
+/// Adapts a strict "less than" predicate to a comparator.
+///
+/// `a` is less than `b` if `is_less(a, b)`, greater if `is_less(b, a)`,
+/// and equal otherwise.
+pub fn by_less<T, F>(mut is_less: F) -> impl FnMut(&T, &T) -> Ordering
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    move |a, b| {
+        if is_less(a, b) {
+            Ordering::Less
+        } else if is_less(b, a) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+}
+
+/// Adapts a "less than or equal" predicate to a comparator, which is the
+/// form of predicate the sorts used to take.
+///
+/// `a` is less than `b` if `b` can't be placed before `a`, greater if `a`
+/// can't be placed before `b`, and equal otherwise.
+pub fn by_less_equal<T, F>(mut is_less_equal: F) -> impl FnMut(&T, &T) -> Ordering
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    move |a, b| {
+        if !is_less_equal(b, a) {
+            Ordering::Less
+        } else if !is_less_equal(a, b) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+}
+
+/// The comparator is not a strict weak order, so there is no right answer
+/// for sorting. The fields are indices into the slice before sorting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderViolation {
+    /// `a` doesn't compare equal to itself.
+    Irreflexive { a: usize },
+    /// `a` compared to `b` is not the reverse of `b` compared to `a`.
+    Asymmetric { a: usize, b: usize },
+    /// `a <= b` and `b <= c`, but `a` compared to `c` doesn't agree, i.e.
+    /// it's not `a == c` when both are equal, or not `a < c` otherwise.
+    Intransitive { a: usize, b: usize, c: usize },
+}
+
+impl Display for OrderViolation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Self::Irreflexive { a } => write!(f, "element {a} is not equal to itself"),
+            Self::Asymmetric { a, b } => {
+                write!(
+                    f,
+                    "comparing {a} with {b} is not the reverse of {b} with {a}"
+                )
+            }
+            Self::Intransitive { a, b, c } => {
+                write!(
+                    f,
+                    "comparing {a} with {c} contradicts {a} to {b} and {b} to {c}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for OrderViolation {}
+
+/// Stable sort which validates `compare` on the way, see
+/// [`crate::SliceSort::checked_sort_by`].
+///
+/// The indices are sorted by insertion, so every adjacent pair of the
+/// result was compared directly. If all the elements are related as the
+/// result says, `compare` is a strict weak order on them; otherwise the
+/// first mismatch and its left neighbour witness the intransitivity.
+pub(crate) fn checked_sort<T, F>(v: &mut [T], mut compare: F) -> Result<(), OrderViolation>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let violation = Cell::new(None);
+    let report = |found| {
+        if violation.get().is_none() {
+            violation.set(Some(found));
+        }
+    };
+    let mut compare = |a: usize, b: usize| {
+        let order = compare(&v[a], &v[b]);
+        if a == b && order != Ordering::Equal {
+            report(OrderViolation::Irreflexive { a });
+        } else if compare(&v[b], &v[a]) != order.reverse() {
+            report(OrderViolation::Asymmetric { a, b });
+        }
+        order
+    };
+    let len = v.len();
+    for a in 0..len {
+        compare(a, a);
+    }
+    let mut idx = (0..len).collect::<Vec<_>>();
+    for i in 1..len {
+        let mut j = i;
+        while j > 0 && compare(idx[j], idx[j - 1]) == Ordering::Less {
+            idx.swap(j, j - 1);
+            j -= 1;
+        }
+    }
+    let adjacent = idx
+        .windows(2)
+        .map(|pair| compare(pair[0], pair[1]))
+        .collect::<Vec<_>>();
+    if let Some(violation) = violation.get() {
+        return Err(violation);
+    }
+    for i in 0..len {
+        let mut expect = Ordering::Equal;
+        for j in i + 1..len {
+            expect = expect.min(adjacent[j - 1]);
+            if j > i + 1 && compare(idx[i], idx[j]) != expect {
+                return Err(OrderViolation::Intransitive {
+                    a: idx[i],
+                    b: idx[j - 1],
+                    c: idx[j],
+                });
+            }
+        }
+    }
+    if let Some(violation) = violation.get() {
+        return Err(violation);
+    }
+    permute(v, idx);
+    Ok(())
+}
+
+/// Moves `v[idx[i]]` to `v[i]` for every `i`, where `idx` is a permutation.
+///
+/// Each element is swapped into place once; an index below `i` has been
+/// swapped away already, so the chain is followed to where it went.
+pub(crate) fn permute<T>(v: &mut [T], mut idx: Vec<usize>) {
+    for i in 0..v.len() {
+        let mut j = idx[i];
+        while j < i {
+            j = idx[j];
+        }
+        v.swap(i, j);
+        idx[i] = j;
+    }
+}
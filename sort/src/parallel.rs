@@ -0,0 +1,222 @@
+use crate::{quick, tim};
+use std::{mem::MaybeUninit, panic, ptr, thread};
+
+/// Options of the parallel sorts.
+///
+/// A slice is split among at most `threads` threads, and a part not longer
+/// than `cutoff` is always sorted sequentially. The result never depends on
+/// the options, so a stable parallel sort gives the same output as the
+/// sequential one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParallelConfig {
+    pub threads: usize,
+    pub cutoff: usize,
+}
+
+impl Default for ParallelConfig {
+    /// As many threads as the available parallelism, and a cutoff of 16K.
+    fn default() -> Self {
+        Self {
+            threads: thread::available_parallelism().map_or(1, |n| n.get()),
+            cutoff: 1 << 14,
+        }
+    }
+}
+
+impl ParallelConfig {
+    /// Whether a slice of `len` elements should be sorted sequentially.
+    fn sequential(&self, len: usize) -> bool {
+        self.threads <= 1 || len <= self.cutoff.max(MIN_CUTOFF)
+    }
+
+    /// Splits the threads for two halves of the work.
+    fn split(&self) -> (Self, Self) {
+        let half = self.threads / 2;
+        (
+            Self {
+                threads: half,
+                ..*self
+            },
+            Self {
+                threads: self.threads - half,
+                ..*self
+            },
+        )
+    }
+}
+
+/// Parts shorter than this are never worth a thread.
+const MIN_CUTOFF: usize = 1 << 10;
+
+/// Parallel quick sort, see [`crate::SliceSort::par_quick_sort_by`].
+///
+/// The slice is partitioned by all the threads, then the two parts are
+/// sorted by half of the threads each. Once a part runs out of threads or
+/// gets short, it's handed over to the sequential quick sort.
+pub(crate) fn par_quick_sort<T, F>(v: &mut [T], is_less: &F, config: ParallelConfig)
+where
+    F: Fn(&T, &T) -> bool + Sync,
+    T: Send + Sync,
+{
+    if config.sequential(v.len()) {
+        quick::quick_sort(v, &mut |a, b| is_less(a, b));
+        return;
+    }
+    let (pivot, _) = quick::choose_pivot(v, &mut |a, b| is_less(a, b));
+    let mid = par_partition(v, pivot, is_less, config.threads);
+    let (left, right) = v.split_at_mut(mid);
+    let (left_config, right_config) = config.split();
+    thread::scope(|scope| {
+        scope.spawn(|| par_quick_sort(left, is_less, left_config));
+        par_quick_sort(&mut right[1..], is_less, right_config);
+    });
+}
+
+/// Partitions `v` around `v[pivot]` like the sequential partition, and
+/// returns the new index of the pivot.
+///
+/// Each thread partitions a chunk on its own, then the elements on the
+/// wrong side of the final pivot index are swapped pairwise.
+fn par_partition<T, F>(v: &mut [T], pivot: usize, is_less: &F, threads: usize) -> usize
+where
+    F: Fn(&T, &T) -> bool + Sync,
+    T: Send + Sync,
+{
+    v.swap(0, pivot);
+    let (pivot, rest) = v.split_first_mut().unwrap();
+    let pivot = &*pivot;
+    let chunk = rest.len().div_ceil(threads);
+    let counts = thread::scope(|scope| {
+        let handles = rest
+            .chunks_mut(chunk)
+            .map(|part| {
+                scope.spawn(move || {
+                    let mut less = 0;
+                    for i in 0..part.len() {
+                        if is_less(&part[i], pivot) {
+                            part.swap(less, i);
+                            less += 1;
+                        }
+                    }
+                    less
+                })
+            })
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|err| panic::resume_unwind(err))
+            })
+            .collect::<Vec<_>>()
+    });
+    let mid = counts.iter().sum();
+    let (mut misplaced_less, mut misplaced_greater) = (Vec::new(), Vec::new());
+    for (idx, &less) in counts.iter().enumerate() {
+        let start = idx * chunk;
+        let end = (start + chunk).min(rest.len());
+        misplaced_less.push(start.max(mid)..start + less);
+        misplaced_greater.push(start + less..end.min(mid));
+    }
+    let misplaced_less = misplaced_less.into_iter().flatten();
+    let misplaced_greater = misplaced_greater.into_iter().flatten();
+    for (a, b) in misplaced_less.zip(misplaced_greater) {
+        rest.swap(a, b);
+    }
+    v.swap(0, mid);
+    mid
+}
+
+/// Parallel stable merge sort, see [`crate::SliceSort::par_merge_sort_by`].
+///
+/// The two halves are sorted by half of the threads each, and then merged
+/// in parallel into a buffer and copied back. The elements are only copied
+/// out while merging, so if `is_less` panics, `v` still holds every element
+/// exactly once.
+pub(crate) fn par_merge_sort<T, F>(v: &mut [T], is_less: &F, config: ParallelConfig)
+where
+    F: Fn(&T, &T) -> bool + Sync,
+    T: Send + Sync,
+{
+    if config.sequential(v.len()) {
+        tim::tim_sort(v, &mut |a, b| is_less(a, b));
+        return;
+    }
+    let mut buf = Vec::with_capacity(v.len());
+    sort(v, buf.spare_capacity_mut(), is_less, config);
+}
+
+fn sort<T, F>(v: &mut [T], buf: &mut [MaybeUninit<T>], is_less: &F, config: ParallelConfig)
+where
+    F: Fn(&T, &T) -> bool + Sync,
+    T: Send + Sync,
+{
+    if config.sequential(v.len()) {
+        tim::tim_sort(v, &mut |a, b| is_less(a, b));
+        return;
+    }
+    let mid = v.len() / 2;
+    let (left_config, right_config) = config.split();
+    {
+        let (left, right) = v.split_at_mut(mid);
+        let (left_buf, right_buf) = buf.split_at_mut(mid);
+        thread::scope(|scope| {
+            scope.spawn(|| sort(left, left_buf, is_less, left_config));
+            sort(right, right_buf, is_less, right_config);
+        });
+    }
+    let (left, right) = v.split_at(mid);
+    let buf = &mut buf[..v.len()];
+    par_merge(left, right, buf, is_less, config);
+    // SAFETY: `buf` holds a permutation of the elements of `v`, which are
+    // overwritten without being dropped, and `buf` never drops them.
+    unsafe { ptr::copy_nonoverlapping(buf.as_ptr() as *const T, v.as_mut_ptr(), v.len()) };
+}
+
+/// Copies the merging of the sorted `a` and `b` into `dst`.
+///
+/// The longer run is split in the middle, and the other one is split by a
+/// binary search, so that both halves can be merged in parallel.
+fn par_merge<T, F>(
+    a: &[T],
+    b: &[T],
+    dst: &mut [MaybeUninit<T>],
+    is_less: &F,
+    config: ParallelConfig,
+) where
+    F: Fn(&T, &T) -> bool + Sync,
+    T: Send + Sync,
+{
+    if config.sequential(a.len() + b.len()) {
+        let (mut i, mut j) = (0, 0);
+        for slot in dst {
+            let src = if i == a.len() || (j < b.len() && is_less(&b[j], &a[i])) {
+                j += 1;
+                &b[j - 1]
+            } else {
+                i += 1;
+                &a[i - 1]
+            };
+            // SAFETY: the copy lives in `dst`, which never drops it.
+            slot.write(unsafe { ptr::read(src) });
+        }
+        return;
+    }
+    // Equal elements of `a` always go before those of `b`.
+    let (i, j) = if a.len() >= b.len() {
+        let i = a.len() / 2;
+        (i, b.partition_point(|x| is_less(x, &a[i])))
+    } else {
+        let j = b.len() / 2;
+        (a.partition_point(|x| !is_less(&b[j], x)), j)
+    };
+    let (a_left, a_right) = a.split_at(i);
+    let (b_left, b_right) = b.split_at(j);
+    let (left, right) = dst.split_at_mut(i + j);
+    let (left_config, right_config) = config.split();
+    thread::scope(|scope| {
+        scope.spawn(|| par_merge(a_left, b_left, left, is_less, left_config));
+        par_merge(a_right, b_right, right, is_less, right_config);
+    });
+}
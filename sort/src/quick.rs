@@ -0,0 +1,236 @@
+use crate::{
+    harness,
+    heap::{self, SORT_ARITY},
+    merge::insertion_sort,
+};
+use std::mem;
+
+/// Slices not longer than this are sorted by insertion.
+const INSERTION_THRESHOLD: usize = 20;
+
+/// Slices not shorter than this take the ninther as the pivot.
+const NINTHER_THRESHOLD: usize = 128;
+
+/// At most this many out-of-order pairs are fixed by the partial insertion.
+const MAX_PARTIAL_STEPS: usize = 5;
+
+/// Slices shorter than this are never shifted by the partial insertion.
+const SHORTEST_SHIFTING: usize = 50;
+
+/// In-place pattern-defeating quick sort, see [`crate::Solution::quick_sort`].
+///
+/// The recursion only goes into the shorter part, so the stack depth is
+/// O(log n). After too many unbalanced partitions, the rest is handed over
+/// to heap sort, so the worst case is O(n log n).
+pub(crate) fn quick_sort<T, F>(v: &mut [T], is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    if v.len() > 1 {
+        let limit = 2 * (usize::BITS - v.len().leading_zeros());
+        recurse(v, is_less, None, limit);
+    }
+}
+
+/// Sorts `v`, whose elements are all not less than `pred` if it exists.
+fn recurse<'a, T, F>(mut v: &'a mut [T], is_less: &mut F, mut pred: Option<&'a T>, mut limit: u32)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let (mut was_balanced, mut was_partitioned) = (true, true);
+    loop {
+        let len = v.len();
+        if len <= INSERTION_THRESHOLD {
+            insertion_sort(v, is_less);
+            return;
+        }
+        if limit == 0 {
+            heap::heap_sort::<T, F, SORT_ARITY>(v, is_less);
+            return;
+        }
+        if !was_balanced {
+            break_patterns(v);
+            limit -= 1;
+        }
+        let (pivot, likely_sorted) = choose_pivot(v, is_less);
+        // The slice was probably sorted already, try to finish it cheaply.
+        if was_balanced && was_partitioned && likely_sorted && partial_insertion_sort(v, is_less) {
+            return;
+        }
+        // The pivot equals to the predecessor, which is the smallest one in
+        // the slice, so put all the equal elements together and skip them.
+        if pred.is_some_and(|pred| !is_less(pred, &v[pivot])) {
+            let mid = partition_equal(v, pivot, is_less);
+            v = &mut mem::take(&mut v)[mid..];
+            continue;
+        }
+        let mid;
+        (mid, was_partitioned) = partition(v, pivot, is_less);
+        was_balanced = mid.min(len - mid) >= len / 8;
+        let (left, right) = mem::take(&mut v).split_at_mut(mid);
+        let (pivot, right) = right.split_first_mut().unwrap();
+        if left.len() < right.len() {
+            recurse(left, is_less, pred, limit);
+            (v, pred) = (right, Some(&*pivot));
+        } else {
+            recurse(right, is_less, Some(&*pivot), limit);
+            v = left;
+        }
+    }
+}
+
+/// Returns the index of the pivot, and whether the slice seems sorted.
+///
+/// The pivot is the median of three, or the median of three medians for
+/// long slices. If every comparison swapped, the slice seems to be in
+/// descending order, so it's reversed.
+pub(crate) fn choose_pivot<T, F>(v: &mut [T], is_less: &mut F) -> (usize, bool)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    const MAX_SWAPS: usize = 4 * 3;
+    let len = v.len();
+    let (mut a, mut b, mut c) = (len / 4, len / 4 * 2, len / 4 * 3);
+    let mut swaps = 0;
+    let mut sort2 = |a: &mut usize, b: &mut usize| {
+        if is_less(&v[*b], &v[*a]) {
+            mem::swap(a, b);
+            swaps += 1;
+        }
+    };
+    let mut sort3 = |a: &mut usize, b: &mut usize, c: &mut usize| {
+        sort2(a, b);
+        sort2(b, c);
+        sort2(a, b);
+    };
+    if len >= NINTHER_THRESHOLD {
+        let mut median = |mid: &mut usize| {
+            let (mut lo, mut hi) = (*mid - 1, *mid + 1);
+            sort3(&mut lo, mid, &mut hi);
+        };
+        median(&mut a);
+        median(&mut b);
+        median(&mut c);
+    }
+    sort3(&mut a, &mut b, &mut c);
+    if swaps < MAX_SWAPS {
+        (b, swaps == 0)
+    } else {
+        v.reverse();
+        harness::count_swaps(len / 2);
+        (len - 1 - b, true)
+    }
+}
+
+/// Partitions `v` into elements less than `v[pivot]`, the pivot itself,
+/// and elements not less than it. Returns the new index of the pivot, and
+/// whether the slice was already partitioned.
+pub(crate) fn partition<T, F>(v: &mut [T], pivot: usize, is_less: &mut F) -> (usize, bool)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    harness::swap(v, 0, pivot);
+    let (pivot, rest) = v.split_first_mut().unwrap();
+    let (mut l, mut r) = (0, rest.len());
+    while l < r && is_less(&rest[l], pivot) {
+        l += 1;
+    }
+    while l < r && !is_less(&rest[r - 1], pivot) {
+        r -= 1;
+    }
+    let was_partitioned = l >= r;
+    while l < r {
+        r -= 1;
+        harness::swap(rest, l, r);
+        l += 1;
+        while l < r && is_less(&rest[l], pivot) {
+            l += 1;
+        }
+        while l < r && !is_less(&rest[r - 1], pivot) {
+            r -= 1;
+        }
+    }
+    harness::swap(v, 0, l);
+    (l, was_partitioned)
+}
+
+/// Partitions `v` into elements equal to `v[pivot]`, which is assumed to be
+/// the smallest one, and elements greater than it. Returns the number of
+/// the equal ones.
+pub(crate) fn partition_equal<T, F>(v: &mut [T], pivot: usize, is_less: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    harness::swap(v, 0, pivot);
+    let (pivot, rest) = v.split_first_mut().unwrap();
+    let (mut l, mut r) = (0, rest.len());
+    loop {
+        while l < r && !is_less(pivot, &rest[l]) {
+            l += 1;
+        }
+        while l < r && is_less(pivot, &rest[r - 1]) {
+            r -= 1;
+        }
+        if l >= r {
+            return l + 1;
+        }
+        r -= 1;
+        harness::swap(rest, l, r);
+        l += 1;
+    }
+}
+
+/// Sorts `v` by fixing at most a few out-of-order pairs, returns whether
+/// the slice ends up sorted.
+fn partial_insertion_sort<T, F>(v: &mut [T], is_less: &mut F) -> bool
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let len = v.len();
+    let mut i = 1;
+    for _ in 0..MAX_PARTIAL_STEPS {
+        while i < len && !is_less(&v[i], &v[i - 1]) {
+            i += 1;
+        }
+        if i == len {
+            return true;
+        } else if len < SHORTEST_SHIFTING {
+            return false;
+        }
+        harness::swap(v, i - 1, i);
+        let mut j = i - 1;
+        while j > 0 && is_less(&v[j], &v[j - 1]) {
+            harness::swap(v, j, j - 1);
+            j -= 1;
+        }
+        let mut j = i;
+        while j + 1 < len && is_less(&v[j + 1], &v[j]) {
+            harness::swap(v, j, j + 1);
+            j += 1;
+        }
+    }
+    false
+}
+
+/// Swaps a few elements around the middle to pseudo-random positions, so
+/// that the next pivots are unlikely to be as bad as the last one.
+fn break_patterns<T>(v: &mut [T]) {
+    let len = v.len();
+    let mut seed = len;
+    let mut random = || {
+        // Xorshift RNG, see https://www.jstatsoft.org/v08/i14/paper.
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        seed
+    };
+    let mask = len.next_power_of_two() - 1;
+    let pos = len / 4 * 2;
+    for i in 0..3 {
+        let mut other = random() & mask;
+        if other >= len {
+            other -= len;
+        }
+        harness::swap(v, pos - 1 + i, other);
+    }
+}
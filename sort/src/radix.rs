@@ -0,0 +1,349 @@
+use crate::{merge::insertion_sort, order::permute, SliceSort};
+use std::{mem, ptr, slice};
+
+/// Slices not longer than this are sorted by insertion in MSD radix sort.
+const INSERTION_THRESHOLD: usize = 32;
+
+/// A key which can be sorted by radix sort, as a string of `LEVELS` bytes.
+///
+/// Comparing the byte strings lexicographically (the byte at level `0` is
+/// the most significant) must give the order of the keys. Integers are
+/// stored big-endian, with the sign bit flipped for signed ones, and floats
+/// follow the total order of [`f64::total_cmp`].
+///
+/// # Examples
+///
+/// ```
+/// use sort::{RadixKey, SliceSort};
+///
+/// /// A date, which sorts by year, month and day.
+/// struct Date(i16, u8, u8);
+///
+/// impl RadixKey for Date {
+///     const LEVELS: usize = 4;
+///
+///     fn key_byte(&self, level: usize) -> u8 {
+///         match level {
+///             0 | 1 => self.0.key_byte(level),
+///             2 => self.1,
+///             _ => self.2,
+///         }
+///     }
+/// }
+///
+/// let mut vec = vec![Date(2024, 2, 29), Date(-44, 3, 15), Date(1969, 7, 20)];
+/// vec.lsd_radix_sort();
+/// assert_eq!(vec.iter().map(|date| date.0).collect::<Vec<_>>(), [-44, 1969, 2024]);
+/// ```
+pub trait RadixKey {
+    /// The number of bytes of the key.
+    const LEVELS: usize;
+
+    /// The byte at `level`, where `level < Self::LEVELS`.
+    fn key_byte(&self, level: usize) -> u8;
+}
+
+macro_rules! radix_key_unsigned {
+    ($($primitive:ty),*) => {$(
+        impl RadixKey for $primitive {
+            const LEVELS: usize = mem::size_of::<$primitive>();
+
+            fn key_byte(&self, level: usize) -> u8 {
+                (self >> ((Self::LEVELS - 1 - level) * 8)) as u8
+            }
+        }
+    )*};
+}
+
+radix_key_unsigned!(u8, u16, u32, u64, u128, usize);
+
+macro_rules! radix_key_signed {
+    ($($primitive:ty => $unsigned:ty),*) => {$(
+        impl RadixKey for $primitive {
+            const LEVELS: usize = mem::size_of::<$primitive>();
+
+            fn key_byte(&self, level: usize) -> u8 {
+                (*self as $unsigned ^ 1 << (<$unsigned>::BITS - 1)).key_byte(level)
+            }
+        }
+    )*};
+}
+
+radix_key_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+
+macro_rules! radix_key_float {
+    ($($primitive:ty => $unsigned:ty),*) => {$(
+        impl RadixKey for $primitive {
+            const LEVELS: usize = mem::size_of::<$primitive>();
+
+            /// Flips all the bits of negative values, and only the sign bit
+            /// of positive ones.
+            fn key_byte(&self, level: usize) -> u8 {
+                let bits = self.to_bits();
+                let sign = 1 << (<$unsigned>::BITS - 1);
+                let mask = (bits >> (<$unsigned>::BITS - 1)).wrapping_neg();
+                (bits ^ (mask | sign)).key_byte(level)
+            }
+        }
+    )*};
+}
+
+radix_key_float!(f32 => u32, f64 => u64);
+
+impl RadixKey for bool {
+    const LEVELS: usize = 1;
+
+    fn key_byte(&self, _: usize) -> u8 {
+        *self as u8
+    }
+}
+
+impl RadixKey for char {
+    const LEVELS: usize = 3;
+
+    fn key_byte(&self, level: usize) -> u8 {
+        (*self as u32).key_byte(level + 1)
+    }
+}
+
+impl<K: RadixKey, const N: usize> RadixKey for [K; N] {
+    const LEVELS: usize = N * K::LEVELS;
+
+    fn key_byte(&self, level: usize) -> u8 {
+        self[level / K::LEVELS].key_byte(level % K::LEVELS)
+    }
+}
+
+impl<K: RadixKey + ?Sized> RadixKey for &K {
+    const LEVELS: usize = K::LEVELS;
+
+    fn key_byte(&self, level: usize) -> u8 {
+        (**self).key_byte(level)
+    }
+}
+
+macro_rules! radix_key_tuple {
+    ($(($($K:ident $idx:tt),*)),*) => {$(
+        impl<$($K: RadixKey),*> RadixKey for ($($K,)*) {
+            const LEVELS: usize = 0 $(+ $K::LEVELS)*;
+
+            #[allow(unused_assignments)]
+            fn key_byte(&self, mut level: usize) -> u8 {
+                $(
+                    if level < $K::LEVELS {
+                        return self.$idx.key_byte(level);
+                    }
+                    level -= $K::LEVELS;
+                )*
+                unreachable!("level out of range")
+            }
+        }
+    )*};
+}
+
+radix_key_tuple! {
+    (A 0, B 1),
+    (A 0, B 1, C 2),
+    (A 0, B 1, C 2, D 3)
+}
+
+/// Stable LSD radix sort, see [`SliceSort::lsd_radix_sort`].
+///
+/// Each level is a counting sort from `v` into a buffer or the other way
+/// round. The bytes of a level are all extracted before any element moves,
+/// so if `key_byte` panics, `v` still holds every element exactly once.
+///
+/// `key_byte(elem, level)` is the byte at `level` of the key of `elem`.
+pub(crate) fn lsd_radix_sort<T, F>(v: &mut [T], levels: usize, key_byte: &mut F)
+where
+    F: FnMut(&T, usize) -> u8,
+{
+    let len = v.len();
+    if len < 2 {
+        return;
+    }
+    let mut buf = Vec::<T>::with_capacity(len);
+    let mut bytes = vec![0u8; len];
+    let base = v.as_mut_ptr();
+    let (mut src, mut dst) = (base, buf.as_mut_ptr());
+    for level in (0..levels).rev() {
+        let mut counts = [0; 256];
+        // SAFETY: `src` holds all the elements, either `v` or `buf`.
+        for (byte, elem) in bytes
+            .iter_mut()
+            .zip(unsafe { slice::from_raw_parts(src, len) })
+        {
+            *byte = key_byte(elem, level);
+            counts[*byte as usize] += 1;
+        }
+        if counts[bytes[0] as usize] == len {
+            continue;
+        }
+        let mut offset = 0;
+        for count in &mut counts {
+            (*count, offset) = (offset, offset + *count);
+        }
+        for (i, &byte) in bytes.iter().enumerate() {
+            // SAFETY: the offsets are a permutation of `0..len`.
+            unsafe { ptr::copy_nonoverlapping(src.add(i), dst.add(counts[byte as usize]), 1) };
+            counts[byte as usize] += 1;
+        }
+        mem::swap(&mut src, &mut dst);
+    }
+    if src != base {
+        // SAFETY: the sorted elements are in `buf`, which never drops them.
+        unsafe { ptr::copy_nonoverlapping(src, base, len) };
+    }
+}
+
+/// In-place MSD radix sort, see [`SliceSort::msd_radix_sort`].
+///
+/// Each level distributes the elements into the buckets by swapping them
+/// in cycles (the American flag sort), then every bucket goes to the next
+/// level. Short buckets are sorted by insertion instead.
+pub(crate) fn msd_radix_sort<T, F>(v: &mut [T], levels: usize, key_byte: &mut F)
+where
+    F: FnMut(&T, usize) -> u8,
+{
+    if levels > 0 {
+        msd_level(v, 0, levels, key_byte);
+    }
+}
+
+fn msd_level<T, F>(v: &mut [T], level: usize, levels: usize, key_byte: &mut F)
+where
+    F: FnMut(&T, usize) -> u8,
+{
+    if v.len() <= INSERTION_THRESHOLD {
+        insertion_sort(v, &mut |a, b| {
+            (level..levels)
+                .map(|level| (key_byte(a, level), key_byte(b, level)))
+                .find(|(a, b)| a != b)
+                .is_some_and(|(a, b)| a < b)
+        });
+        return;
+    }
+    let mut counts = [0; 256];
+    for elem in v.iter() {
+        counts[key_byte(elem, level) as usize] += 1;
+    }
+    let (mut heads, mut tails) = ([0; 256], [0; 256]);
+    let mut offset = 0;
+    for (byte, &count) in counts.iter().enumerate() {
+        heads[byte] = offset;
+        offset += count;
+        tails[byte] = offset;
+    }
+    for byte in 0..256 {
+        while heads[byte] < tails[byte] {
+            let dest = key_byte(&v[heads[byte]], level) as usize;
+            if dest != byte {
+                v.swap(heads[byte], heads[dest]);
+            }
+            heads[dest] += 1;
+        }
+    }
+    if level + 1 < levels {
+        let mut start = 0;
+        for end in tails {
+            if end - start > 1 {
+                msd_level(&mut v[start..end], level + 1, levels, key_byte);
+            }
+            start = end;
+        }
+    }
+}
+
+/// Key ranges up to this, or the length if it's longer, are counted in a
+/// table, and wider ones are radix sorted.
+const COUNTING_RANGE: usize = 1 << 16;
+
+/// Stable counting sort, see [`SliceSort::counting_sort_by_key`].
+///
+/// The table has an entry for each key from the least one to the greatest
+/// one. If that would be more than [`COUNTING_RANGE`] entries and more than
+/// the length, the keys less the least one are LSD radix sorted instead, by
+/// as many bytes as the range takes.
+pub(crate) fn counting_sort<T, F>(v: &mut [T], key: F)
+where
+    F: FnMut(&T) -> usize,
+{
+    let mut keys = v.iter().map(key).collect::<Vec<_>>();
+    let (Some(&min), Some(&max)) = (keys.iter().min(), keys.iter().max()) else {
+        return;
+    };
+    let range = max - min;
+    keys.iter_mut().for_each(|key| *key -= min);
+    let mut idx = (0..keys.len()).collect::<Vec<_>>();
+    if range >= COUNTING_RANGE.max(keys.len()) {
+        let levels = (usize::BITS - range.leading_zeros()).div_ceil(8) as usize;
+        let skip = usize::LEVELS - levels;
+        lsd_radix_sort(&mut idx, levels, &mut |&i, level| {
+            keys[i].key_byte(skip + level)
+        });
+        permute(v, idx);
+        return;
+    }
+    let mut counts = vec![0; range + 1];
+    for &key in &keys {
+        counts[key] += 1;
+    }
+    let mut offset = 0;
+    for count in &mut counts {
+        (*count, offset) = (offset, offset + *count);
+    }
+    for (i, key) in keys.into_iter().enumerate() {
+        idx[counts[key]] = i;
+        counts[key] += 1;
+    }
+    permute(v, idx);
+}
+
+/// Stable bucket sort, see [`SliceSort::bucket_sort_by_key`].
+///
+/// The range of the keys is split evenly into `len` buckets, which are
+/// filled by a counting sort and then sorted by insertion. If any key is
+/// not finite, all the keys are merge sorted by [`f64::total_cmp`] instead.
+pub(crate) fn bucket_sort<T, F>(v: &mut [T], key: F)
+where
+    F: FnMut(&T) -> f64,
+{
+    let keys = v.iter().map(key).collect::<Vec<_>>();
+    let len = keys.len();
+    let (min, max) = keys
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &key| {
+            (min.min(key), max.max(key))
+        });
+    let mut idx = (0..len).collect::<Vec<_>>();
+    if keys.iter().any(|key| !key.is_finite()) {
+        idx.merge_sort_by(|&a, &b| keys[a].total_cmp(&keys[b]));
+    } else if min < max {
+        // Halved first, so that `max - min` never overflows.
+        let scale = (len - 1) as f64 / (max / 2.0 - min / 2.0);
+        let buckets = keys
+            .iter()
+            .map(|&key| (((key / 2.0 - min / 2.0) * scale) as usize).min(len - 1))
+            .collect::<Vec<_>>();
+        let mut counts = vec![0; len + 1];
+        for &bucket in &buckets {
+            counts[bucket + 1] += 1;
+        }
+        for i in 1..=len {
+            counts[i] += counts[i - 1];
+        }
+        let starts = counts.clone();
+        for (i, &bucket) in buckets.iter().enumerate() {
+            idx[counts[bucket]] = i;
+            counts[bucket] += 1;
+        }
+        for bucket in starts.windows(2) {
+            insertion_sort(&mut idx[bucket[0]..bucket[1]], &mut |&a, &b| {
+                keys[a].total_cmp(&keys[b]).is_lt()
+            });
+        }
+    } else {
+        return;
+    }
+    permute(v, idx);
+}
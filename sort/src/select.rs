@@ -0,0 +1,201 @@
+//! Selection, i.e. finding the k-th smallest elements without a full sort.
+
+use crate::{
+    harness,
+    heap::{sift_down, sift_up},
+    merge::insertion_sort,
+    quick::{self, choose_pivot, partition, partition_equal},
+    SliceSort,
+};
+use std::{cmp::Ordering, mem};
+
+/// Slices not longer than this are sorted by insertion.
+const INSERTION_THRESHOLD: usize = 16;
+
+/// Introselect, see [`SliceSort::select_nth`].
+///
+/// It's quick select with the pivots of the quick sort, which only goes
+/// into the part containing `n`. After too many unbalanced partitions, the
+/// pivots are chosen by the median of medians instead, which guarantees
+/// O(n) time in the worst case.
+pub(crate) fn select_nth<T, F>(mut v: &mut [T], mut n: usize, is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let mut limit = v.len().ilog2();
+    let mut pred = None;
+    loop {
+        let len = v.len();
+        if len <= INSERTION_THRESHOLD {
+            insertion_sort(v, is_less);
+            return;
+        }
+        let pivot = if limit == 0 {
+            median_of_medians(v, is_less)
+        } else {
+            choose_pivot(v, is_less).0
+        };
+        // The pivot equals to the predecessor, which is the smallest one in
+        // the slice, so the equal elements are done.
+        if pred.is_some_and(|pred| !is_less(pred, &v[pivot])) {
+            let mid = partition_equal(v, pivot, is_less);
+            if n < mid {
+                return;
+            }
+            v = &mut mem::take(&mut v)[mid..];
+            n -= mid;
+            continue;
+        }
+        let (mid, _) = partition(v, pivot, is_less);
+        if mid.min(len - mid) < len / 8 {
+            limit = limit.saturating_sub(1);
+        }
+        let (left, right) = mem::take(&mut v).split_at_mut(mid);
+        let (pivot, right) = right.split_first_mut().unwrap();
+        match n.cmp(&mid) {
+            Ordering::Less => v = left,
+            Ordering::Equal => return,
+            Ordering::Greater => {
+                (v, n, pred) = (right, n - mid - 1, Some(&*pivot));
+            }
+        }
+    }
+}
+
+/// Returns the index of the median of the medians of every five elements,
+/// which is greater than and less than at least 30% of the elements each.
+fn median_of_medians<T, F>(v: &mut [T], is_less: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let groups = v.len() / 5;
+    for group in 0..groups {
+        insertion_sort(&mut v[group * 5..group * 5 + 5], is_less);
+        // The slot `group` belongs to a group done already.
+        harness::swap(v, group, group * 5 + 2);
+    }
+    select_nth(&mut v[..groups], groups / 2, is_less);
+    groups / 2
+}
+
+/// Partial sort, see [`SliceSort::partial_sort`].
+pub(crate) fn partial_sort<T, F>(v: &mut [T], k: usize, is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let k = k.min(v.len());
+    if k < v.len() {
+        select_nth(v, k, is_less);
+    }
+    quick::quick_sort(&mut v[..k], is_less);
+}
+
+/// Returns the `k` smallest items in order, see [`top_k_by`].
+pub fn top_k<I>(iter: I, k: usize) -> Vec<I::Item>
+where
+    I: IntoIterator,
+    I::Item: Ord,
+{
+    top_k_by(iter, k, Ord::cmp)
+}
+
+/// Returns the `k` smallest items in order with a key extraction function,
+/// see [`top_k_by`].
+pub fn top_k_by_key<I, K, F>(iter: I, k: usize, mut f: F) -> Vec<I::Item>
+where
+    I: IntoIterator,
+    F: FnMut(&I::Item) -> K,
+    K: Ord,
+{
+    top_k_by(iter, k, |a, b| f(a).cmp(&f(b)))
+}
+
+/// Returns the `k` smallest items in order with a comparator, equal items
+/// are kept in the order of `iter`.
+///
+/// Only a max-heap of the `k` smallest items so far is kept, so it takes
+/// O(n log k) time and O(k) memory, which suits long or endless streams.
+///
+/// # Examples
+///
+/// ```
+/// use sort::select;
+///
+/// let words = ["delta", "alpha", "echo", "charlie", "bravo"];
+/// assert_eq!(select::top_k(words, 2), ["alpha", "bravo"]);
+/// assert_eq!(select::top_k_by_key(words, 3, |s| s.len()), ["echo", "delta", "alpha"]);
+/// ```
+pub fn top_k_by<I, F>(iter: I, k: usize, mut compare: F) -> Vec<I::Item>
+where
+    I: IntoIterator,
+    F: FnMut(&I::Item, &I::Item) -> Ordering,
+{
+    if k == 0 {
+        return Vec::new();
+    }
+    // Ties are broken by the position, so later items never replace
+    // earlier equal ones.
+    let mut is_less = |a: &(usize, I::Item), b: &(usize, I::Item)| {
+        compare(&a.1, &b.1).then(a.0.cmp(&b.0)) == Ordering::Less
+    };
+    let mut heap = Vec::with_capacity(k);
+    for item in iter.into_iter().enumerate() {
+        if heap.len() < k {
+            let node = heap.len();
+            heap.push(item);
+            sift_up::<_, _, 2>(&mut heap, node, &mut is_less);
+        } else if is_less(&item, &heap[0]) {
+            heap[0] = item;
+            sift_down::<_, _, 2>(&mut heap, 0, &mut is_less);
+        }
+    }
+    heap.quick_sort_by(|a, b| compare(&a.1, &b.1).then(a.0.cmp(&b.0)));
+    heap.into_iter().map(|(_, item)| item).collect()
+}
+
+/// The lower median of `v`, which is reordered as by [`SliceSort::select_nth`].
+pub fn median<T: Ord>(v: &mut [T]) -> Option<&T> {
+    if v.is_empty() {
+        return None;
+    }
+    let mid = (v.len() - 1) / 2;
+    Some(v.select_nth(mid))
+}
+
+/// The median of `v`, the mean of the two middle ones if the length is
+/// even. The values are ordered by [`f64::total_cmp`].
+pub fn median_f64(v: &mut [f64]) -> Option<f64> {
+    quantile_f64(v, 0.5)
+}
+
+/// The `q`-quantile of `v`, interpolated linearly between the closest
+/// ranks, where the rank of `q` is `q * (len - 1)`. The values are ordered
+/// by [`f64::total_cmp`].
+///
+/// Panics if `q` is not in `0.0..=1.0`.
+///
+/// # Examples
+///
+/// ```
+/// use sort::select;
+///
+/// let mut vec = vec![7.0, 1.0, 3.0, 5.0];
+/// assert_eq!(select::median_f64(&mut vec), Some(4.0));
+/// assert_eq!(select::quantile_f64(&mut vec, 0.25), Some(2.5));
+/// assert_eq!(select::quantile_f64(&mut vec, 1.0), Some(7.0));
+/// ```
+pub fn quantile_f64(v: &mut [f64], q: f64) -> Option<f64> {
+    assert!((0.0..=1.0).contains(&q), "quantile must be in 0..=1");
+    if v.is_empty() {
+        return None;
+    }
+    let rank = q * (v.len() - 1) as f64;
+    let (lo, frac) = (rank.floor() as usize, rank.fract());
+    let low = *v.select_nth_by(lo, f64::total_cmp);
+    if frac == 0.0 {
+        return Some(low);
+    }
+    // The next one is the least of the rest.
+    let high = v[lo + 1..].iter().copied().min_by(f64::total_cmp).unwrap();
+    Some(low + (high - low) * frac)
+}
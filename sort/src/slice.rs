@@ -0,0 +1,479 @@
+use crate::{
+    heap, merge, order, parallel, quick, radix, select, tim, OrderViolation, ParallelConfig,
+    RadixKey,
+};
+use std::cmp::Ordering;
+
+/// Sorting methods on slices, so that `slice.quick_sort_by(..)` reads the
+/// same as the `sort_by` of the Standard Library.
+///
+/// All the methods sort in place and never clone the elements. Same as the
+/// Standard Library, `compare` must be a strict weak order, i.e. consistent
+/// with itself and transitive. An inconsistent comparator never causes
+/// undefined behavior, but the result is unspecified, so use
+/// [`SliceSort::checked_sort_by`] to find out what's wrong with it.
+///
+/// # Examples
+///
+/// ```
+/// use sort::SliceSort;
+///
+/// let mut vec = vec!["delta", "alpha", "charlie", "bravo"];
+/// vec.quick_sort_by(|a, b| a.cmp(b));
+/// assert_eq!(vec, ["alpha", "bravo", "charlie", "delta"]);
+///
+/// vec.merge_sort_by_key(|s| s.len());
+/// assert_eq!(vec, ["alpha", "bravo", "delta", "charlie"]);
+/// ```
+pub trait SliceSort<T> {
+    /// Sorts the slice by quick sort, which is not stable.
+    fn quick_sort(&mut self)
+    where
+        T: Ord;
+
+    /// Sorts the slice by quick sort with a comparator.
+    fn quick_sort_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering;
+
+    /// Sorts the slice by quick sort with a key extraction function.
+    fn quick_sort_by_key<K, F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord;
+
+    /// Sorts the slice by merge sort, which is stable.
+    fn merge_sort(&mut self)
+    where
+        T: Ord;
+
+    /// Sorts the slice by merge sort with a comparator.
+    fn merge_sort_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering;
+
+    /// Sorts the slice by merge sort with a key extraction function.
+    fn merge_sort_by_key<K, F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord;
+
+    /// Sorts the slice by TimSort, which is stable and runs in linear time
+    /// on presorted slices.
+    fn tim_sort(&mut self)
+    where
+        T: Ord;
+
+    /// Sorts the slice by TimSort with a comparator.
+    fn tim_sort_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering;
+
+    /// Sorts the slice by TimSort with a key extraction function.
+    fn tim_sort_by_key<K, F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord;
+
+    /// Sorts the slice by heap sort, which is not stable, and takes
+    /// O(n log n) time without any extra memory.
+    fn heap_sort(&mut self)
+    where
+        T: Ord;
+
+    /// Sorts the slice by heap sort with a comparator.
+    fn heap_sort_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering;
+
+    /// Sorts the slice by heap sort with a key extraction function.
+    fn heap_sort_by_key<K, F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord;
+
+    /// Sorts the slice by LSD radix sort, which is stable and takes
+    /// O(n * `T::LEVELS`) time and a buffer of `n` elements.
+    fn lsd_radix_sort(&mut self)
+    where
+        T: RadixKey;
+
+    /// Sorts the slice by LSD radix sort with a key extraction function,
+    /// which is called `K::LEVELS` times for each element.
+    fn lsd_radix_sort_by_key<K, F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: RadixKey;
+
+    /// Sorts the slice by MSD radix sort, which is not stable but takes no
+    /// extra memory.
+    fn msd_radix_sort(&mut self)
+    where
+        T: RadixKey;
+
+    /// Sorts the slice by MSD radix sort with a key extraction function.
+    fn msd_radix_sort_by_key<K, F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: RadixKey;
+
+    /// Sorts the slice by counting sort with a key extraction function,
+    /// which is stable and takes O(n + max key - min key) time, so it's
+    /// meant for small key ranges. Wider ranges are radix sorted, so the
+    /// table never grows past the length or 65536 entries.
+    fn counting_sort_by_key<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> usize;
+
+    /// Sorts the slice by bucket sort with a key extraction function, which
+    /// is stable and takes O(n) time on average if the keys are uniformly
+    /// distributed.
+    fn bucket_sort_by_key<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> f64;
+
+    /// Sorts the slice by quick sort on multiple threads, see
+    /// [`ParallelConfig`] for the options.
+    fn par_quick_sort(&mut self, config: ParallelConfig)
+    where
+        T: Ord + Send + Sync;
+
+    /// Sorts the slice by quick sort on multiple threads with a comparator.
+    fn par_quick_sort_by<F>(&mut self, config: ParallelConfig, compare: F)
+    where
+        F: Fn(&T, &T) -> Ordering + Sync,
+        T: Send + Sync;
+
+    /// Sorts the slice by quick sort on multiple threads with a key
+    /// extraction function.
+    fn par_quick_sort_by_key<K, F>(&mut self, config: ParallelConfig, f: F)
+    where
+        F: Fn(&T) -> K + Sync,
+        K: Ord,
+        T: Send + Sync;
+
+    /// Sorts the slice by merge sort on multiple threads, which is stable,
+    /// see [`ParallelConfig`] for the options.
+    fn par_merge_sort(&mut self, config: ParallelConfig)
+    where
+        T: Ord + Send + Sync;
+
+    /// Sorts the slice by merge sort on multiple threads with a comparator.
+    fn par_merge_sort_by<F>(&mut self, config: ParallelConfig, compare: F)
+    where
+        F: Fn(&T, &T) -> Ordering + Sync,
+        T: Send + Sync;
+
+    /// Sorts the slice by merge sort on multiple threads with a key
+    /// extraction function.
+    fn par_merge_sort_by_key<K, F>(&mut self, config: ParallelConfig, f: F)
+    where
+        F: Fn(&T) -> K + Sync,
+        K: Ord,
+        T: Send + Sync;
+
+    /// Reorders the slice so that the element at `n` is where it would be
+    /// when sorted, with no greater one before and no less one after it,
+    /// and returns it. Takes O(n) time in the worst case.
+    ///
+    /// Panics if `n >= len`.
+    fn select_nth(&mut self, n: usize) -> &mut T
+    where
+        T: Ord;
+
+    /// Selects the `n`-th element with a comparator.
+    fn select_nth_by<F>(&mut self, n: usize, compare: F) -> &mut T
+    where
+        F: FnMut(&T, &T) -> Ordering;
+
+    /// Selects the `n`-th element with a key extraction function.
+    fn select_nth_by_key<K, F>(&mut self, n: usize, f: F) -> &mut T
+    where
+        F: FnMut(&T) -> K,
+        K: Ord;
+
+    /// Sorts the `k` smallest elements into `..k`, the rest are left in an
+    /// unspecified order after them.
+    fn partial_sort(&mut self, k: usize)
+    where
+        T: Ord;
+
+    /// Sorts the `k` smallest elements with a comparator.
+    fn partial_sort_by<F>(&mut self, k: usize, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering;
+
+    /// Sorts the `k` smallest elements with a key extraction function.
+    fn partial_sort_by_key<K, F>(&mut self, k: usize, f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord;
+
+    /// Stable sorts the slice while validating the comparator, which takes
+    /// O(n^2) comparisons and is meant for debugging.
+    ///
+    /// Every comparison is checked for consistency with the reversed one,
+    /// and the result is checked against all pairs of elements. If
+    /// `compare` turns out not to be a strict weak order, the slice is
+    /// left untouched and the violation is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sort::{OrderViolation, SliceSort};
+    ///
+    /// // Rock, paper, scissors.
+    /// let mut vec = vec![0, 1, 2];
+    /// let result = vec.checked_sort_by(|a, b| match (b + 3 - a) % 3 {
+    ///     0 => std::cmp::Ordering::Equal,
+    ///     1 => std::cmp::Ordering::Less,
+    ///     _ => std::cmp::Ordering::Greater,
+    /// });
+    /// assert!(matches!(result, Err(OrderViolation::Intransitive { .. })));
+    /// assert_eq!(vec, [0, 1, 2]);
+    /// ```
+    fn checked_sort_by<F>(&mut self, compare: F) -> Result<(), OrderViolation>
+    where
+        F: FnMut(&T, &T) -> Ordering;
+}
+
+impl<T> SliceSort<T> for [T] {
+    fn quick_sort(&mut self)
+    where
+        T: Ord,
+    {
+        quick::quick_sort(self, &mut T::lt);
+    }
+
+    fn quick_sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        quick::quick_sort(self, &mut |a, b| compare(a, b) == Ordering::Less);
+    }
+
+    fn quick_sort_by_key<K, F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        quick::quick_sort(self, &mut |a, b| f(a).lt(&f(b)));
+    }
+
+    fn merge_sort(&mut self)
+    where
+        T: Ord,
+    {
+        merge::merge_sort(self, &mut T::lt);
+    }
+
+    fn merge_sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        merge::merge_sort(self, &mut |a, b| compare(a, b) == Ordering::Less);
+    }
+
+    fn merge_sort_by_key<K, F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        merge::merge_sort(self, &mut |a, b| f(a).lt(&f(b)));
+    }
+
+    fn tim_sort(&mut self)
+    where
+        T: Ord,
+    {
+        tim::tim_sort(self, &mut T::lt);
+    }
+
+    fn tim_sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        tim::tim_sort(self, &mut |a, b| compare(a, b) == Ordering::Less);
+    }
+
+    fn tim_sort_by_key<K, F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        tim::tim_sort(self, &mut |a, b| f(a).lt(&f(b)));
+    }
+
+    fn heap_sort(&mut self)
+    where
+        T: Ord,
+    {
+        heap::heap_sort::<_, _, { heap::SORT_ARITY }>(self, &mut T::lt);
+    }
+
+    fn heap_sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        heap::heap_sort::<_, _, { heap::SORT_ARITY }>(self, &mut |a, b| {
+            compare(a, b) == Ordering::Less
+        });
+    }
+
+    fn heap_sort_by_key<K, F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        heap::heap_sort::<_, _, { heap::SORT_ARITY }>(self, &mut |a, b| f(a).lt(&f(b)));
+    }
+
+    fn lsd_radix_sort(&mut self)
+    where
+        T: RadixKey,
+    {
+        radix::lsd_radix_sort(self, T::LEVELS, &mut T::key_byte);
+    }
+
+    fn lsd_radix_sort_by_key<K, F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: RadixKey,
+    {
+        radix::lsd_radix_sort(self, K::LEVELS, &mut |v, level| f(v).key_byte(level));
+    }
+
+    fn msd_radix_sort(&mut self)
+    where
+        T: RadixKey,
+    {
+        radix::msd_radix_sort(self, T::LEVELS, &mut T::key_byte);
+    }
+
+    fn msd_radix_sort_by_key<K, F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: RadixKey,
+    {
+        radix::msd_radix_sort(self, K::LEVELS, &mut |v, level| f(v).key_byte(level));
+    }
+
+    fn counting_sort_by_key<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> usize,
+    {
+        radix::counting_sort(self, f);
+    }
+
+    fn bucket_sort_by_key<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> f64,
+    {
+        radix::bucket_sort(self, f);
+    }
+
+    fn par_quick_sort(&mut self, config: ParallelConfig)
+    where
+        T: Ord + Send + Sync,
+    {
+        parallel::par_quick_sort(self, &T::lt, config);
+    }
+
+    fn par_quick_sort_by<F>(&mut self, config: ParallelConfig, compare: F)
+    where
+        F: Fn(&T, &T) -> Ordering + Sync,
+        T: Send + Sync,
+    {
+        parallel::par_quick_sort(self, &|a, b| compare(a, b) == Ordering::Less, config);
+    }
+
+    fn par_quick_sort_by_key<K, F>(&mut self, config: ParallelConfig, f: F)
+    where
+        F: Fn(&T) -> K + Sync,
+        K: Ord,
+        T: Send + Sync,
+    {
+        parallel::par_quick_sort(self, &|a, b| f(a).lt(&f(b)), config);
+    }
+
+    fn par_merge_sort(&mut self, config: ParallelConfig)
+    where
+        T: Ord + Send + Sync,
+    {
+        parallel::par_merge_sort(self, &T::lt, config);
+    }
+
+    fn par_merge_sort_by<F>(&mut self, config: ParallelConfig, compare: F)
+    where
+        F: Fn(&T, &T) -> Ordering + Sync,
+        T: Send + Sync,
+    {
+        parallel::par_merge_sort(self, &|a, b| compare(a, b) == Ordering::Less, config);
+    }
+
+    fn par_merge_sort_by_key<K, F>(&mut self, config: ParallelConfig, f: F)
+    where
+        F: Fn(&T) -> K + Sync,
+        K: Ord,
+        T: Send + Sync,
+    {
+        parallel::par_merge_sort(self, &|a, b| f(a).lt(&f(b)), config);
+    }
+
+    fn select_nth(&mut self, n: usize) -> &mut T
+    where
+        T: Ord,
+    {
+        self.select_nth_by(n, T::cmp)
+    }
+
+    fn select_nth_by<F>(&mut self, n: usize, mut compare: F) -> &mut T
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        assert!(
+            n < self.len(),
+            "index {n} out of range for length {}",
+            self.len()
+        );
+        select::select_nth(self, n, &mut |a, b| compare(a, b) == Ordering::Less);
+        &mut self[n]
+    }
+
+    fn select_nth_by_key<K, F>(&mut self, n: usize, mut f: F) -> &mut T
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.select_nth_by(n, |a, b| f(a).cmp(&f(b)))
+    }
+
+    fn partial_sort(&mut self, k: usize)
+    where
+        T: Ord,
+    {
+        select::partial_sort(self, k, &mut T::lt);
+    }
+
+    fn partial_sort_by<F>(&mut self, k: usize, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        select::partial_sort(self, k, &mut |a, b| compare(a, b) == Ordering::Less);
+    }
+
+    fn partial_sort_by_key<K, F>(&mut self, k: usize, mut f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        select::partial_sort(self, k, &mut |a, b| f(a).lt(&f(b)));
+    }
+
+    fn checked_sort_by<F>(&mut self, compare: F) -> Result<(), OrderViolation>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        order::checked_sort(self, compare)
+    }
+}
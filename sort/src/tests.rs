@@ -1,32 +1,28 @@
-use crate::Solution;
-use rand::prelude::*;
+use crate::{by_less, by_less_equal, OrderViolation, SliceSort, Solution};
+use std::cmp::Ordering;
 
 #[test]
 fn quick_sort_1() {
-    assert!(Solution::quick_sort(
-        &(-10_000..10_000).into_iter().collect::<Vec<_>>(),
-        |a, b| a < b
-    )
-    .is_sorted());
+    assert!(
+        Solution::quick_sort(&(-10_000..10_000).collect::<Vec<_>>(), |a, b| a.cmp(b)).is_sorted()
+    );
 }
 
 #[test]
 fn quick_sort_2() {
-    assert!(Solution::quick_sort(
-        &(-10_000..10_000).into_iter().collect::<Vec<_>>(),
-        |a, b| a > b
-    )
-    .is_sorted_by(|a, b| b.partial_cmp(a)));
+    assert!(
+        Solution::quick_sort(&(-10_000..10_000).collect::<Vec<_>>(), |a, b| b.cmp(a))
+            .is_sorted_by(|a, b| a >= b)
+    );
 }
 
 #[test]
 fn quick_sort_3() {
     assert!(Solution::quick_sort(
         &(0..2_000_000)
-            .into_iter()
-            .map(|_| thread_rng().gen_range(-1_000_000..1_000_000))
+            .map(|_| rand::random_range(-1_000_000..1_000_000))
             .collect::<Vec<_>>(),
-        |a, b| a < b
+        i32::cmp
     )
     .is_sorted());
 }
@@ -34,8 +30,8 @@ fn quick_sort_3() {
 #[test]
 fn merge_sort_1() {
     assert!(Solution::merge_sort(
-        &(-1_000_000..1_000_000).into_iter().collect::<Vec<_>>(),
-        |a, b| a < b,
+        &(-1_000_000..1_000_000).collect::<Vec<_>>(),
+        by_less(|a, b| a < b)
     )
     .is_sorted());
 }
@@ -43,20 +39,762 @@ fn merge_sort_1() {
 #[test]
 fn merge_sort_2() {
     assert!(Solution::merge_sort(
-        &(-1_000_000..1_000_000).into_iter().collect::<Vec<_>>(),
-        |a, b| a > b,
+        &(-1_000_000..1_000_000).collect::<Vec<_>>(),
+        by_less_equal(|a, b| a >= b)
     )
-    .is_sorted_by(|a, b| b.partial_cmp(a)));
+    .is_sorted_by(|a, b| a >= b));
 }
 
 #[test]
 fn merge_sort_3() {
     assert!(Solution::merge_sort(
         &(0..2_000_000)
-            .into_iter()
-            .map(|_| thread_rng().gen_range(-1_000_000..1_000_000))
+            .map(|_| rand::random_range(-1_000_000..1_000_000))
             .collect::<Vec<_>>(),
-        |a, b| a < b
+        i32::cmp
     )
     .is_sorted());
 }
+
+/// Neither `Clone` nor `Copy`, to make sure the elements are only moved.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Record(i32, Box<usize>);
+
+fn records(len: usize) -> Vec<Record> {
+    (0..len)
+        .map(|idx| Record(rand::random_range(0..100), Box::new(idx)))
+        .collect()
+}
+
+#[test]
+fn sort_in_place() {
+    let mut vec = records(10_000);
+    vec.quick_sort();
+    assert!(vec.is_sorted());
+    let mut vec = records(10_000);
+    vec.merge_sort_by(|a, b| b.0.cmp(&a.0));
+    assert!(vec.is_sorted_by(|a, b| a.0 >= b.0));
+    let mut vec = records(10_000);
+    Solution::sort_unstable_by(&mut vec, |a, b| a.1.cmp(&b.1));
+    assert!(vec.iter().enumerate().all(|(idx, v)| *v.1 == idx));
+}
+
+#[test]
+fn sort_by_key_is_stable() {
+    let mut vec = records(100_000);
+    Solution::sort_by_key(&mut vec, |v| v.0);
+    assert!(vec.is_sorted_by(|a, b| (a.0, &a.1) <= (b.0, &b.1)));
+    let mut vec = records(100_000);
+    Solution::sort_by(&mut vec, |a, b| a.0.cmp(&b.0));
+    assert!(vec.is_sorted_by(|a, b| (a.0, &a.1) <= (b.0, &b.1)));
+}
+
+#[test]
+fn merge_sort_panic_safety() {
+    use std::panic::{self, AssertUnwindSafe};
+    let mut vec = records(1_000);
+    let mut cnt = 0;
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        vec.merge_sort_by(|a, b| {
+            cnt += 1;
+            assert!(cnt < 5_000, "comparator panicked");
+            a.cmp(b)
+        })
+    }));
+    assert!(result.is_err());
+    let mut idx = vec.iter().map(|v| *v.1).collect::<Vec<_>>();
+    idx.quick_sort();
+    assert!(idx.into_iter().eq(0..1_000));
+}
+
+/// Sorts `v` by quick sort, and checks that the comparisons are bounded
+/// by `c * n * log2(n)` for a small `c`.
+fn quick_sort_bounded(mut v: Vec<i64>) {
+    let len = v.len() as u64;
+    let mut cnt = 0u64;
+    v.quick_sort_by(|a, b| {
+        cnt += 1;
+        a.cmp(b)
+    });
+    assert!(v.is_sorted());
+    assert!(cnt <= 4 * len * u64::from(len.ilog2()), "{cnt} comparisons");
+}
+
+#[test]
+fn quick_sort_patterns() {
+    const LEN: i64 = 10_000_000;
+    quick_sort_bounded((0..LEN).collect());
+    quick_sort_bounded((0..LEN).rev().collect());
+    quick_sort_bounded(vec![7; LEN as usize]);
+    quick_sort_bounded((0..LEN).map(|v| v % 16).collect());
+    quick_sort_bounded((0..LEN).map(|v| v.min(LEN - v)).collect());
+    quick_sort_bounded((0..LEN).map(|v| (v * 7 + 3) % 100).collect());
+    quick_sort_bounded(
+        (0..LEN)
+            .map(|v| if v % 1000 == 0 { -v } else { v })
+            .collect(),
+    );
+    quick_sort_bounded(
+        (0..LEN)
+            .map(|_| rand::random_range(-1_000_000..1_000_000))
+            .collect(),
+    );
+}
+
+#[test]
+fn checked_sort() {
+    let mut vec = records(1_000);
+    assert_eq!(vec.checked_sort_by(|a, b| a.0.cmp(&b.0)), Ok(()));
+    assert!(vec.is_sorted_by(|a, b| (a.0, &a.1) <= (b.0, &b.1)));
+
+    let mut vec = vec![3i32, 1, 2];
+    assert_eq!(
+        vec.checked_sort_by(|a, b| a.cmp(b).then(Ordering::Less)),
+        Err(OrderViolation::Irreflexive { a: 0 })
+    );
+    assert_eq!(
+        vec.checked_sort_by(|a, b| if a < b {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        }),
+        Err(OrderViolation::Irreflexive { a: 0 })
+    );
+    assert_eq!(
+        vec.checked_sort_by(by_less_equal(|a, b| a <= b && b - a != 2)),
+        Err(OrderViolation::Asymmetric { a: 1, b: 0 })
+    );
+    // Elements within a distance of one are equal, but it's not transitive.
+    let result = vec.checked_sort_by(|a, b| match a.abs_diff(*b) {
+        0 | 1 => Ordering::Equal,
+        _ => a.cmp(b),
+    });
+    assert_eq!(
+        result,
+        Err(OrderViolation::Intransitive { a: 1, b: 0, c: 2 })
+    );
+    assert_eq!(vec, [3, 1, 2]);
+}
+
+#[test]
+fn tim_sort() {
+    assert!(Solution::tim_sort(&(0..1_000_000).rev().collect::<Vec<_>>(), i32::cmp).is_sorted());
+    for len in [0, 1, 2, 63, 64, 65, 1_000, 100_000] {
+        let mut vec = records(len);
+        vec.tim_sort_by_key(|v| v.0);
+        assert!(vec.is_sorted_by(|a, b| (a.0, &a.1) <= (b.0, &b.1)));
+        // Runs of all kinds of lengths, galloping most of the time.
+        let mut vec = (0..len)
+            .map(|idx| Record((idx % 1000 / 3) as i32, Box::new(idx)))
+            .collect::<Vec<_>>();
+        vec.tim_sort();
+        assert!(vec.is_sorted());
+        let mut vec = (0..len)
+            .map(|idx| {
+                let key = rand::random_range(0..len / 500 + 1) * 1000 + idx % 1000;
+                Record(key as i32, Box::new(idx))
+            })
+            .collect::<Vec<_>>();
+        vec.tim_sort_by(|a, b| b.0.cmp(&a.0));
+        assert!(vec.is_sorted_by(|a, b| (b.0, &a.1) <= (a.0, &b.1)));
+    }
+    let mut cnt = 0;
+    let mut vec = (0..1_000_000).collect::<Vec<_>>();
+    vec.tim_sort_by(|a, b| {
+        cnt += 1;
+        a.cmp(b)
+    });
+    assert_eq!(cnt, 999_999);
+}
+
+#[test]
+fn tim_sort_panic_safety() {
+    use std::panic::{self, AssertUnwindSafe};
+    for limit in [1_000, 10_000, 50_000] {
+        let mut vec = records(10_000);
+        let mut cnt = 0;
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            vec.tim_sort_by(|a, b| {
+                cnt += 1;
+                assert!(cnt < limit, "comparator panicked");
+                a.cmp(b)
+            })
+        }));
+        assert!(result.is_err());
+        let mut idx = vec.iter().map(|v| *v.1).collect::<Vec<_>>();
+        idx.quick_sort();
+        assert!(idx.into_iter().eq(0..10_000));
+    }
+}
+
+/// Checks the LSD and MSD radix sorts of `vec` against `sort_by(cmp)`.
+fn check_radix_sort<T: crate::RadixKey + Copy>(vec: Vec<T>, cmp: fn(&T, &T) -> Ordering) {
+    let mut expect = vec.clone();
+    expect.sort_by(cmp);
+    let mut lsd = vec.clone();
+    lsd.lsd_radix_sort();
+    assert!(lsd.iter().zip(&expect).all(|(a, b)| cmp(a, b).is_eq()));
+    let mut msd = vec;
+    msd.msd_radix_sort();
+    assert!(msd.iter().zip(&expect).all(|(a, b)| cmp(a, b).is_eq()));
+}
+
+#[test]
+fn radix_sort_1() {
+    for len in [0, 1, 2, 100, 100_000] {
+        check_radix_sort((0..len).map(|_| rand::random::<u8>()).collect(), u8::cmp);
+        check_radix_sort((0..len).map(|_| rand::random::<u64>()).collect(), u64::cmp);
+    }
+}
+
+#[test]
+fn radix_sort_2() {
+    for len in [0, 1, 2, 100, 100_000] {
+        check_radix_sort((0..len).map(|_| rand::random::<i32>()).collect(), i32::cmp);
+        check_radix_sort(
+            (0..len).map(|_| rand::random_range(-9..9i128)).collect(),
+            i128::cmp,
+        );
+    }
+}
+
+#[test]
+fn radix_sort_3() {
+    for len in [0, 1, 2, 100, 100_000] {
+        check_radix_sort(
+            (0..len).map(|_| rand::random::<[u8; 5]>()).collect(),
+            <[u8; 5]>::cmp,
+        );
+        check_radix_sort(
+            (0..len)
+                .map(|_| rand::random::<(bool, i16, char)>())
+                .collect(),
+            |a, b| a.cmp(b),
+        );
+    }
+}
+
+#[test]
+fn radix_sort_4() {
+    let floats = [
+        0.0,
+        -0.0,
+        1.5,
+        -1.5,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::NAN,
+        -f64::NAN,
+    ];
+    for len in [0, 1, 2, 100, 100_000] {
+        check_radix_sort(
+            (0..len)
+                .map(|_| match rand::random_range(0..16) {
+                    idx @ 0..8 => floats[idx],
+                    _ => rand::random::<f64>() * 2e9 - 1e9,
+                })
+                .collect(),
+            f64::total_cmp,
+        );
+        check_radix_sort(
+            (0..len).map(|_| rand::random::<f32>() - 0.5).collect(),
+            f32::total_cmp,
+        );
+    }
+}
+
+#[test]
+fn lsd_radix_sort_by_key_is_stable() {
+    let mut vec = records(100_000);
+    vec.lsd_radix_sort_by_key(|v| v.0);
+    assert!(vec.is_sorted_by(|a, b| (a.0, &a.1) <= (b.0, &b.1)));
+}
+
+#[test]
+fn msd_radix_sort_by_key() {
+    let mut vec = records(100_000);
+    vec.msd_radix_sort_by_key(|v| -v.0);
+    assert!(vec.is_sorted_by(|a, b| a.0 >= b.0));
+}
+
+#[test]
+fn counting_sort_by_key_is_stable() {
+    let mut vec = records(100_000);
+    vec.counting_sort_by_key(|v| v.0 as usize);
+    assert!(vec.is_sorted_by(|a, b| (a.0, &a.1) <= (b.0, &b.1)));
+}
+
+#[test]
+fn counting_sort_wide_keys() {
+    let mut vec = [(1 << 40) + 3, 1 << 40, (1 << 40) + 1];
+    vec.counting_sort_by_key(|&v| v);
+    assert_eq!(vec, [1 << 40, (1 << 40) + 1, (1 << 40) + 3]);
+    let mut vec = [
+        (usize::MAX, 0),
+        (0, 1),
+        (usize::MAX, 2),
+        (1 << 40, 3),
+        (0, 4),
+    ];
+    vec.counting_sort_by_key(|v| v.0);
+    assert_eq!(vec.map(|v| v.1), [1, 4, 3, 0, 2]);
+    let mut vec = records(10_000);
+    vec.counting_sort_by_key(|v| (v.0 as usize) << 20);
+    assert!(vec.is_sorted_by(|a, b| (a.0, &a.1) <= (b.0, &b.1)));
+}
+
+#[test]
+fn bucket_sort_1() {
+    for len in [0, 1, 2, 1_000, 100_000] {
+        let mut vec = (0..len)
+            .map(|idx| (rand::random::<f64>(), idx))
+            .collect::<Vec<_>>();
+        vec.bucket_sort_by_key(|v| v.0);
+        assert!(vec.is_sorted_by(|a, b| a.0 <= b.0));
+        let mut vec = records(len);
+        vec.bucket_sort_by_key(|v| f64::from(v.0));
+        assert!(vec.is_sorted_by(|a, b| (a.0, &a.1) <= (b.0, &b.1)));
+    }
+}
+
+#[test]
+fn bucket_sort_2() {
+    let mut vec = [f64::MAX, 1.0, f64::MIN, -1.0, 0.0];
+    vec.bucket_sort_by_key(|&v| v);
+    assert_eq!(vec, [f64::MIN, -1.0, 0.0, 1.0, f64::MAX]);
+    let mut vec = [f64::NAN, 1.0, f64::NEG_INFINITY, -1.0];
+    vec.bucket_sort_by_key(|&v| v);
+    assert_eq!(vec[..3], [f64::NEG_INFINITY, -1.0, 1.0]);
+    assert!(vec[3].is_nan());
+}
+
+#[test]
+fn par_sort() {
+    use crate::ParallelConfig;
+    for threads in [0, 1, 2, 3, 8] {
+        let config = ParallelConfig {
+            threads,
+            cutoff: 1_000,
+        };
+        let mut vec = (0..1_000_000)
+            .map(|_| rand::random_range(-1_000_000..1_000_000))
+            .collect::<Vec<_>>();
+        let mut expect = vec.clone();
+        expect.sort();
+        let mut other = vec.clone();
+        vec.par_quick_sort(config);
+        assert_eq!(vec, expect);
+        other.par_merge_sort_by(config, |a, b| b.cmp(a));
+        assert!(other.into_iter().eq(expect.into_iter().rev()));
+
+        let mut vec = (0..100_000).rev().collect::<Vec<_>>();
+        vec.par_quick_sort_by_key(config, |v| v / 3);
+        assert!(vec.is_sorted_by_key(|v| v / 3));
+
+        let mut vec = records(100_000);
+        vec.par_merge_sort_by_key(config, |v| v.0);
+        assert!(vec.is_sorted_by(|a, b| (a.0, &a.1) <= (b.0, &b.1)));
+    }
+}
+
+#[test]
+fn par_merge_sort_panic_safety() {
+    use crate::ParallelConfig;
+    use std::{
+        panic::{self, AssertUnwindSafe},
+        sync::atomic::{AtomicUsize, Ordering::Relaxed},
+    };
+    let config = ParallelConfig {
+        threads: 4,
+        cutoff: 1_000,
+    };
+    for limit in [10_000, 100_000, 300_000] {
+        let mut vec = records(20_000);
+        let cnt = AtomicUsize::new(0);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            vec.par_merge_sort_by(config, |a, b| {
+                assert!(cnt.fetch_add(1, Relaxed) < limit, "comparator panicked");
+                a.cmp(b)
+            })
+        }));
+        assert!(result.is_err());
+        let mut idx = vec.iter().map(|v| *v.1).collect::<Vec<_>>();
+        idx.quick_sort();
+        assert!(idx.into_iter().eq(0..20_000));
+    }
+}
+
+#[test]
+fn external_sort() {
+    use crate::external::{self, Codec, ExternalConfig, FixedWidth, LengthPrefixed, Lines};
+    use std::io::Cursor;
+
+    fn check<C: Codec<Record = Vec<u8>>>(codec: &C, records: &[Vec<u8>]) {
+        let mut input = Vec::new();
+        for record in records {
+            codec.write(&mut input, record).unwrap();
+        }
+        // The first byte is the key only, to check the stability.
+        let mut expect = records.to_vec();
+        expect.sort_by_key(|record| record.first().copied());
+        for (memory, fan_in) in [(usize::MAX, 16), (1 << 12, 16), (1 << 12, 2), (1, 3)] {
+            let config = ExternalConfig {
+                memory,
+                fan_in,
+                ..Default::default()
+            };
+            let mut output = Vec::new();
+            external::external_sort_by(&input[..], &mut output, codec, &config, |a, b| {
+                a.first().cmp(&b.first())
+            })
+            .unwrap();
+            let mut output = Cursor::new(output);
+            for record in &expect {
+                assert_eq!(codec.read(&mut output).unwrap().as_ref(), Some(record));
+            }
+            assert_eq!(codec.read(&mut output).unwrap(), None);
+        }
+    }
+    let records = (0..2_000)
+        .map(|_| {
+            (0..rand::random_range(0..20))
+                .map(|_| rand::random_range(b'a'..=b'z'))
+                .collect()
+        })
+        .collect::<Vec<Vec<u8>>>();
+    check(&Lines, &records);
+    check(&LengthPrefixed, &records);
+    let records = (0..2_000)
+        .map(|_| rand::random::<[u8; 8]>().to_vec())
+        .collect::<Vec<_>>();
+    check(&FixedWidth::new(8), &records);
+    check(&Lines, &[]);
+
+    let mut output = Vec::new();
+    external::external_sort(
+        &b"b\r\nc\na"[..],
+        &mut output,
+        &Lines,
+        &ExternalConfig::default(),
+    )
+    .unwrap();
+    assert_eq!(output, b"a\nb\r\nc\n");
+    let result = external::external_sort(
+        &[0; 13][..],
+        &mut output,
+        &FixedWidth::new(4),
+        &ExternalConfig::default(),
+    );
+    assert_eq!(
+        result.unwrap_err().kind(),
+        std::io::ErrorKind::UnexpectedEof
+    );
+}
+
+#[test]
+fn select_nth() {
+    for len in [1, 2, 17, 1_000, 100_000] {
+        let mut vec = (0..len)
+            .map(|_| rand::random_range(0..len / 2 + 1))
+            .collect::<Vec<_>>();
+        let mut expect = vec.clone();
+        expect.sort();
+        for n in [0, len / 3, len / 2, len - 1] {
+            assert_eq!(*vec.select_nth(n), expect[n]);
+            assert!(vec[..n].iter().all(|v| *v <= expect[n]));
+            assert!(vec[n..].iter().all(|v| *v >= expect[n]));
+        }
+    }
+    // Adversarial patterns for the pivots, which fall back to the median
+    // of medians, and many equal elements.
+    let len = 1_000_000;
+    for mut vec in [
+        (0..len).collect::<Vec<_>>(),
+        (0..len).rev().collect(),
+        (0..len).map(|v| v.min(len - v)).collect(),
+        (0..len).map(|v| v % 3).collect(),
+        vec![7; len],
+    ] {
+        let mut expect = vec.clone();
+        expect.sort();
+        let mut cnt = 0;
+        let nth = *vec.select_nth_by(len / 2, |a, b| {
+            cnt += 1;
+            a.cmp(b)
+        });
+        assert_eq!(nth, expect[len / 2]);
+        assert!(cnt < 20 * len, "{cnt} comparisons");
+    }
+    let mut vec = records(1_000);
+    let nth = vec.select_nth_by_key(10, |v| v.0).0;
+    assert!(vec[..10].iter().all(|v| v.0 <= nth));
+}
+
+#[test]
+fn partial_sort() {
+    for k in [0, 1, 10, 999, 1_000, 2_000] {
+        let mut vec = (0..1_000)
+            .map(|_| rand::random_range(0..100))
+            .collect::<Vec<_>>();
+        let mut expect = vec.clone();
+        expect.sort();
+        vec.partial_sort(k);
+        let k = k.min(1_000);
+        assert_eq!(vec[..k], expect[..k]);
+        let mut vec = records(1_000);
+        vec.partial_sort_by_key(k, |v| v.0);
+        assert!(vec[..k].is_sorted_by_key(|v| v.0));
+        assert!(vec[k..].iter().all(|v| k == 0 || v.0 >= vec[k - 1].0));
+        vec.partial_sort_by(k, |a, b| b.1.cmp(&a.1));
+        assert!(vec[..k].iter().map(|v| *v.1).eq((1_000 - k..1_000).rev()));
+    }
+}
+
+#[test]
+fn top_k() {
+    use crate::select;
+    let vec = (0..100_000)
+        .map(|_| rand::random_range(0..1_000))
+        .collect::<Vec<_>>();
+    let mut expect = vec.clone();
+    expect.sort();
+    for k in [0, 1, 100, 100_000, 200_000] {
+        assert_eq!(
+            select::top_k(vec.iter().copied(), k),
+            expect[..k.min(100_000)]
+        );
+    }
+    let top = select::top_k_by(records(10_000), 500, |a, b| b.0.cmp(&a.0));
+    assert!(top.is_sorted_by(|a, b| (b.0, &a.1) <= (a.0, &b.1)));
+    assert_eq!(top.len(), 500);
+    let top = select::top_k_by_key((0..10).map(|v| v % 3), 4, |&v| v);
+    assert_eq!(top, [0, 0, 0, 0]);
+}
+
+#[test]
+fn median_and_quantile() {
+    use crate::select;
+    assert_eq!(select::median::<i32>(&mut []), None);
+    assert_eq!(select::median(&mut [4, 1, 3, 2]), Some(&2));
+    assert_eq!(select::median(&mut [5, 1, 3]), Some(&3));
+    assert_eq!(select::median_f64(&mut []), None);
+    assert_eq!(select::median_f64(&mut [2.0]), Some(2.0));
+    assert_eq!(select::median_f64(&mut [5.0, 1.0, 3.0]), Some(3.0));
+    let mut vec = (1..=101).rev().map(f64::from).collect::<Vec<_>>();
+    assert_eq!(select::quantile_f64(&mut vec, 0.0), Some(1.0));
+    assert_eq!(select::quantile_f64(&mut vec, 0.9), Some(91.0));
+    assert_eq!(select::quantile_f64(&mut vec, 0.995), Some(100.5));
+    assert_eq!(select::quantile_f64(&mut vec, 1.0), Some(101.0));
+}
+
+#[test]
+fn harness() {
+    use crate::harness::{self, Algorithm, Distribution, Failure, Item, ALGORITHMS};
+    for algorithm in &ALGORITHMS {
+        for distribution in Distribution::STANDARD {
+            for len in [0, 1, 2, 100, 10_000] {
+                let report = harness::run(algorithm, distribution, len, len as u64);
+                assert_eq!(
+                    report.result,
+                    Ok(()),
+                    "{} on {distribution}",
+                    algorithm.name
+                );
+                let bound = 4 * len as u64 * u64::from(len.max(2).ilog2());
+                assert!(report.comparisons <= bound, "{report:?}");
+            }
+        }
+    }
+    assert_eq!(
+        Distribution::MostlySorted { swaps: 3 }.generate(8, 7),
+        Distribution::MostlySorted { swaps: 3 }.generate(8, 7)
+    );
+    assert_eq!(Distribution::OrganPipe.generate(5, 0), [0, 1, 2, 1, 0]);
+    assert_eq!(
+        Distribution::Sawtooth { period: 3 }.generate(5, 0),
+        [0, 1, 2, 0, 1]
+    );
+    // The first element as the pivot, which the killer adversary makes
+    // quadratic.
+    fn naive(v: &mut [Item], compare: &mut dyn FnMut(&Item, &Item) -> Ordering) {
+        if v.len() < 2 {
+            return;
+        }
+        let mut mid = 0;
+        for i in 1..v.len() {
+            if compare(&v[i], &v[0]) == Ordering::Less {
+                mid += 1;
+                v.swap(mid, i);
+            }
+        }
+        v.swap(0, mid);
+        naive(&mut v[..mid], compare);
+        naive(&mut v[mid + 1..], compare);
+    }
+    let naive = Algorithm {
+        name: "naive",
+        stable: false,
+        counts_swaps: false,
+        sort: naive,
+    };
+    let report = harness::run(&naive, Distribution::AntiQsort, 2_000, 0);
+    assert_eq!(report.result, Ok(()));
+    assert!(report.comparisons >= 2_000 * 1_999 / 2, "{report:?}");
+    let input = [1, 0, 1];
+    let item = |index: usize| Item {
+        key: input[index],
+        index,
+    };
+    assert_eq!(
+        harness::verify(&input, &[item(1), item(0), item(2)], true),
+        Ok(())
+    );
+    assert_eq!(
+        harness::verify(&input, &[item(1), item(2), item(0)], true),
+        Err(Failure::Unstable { index: 2 })
+    );
+    assert_eq!(
+        harness::verify(&input, &[item(1), item(2), item(0)], false),
+        Ok(())
+    );
+    assert_eq!(
+        harness::verify(&input, &[item(0), item(1), item(2)], false),
+        Err(Failure::Unsorted { index: 1 })
+    );
+    assert_eq!(
+        harness::verify(&input, &[item(1), item(0), item(0)], false),
+        Err(Failure::NotPermutation { index: 2 })
+    );
+    assert_eq!(
+        harness::verify(&input, &[item(1), item(0)], false),
+        Err(Failure::NotPermutation { index: 2 })
+    );
+}
+
+#[test]
+fn harness_swaps() {
+    use crate::harness::{self, Distribution, ALGORITHMS};
+    let swaps = |name: &str, distribution: Distribution, len: usize| {
+        let algorithm = ALGORITHMS.iter().find(|a| a.name == name).unwrap();
+        harness::run(algorithm, distribution, len, 0).swaps
+    };
+    // Building the heap swaps 4 to the root, then each of the 4 pops is a
+    // swap and a sifting of one level but the last.
+    assert_eq!(swaps("heap_sort", Distribution::Sorted, 5), Some(8));
+    assert_eq!(swaps("heap_sort", Distribution::AllEqual, 1_000), Some(999));
+    for name in ["quick_sort", "merge_sort", "tim_sort"] {
+        assert_eq!(swaps(name, Distribution::Sorted, 10_000), Some(0), "{name}");
+    }
+    // Reversing the only run.
+    assert_eq!(swaps("tim_sort", Distribution::Reversed, 1_000), Some(500));
+    // Each of the 190 inversions takes an adjacent swap.
+    assert_eq!(swaps("merge_sort", Distribution::Reversed, 20), Some(190));
+    assert!(swaps("quick_sort", Distribution::Random, 1_000).unwrap() > 0);
+    assert_eq!(swaps("std sort", Distribution::Random, 1_000), None);
+    assert_eq!(
+        swaps("std sort_unstable", Distribution::Random, 1_000),
+        None
+    );
+}
+
+#[test]
+fn heap_sort() {
+    for len in [0, 1, 2, 3, 4, 5, 17, 1_000] {
+        let vec = (0..len)
+            .map(|_| rand::random_range(0..100))
+            .collect::<Vec<i32>>();
+        let mut expect = vec.clone();
+        expect.sort();
+        assert_eq!(Solution::heap_sort(&vec, Ord::cmp), expect);
+        let mut vec = vec;
+        vec.heap_sort_by(|a, b| b.cmp(a));
+        expect.reverse();
+        assert_eq!(vec, expect);
+    }
+    let mut vec = records(1_000);
+    vec.heap_sort_by_key(|v| v.0);
+    assert!(vec.is_sorted_by_key(|v| v.0));
+    let mut idx = vec.iter().map(|v| *v.1).collect::<Vec<_>>();
+    idx.heap_sort();
+    assert!(idx.into_iter().eq(0..1_000));
+}
+
+#[test]
+fn dary_heap() {
+    use crate::DaryHeap;
+    use std::collections::BinaryHeap;
+    fn check<const D: usize>() {
+        let mut heap = DaryHeap::<i32, D>::new();
+        let mut expect = BinaryHeap::new();
+        for _ in 0..10_000 {
+            if rand::random_range(0..3) == 0 {
+                assert_eq!(
+                    heap.pop(),
+                    expect.pop().map(|v: std::cmp::Reverse<i32>| v.0)
+                );
+            } else {
+                let v = rand::random_range(0..1_000);
+                heap.push(v);
+                expect.push(std::cmp::Reverse(v));
+            }
+            assert_eq!(heap.peek(), expect.peek().map(|v| &v.0));
+            assert_eq!(heap.len(), expect.len());
+        }
+        let mut sorted = expect.into_sorted_vec();
+        sorted.reverse();
+        assert!(heap
+            .into_sorted_vec()
+            .into_iter()
+            .eq(sorted.into_iter().map(|v| v.0)));
+    }
+    check::<2>();
+    check::<3>();
+    check::<4>();
+    check::<16>();
+
+    // Dijkstra's algorithm on a grid graph, where each node goes to the
+    // right and down with pseudo-random weights.
+    const N: usize = 30;
+    let weight = |node: usize, next: usize| (node * 7 + next * 13) % 10 + 1;
+    let mut dist = vec![usize::MAX; N * N];
+    dist[0] = 0;
+    let nodes = dist.iter().enumerate().map(|(node, &d)| (d, node));
+    let (mut heap, handles) = DaryHeap::<_, 4>::heapify_with_handles(nodes.collect());
+    while let Some((d, node)) = heap.pop() {
+        let mut relax = |next: usize| {
+            let d = d + weight(node, next);
+            if d < dist[next] {
+                dist[next] = d;
+                heap.decrease_key(handles[next], (d, next));
+            }
+        };
+        if node % N + 1 < N {
+            relax(node + 1);
+        }
+        if node + N < N * N {
+            relax(node + N);
+        }
+    }
+    let mut expect = vec![0; N * N];
+    for node in 1..N * N {
+        let (row, col) = (node / N, node % N);
+        expect[node] = [
+            (col > 0).then(|| expect[node - 1] + weight(node - 1, node)),
+            (row > 0).then(|| expect[node - N] + weight(node - N, node)),
+        ]
+        .into_iter()
+        .flatten()
+        .min()
+        .unwrap();
+    }
+    assert_eq!(dist, expect);
+    assert_eq!(heap.get(handles[0]), None);
+
+    // The slot of a popped item is reused, but its handle stays stale.
+    let mut heap = DaryHeap::<_, 2>::new();
+    let a = heap.push(1);
+    assert_eq!(heap.pop(), Some(1));
+    let b = heap.push(2);
+    assert_ne!(a, b);
+    assert_eq!((heap.get(a), heap.get(b)), (None, Some(&2)));
+}
@@ -0,0 +1,363 @@
+use crate::{harness, merge::MergeHole};
+use std::{mem::MaybeUninit, ptr};
+
+/// Slices shorter than this are sorted by insertion without any merging.
+const MIN_MERGE: usize = 64;
+
+/// The initial number of consecutive wins to switch into galloping mode.
+const MIN_GALLOP: usize = 7;
+
+/// A sorted run `v[start..start + len]`.
+#[derive(Clone, Copy)]
+struct Run {
+    start: usize,
+    len: usize,
+}
+
+/// In-place TimSort, see [`crate::Solution::tim_sort`].
+///
+/// Natural runs are detected (descending ones are reversed), short runs
+/// are extended to `min_run` by insertion, and the runs are merged as they
+/// are pushed to a stack so that their lengths shrink at least as fast as
+/// the Fibonacci numbers. A single buffer of `len / 2` elements is enough,
+/// since each merging only copies out the shorter run.
+pub(crate) fn tim_sort<T, F>(v: &mut [T], is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let len = v.len();
+    if len < MIN_MERGE {
+        let run = find_run(v, is_less);
+        insertion_sort_from(v, run, is_less);
+        return;
+    }
+    let min_run = min_run(len);
+    let mut buf = Vec::with_capacity(len / 2);
+    let buf = buf.spare_capacity_mut();
+    let mut min_gallop = MIN_GALLOP;
+    // The lengths on the stack grow like the Fibonacci numbers from the
+    // top, so it never gets deeper than this in practice.
+    let mut runs = Vec::<Run>::with_capacity(usize::BITS as usize);
+    let mut start = 0;
+    while start < len {
+        let mut run = find_run(&mut v[start..], is_less);
+        if run < min_run {
+            let end = min_run.min(len - start);
+            insertion_sort_from(&mut v[start..start + end], run, is_less);
+            run = end;
+        }
+        runs.push(Run { start, len: run });
+        start += run;
+        while let Some(r) = collapse(&runs, len) {
+            let (left, right) = (runs[r], runs[r + 1]);
+            let v = &mut v[left.start..right.start + right.len];
+            merge(v, left.len, buf, &mut min_gallop, is_less);
+            runs[r].len += right.len;
+            runs.remove(r + 1);
+        }
+    }
+}
+
+/// Returns the length of the run at the start of `v`, a strictly
+/// descending run is reversed so that stability is kept.
+fn find_run<T, F>(v: &mut [T], is_less: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    let len = v.len();
+    if len < 2 {
+        return len;
+    }
+    let mut end = 2;
+    if is_less(&v[1], &v[0]) {
+        while end < len && is_less(&v[end], &v[end - 1]) {
+            end += 1;
+        }
+        v[..end].reverse();
+        harness::count_swaps(end / 2);
+    } else {
+        while end < len && !is_less(&v[end], &v[end - 1]) {
+            end += 1;
+        }
+    }
+    end
+}
+
+/// Inserts `v[offset..]` one by one into the sorted `v[..offset]`.
+fn insertion_sort_from<T, F>(v: &mut [T], offset: usize, is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    for i in offset.max(1)..v.len() {
+        let mut j = i;
+        while j > 0 && is_less(&v[j], &v[j - 1]) {
+            harness::swap(v, j, j - 1);
+            j -= 1;
+        }
+    }
+}
+
+/// The minimum length of a run, in `MIN_MERGE / 2..=MIN_MERGE`, so that
+/// `len / min_run` is a power of two or slightly less than it.
+fn min_run(mut len: usize) -> usize {
+    let mut rem = 0;
+    while len >= MIN_MERGE {
+        rem |= len & 1;
+        len >>= 1;
+    }
+    len + rem
+}
+
+/// Returns the index of the run to be merged with the next one, if the
+/// invariants of the stack don't hold, or all the runs are pushed.
+fn collapse(runs: &[Run], len: usize) -> Option<usize> {
+    let n = runs.len();
+    let last = runs.last()?;
+    let at_end = last.start + last.len == len;
+    if n >= 2
+        && (at_end
+            || runs[n - 2].len <= runs[n - 1].len
+            || (n >= 3 && runs[n - 3].len <= runs[n - 2].len + runs[n - 1].len)
+            || (n >= 4 && runs[n - 4].len <= runs[n - 3].len + runs[n - 2].len))
+    {
+        if n >= 3 && runs[n - 3].len < runs[n - 1].len {
+            Some(n - 3)
+        } else {
+            Some(n - 2)
+        }
+    } else {
+        None
+    }
+}
+
+/// Returns the first index in `0..len` that satisfies `pred`, where `pred`
+/// is false and then true, by an exponential search from either end.
+fn gallop<P>(len: usize, from_back: bool, mut pred: P) -> usize
+where
+    P: FnMut(usize) -> bool,
+{
+    let (mut lo, mut hi) = (0, len);
+    let mut step = 1;
+    if from_back {
+        while step <= len && pred(len - step) {
+            hi = len - step;
+            step <<= 1;
+        }
+        if step <= len {
+            lo = len - step + 1;
+        }
+    } else {
+        while step <= len && !pred(step - 1) {
+            lo = step;
+            step <<= 1;
+        }
+        if step <= len {
+            hi = step - 1;
+        }
+    }
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(mid) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    lo
+}
+
+/// Merges the sorted runs `v[..mid]` and `v[mid..]`.
+///
+/// The elements already in place at both ends are skipped first, then the
+/// shorter one of the remaining runs is copied into `buf`.
+fn merge<T, F>(
+    v: &mut [T],
+    mid: usize,
+    buf: &mut [MaybeUninit<T>],
+    min_gallop: &mut usize,
+    is_less: &mut F,
+) where
+    F: FnMut(&T, &T) -> bool,
+{
+    let skip = gallop(mid, false, |i| is_less(&v[mid], &v[i]));
+    let (v, mid) = (&mut v[skip..], mid - skip);
+    if mid == 0 {
+        return;
+    }
+    let (left, right) = v.split_at(mid);
+    let keep = gallop(right.len(), true, |i| !is_less(&right[i], &left[mid - 1]));
+    if keep == 0 {
+        return;
+    }
+    let v = &mut v[..mid + keep];
+    // SAFETY: the shorter run is at most half of the whole slice.
+    unsafe {
+        if mid <= keep {
+            merge_lo(v, mid, buf, min_gallop, is_less);
+        } else {
+            merge_hi(v, mid, buf, min_gallop, is_less);
+        }
+    }
+}
+
+/// Merges by copying out the left run and filling `v` from the front.
+///
+/// # Safety
+///
+/// Both runs must be non-empty, and `buf` must be able to hold `mid`
+/// elements.
+unsafe fn merge_lo<T, F>(
+    v: &mut [T],
+    mid: usize,
+    buf: &mut [MaybeUninit<T>],
+    min_gallop: &mut usize,
+    is_less: &mut F,
+) where
+    F: FnMut(&T, &T) -> bool,
+{
+    debug_assert!(0 < mid && mid < v.len() && mid <= buf.len());
+    let len = v.len();
+    let v = v.as_mut_ptr();
+    let buf = buf.as_mut_ptr() as *mut T;
+    unsafe {
+        ptr::copy_nonoverlapping(v, buf, mid);
+        harness::count_swaps(mid);
+        // The gap between `hole.dest` and `right` always holds exactly the
+        // rest of the left run, which is what the hole fills when dropped.
+        let mut hole = MergeHole {
+            start: buf,
+            end: buf.add(mid),
+            dest: v,
+        };
+        let (mut right, end) = (v.add(mid), v.add(len));
+        'merge: loop {
+            let (mut wins_left, mut wins_right) = (0, 0);
+            while wins_left.max(wins_right) < *min_gallop {
+                if is_less(&*right, &*hole.start) {
+                    ptr::copy_nonoverlapping(right, hole.dest, 1);
+                    harness::count_swaps(1);
+                    right = right.add(1);
+                    (wins_left, wins_right) = (0, wins_right + 1);
+                } else {
+                    ptr::copy_nonoverlapping(hole.start, hole.dest, 1);
+                    harness::count_swaps(1);
+                    hole.start = hole.start.add(1);
+                    (wins_left, wins_right) = (wins_left + 1, 0);
+                }
+                hole.dest = hole.dest.add(1);
+                if hole.start == hole.end || right == end {
+                    break 'merge;
+                }
+            }
+            loop {
+                let rest = hole.end.offset_from(hole.start) as usize;
+                let count_left = gallop(rest, false, |i| is_less(&*right, &*hole.start.add(i)));
+                ptr::copy_nonoverlapping(hole.start, hole.dest, count_left);
+                harness::count_swaps(count_left);
+                hole.start = hole.start.add(count_left);
+                hole.dest = hole.dest.add(count_left);
+                if hole.start == hole.end {
+                    break 'merge;
+                }
+                let rest = end.offset_from(right) as usize;
+                let count_right = gallop(rest, false, |i| !is_less(&*right.add(i), &*hole.start));
+                ptr::copy(right, hole.dest, count_right);
+                harness::count_swaps(count_right);
+                right = right.add(count_right);
+                hole.dest = hole.dest.add(count_right);
+                if right == end {
+                    break 'merge;
+                }
+                *min_gallop = min_gallop.saturating_sub(1);
+                if count_left < MIN_GALLOP && count_right < MIN_GALLOP {
+                    *min_gallop += 2;
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Merges by copying out the right run and filling `v` from the back.
+///
+/// # Safety
+///
+/// Both runs must be non-empty, and `buf` must be able to hold
+/// `v.len() - mid` elements.
+unsafe fn merge_hi<T, F>(
+    v: &mut [T],
+    mid: usize,
+    buf: &mut [MaybeUninit<T>],
+    min_gallop: &mut usize,
+    is_less: &mut F,
+) where
+    F: FnMut(&T, &T) -> bool,
+{
+    debug_assert!(0 < mid && mid < v.len() && v.len() - mid <= buf.len());
+    let len = v.len();
+    let v = v.as_mut_ptr();
+    let buf = buf.as_mut_ptr() as *mut T;
+    unsafe {
+        ptr::copy_nonoverlapping(v.add(mid), buf, len - mid);
+        harness::count_swaps(len - mid);
+        // The left run remains in `v..hole.dest`, and the gap between
+        // `hole.dest` and `out` always holds exactly the rest of the right
+        // run, which is what the hole fills when dropped.
+        let mut hole = MergeHole {
+            start: buf,
+            end: buf.add(len - mid),
+            dest: v.add(mid),
+        };
+        let mut out = v.add(len);
+        'merge: loop {
+            let (mut wins_left, mut wins_right) = (0, 0);
+            while wins_left.max(wins_right) < *min_gallop {
+                let (left, right) = (hole.dest.sub(1), hole.end.sub(1));
+                out = out.sub(1);
+                if is_less(&*right, &*left) {
+                    ptr::copy_nonoverlapping(left, out, 1);
+                    harness::count_swaps(1);
+                    hole.dest = left;
+                    (wins_left, wins_right) = (wins_left + 1, 0);
+                } else {
+                    ptr::copy_nonoverlapping(right, out, 1);
+                    harness::count_swaps(1);
+                    hole.end = right;
+                    (wins_left, wins_right) = (0, wins_right + 1);
+                }
+                if hole.dest == v || hole.start == hole.end {
+                    break 'merge;
+                }
+            }
+            loop {
+                let right = hole.end.sub(1);
+                let rest = hole.dest.offset_from(v) as usize;
+                let idx = gallop(rest, true, |i| is_less(&*right, &*v.add(i)));
+                let count_left = rest - idx;
+                out = out.sub(count_left);
+                ptr::copy(v.add(idx), out, count_left);
+                harness::count_swaps(count_left);
+                hole.dest = v.add(idx);
+                if hole.dest == v {
+                    break 'merge;
+                }
+                let left = hole.dest.sub(1);
+                let rest = hole.end.offset_from(hole.start) as usize;
+                let idx = gallop(rest, true, |i| !is_less(&*hole.start.add(i), &*left));
+                let count_right = rest - idx;
+                out = out.sub(count_right);
+                ptr::copy_nonoverlapping(hole.start.add(idx), out, count_right);
+                harness::count_swaps(count_right);
+                hole.end = hole.start.add(idx);
+                if hole.start == hole.end {
+                    break 'merge;
+                }
+                *min_gallop = min_gallop.saturating_sub(1);
+                if count_left < MIN_GALLOP && count_right < MIN_GALLOP {
+                    *min_gallop += 2;
+                    break;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,402 @@
+//! Lazy permutations, combinations and power sets.
+//!
+//! Every iterator keeps the items and O(n) indices into them, and only
+//! clones the items of each arrangement as it's yielded. The arrangements
+//! are in lexicographic order of the positions, so they are sorted if the
+//! items are. See [`multiset`] for the items with duplicates.
+
+pub mod multiset;
+
+/// Rearranges `v` into the next permutation in lexicographic order, and
+/// returns `true`. If `v` is the last one, i.e. descending, it's reversed
+/// into the first one and `false` is returned.
+///
+/// Equal items are not told apart, so every distinct permutation comes
+/// exactly once.
+pub fn next_permutation<T: Ord>(v: &mut [T]) -> bool {
+    // The longest descending suffix can't be increased by itself.
+    let Some(pivot) = (1..v.len()).rev().find(|&i| v[i - 1] < v[i]).map(|i| i - 1) else {
+        v.reverse();
+        return false;
+    };
+    // The least one in the suffix greater than the pivot.
+    let next = (pivot + 1..v.len())
+        .rev()
+        .find(|&i| v[pivot] < v[i])
+        .unwrap();
+    v.swap(pivot, next);
+    v[pivot + 1..].reverse();
+    true
+}
+
+/// The rank of the permutation `perm` of `0..n` in lexicographic order,
+/// or `None` if it overflows.
+///
+/// The digits of the rank in the factorial number system (factoradic) are
+/// the counts of the smaller ones on the right of each position.
+pub fn rank_permutation(perm: &[usize]) -> Option<u128> {
+    let n = perm.len();
+    let mut rank = 0u128;
+    for (i, &p) in perm.iter().enumerate() {
+        let digit = perm[i + 1..].iter().filter(|&&q| q < p).count();
+        rank = rank
+            .checked_mul((n - i) as u128)?
+            .checked_add(digit as u128)?;
+    }
+    Some(rank)
+}
+
+/// The permutation of `0..n` of `rank` in lexicographic order, or `None`
+/// if `rank` is not less than `n!`.
+pub fn unrank_permutation(n: usize, mut rank: u128) -> Option<Vec<usize>> {
+    // The factoradic digits, from the least significant one.
+    let mut digits = vec![0; n];
+    for (radix, digit) in (1..=n).zip(digits.iter_mut().rev()) {
+        *digit = (rank % radix as u128) as usize;
+        rank /= radix as u128;
+    }
+    if rank > 0 {
+        return None;
+    }
+    let mut rest = (0..n).collect::<Vec<_>>();
+    Some(digits.into_iter().map(|digit| rest.remove(digit)).collect())
+}
+
+/// Clones the items of `pool` at `idx`.
+fn pick<T: Clone>(pool: &[T], idx: &[usize]) -> Vec<T> {
+    idx.iter().map(|&i| pool[i].clone()).collect()
+}
+
+/// All the `n!` permutations in lexicographic order, by
+/// [`next_permutation`] on the positions.
+///
+/// [`Iterator::nth`] jumps to the permutation by its rank, instead of
+/// going through all the ones in between.
+///
+/// # Examples
+///
+/// ```
+/// use try_1::combinatorics::Permutations;
+///
+/// let mut perms = Permutations::new("abc".chars());
+/// assert_eq!(perms.next(), Some(vec!['a', 'b', 'c']));
+/// assert_eq!(perms.nth(3), Some(vec!['c', 'a', 'b']));
+/// assert_eq!(perms.next(), Some(vec!['c', 'b', 'a']));
+/// assert_eq!(perms.next(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Permutations<T> {
+    pool: Vec<T>,
+    /// The positions of the next permutation.
+    idx: Vec<usize>,
+    done: bool,
+}
+
+impl<T: Clone> Permutations<T> {
+    pub fn new<I: IntoIterator<Item = T>>(items: I) -> Self {
+        let pool = items.into_iter().collect::<Vec<_>>();
+        Self {
+            idx: (0..pool.len()).collect(),
+            pool,
+            done: false,
+        }
+    }
+}
+
+impl<T: Clone> Iterator for Permutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+        let perm = pick(&self.pool, &self.idx);
+        self.done = !next_permutation(&mut self.idx);
+        Some(perm)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+        let Some(rank) = rank_permutation(&self.idx).and_then(|rank| rank.checked_add(n as u128))
+        else {
+            // Too many items to be ranked, so go one by one.
+            return (0..n)
+                .try_for_each(|_| self.next().map(drop))
+                .and_then(|_| self.next());
+        };
+        match unrank_permutation(self.idx.len(), rank) {
+            Some(idx) => {
+                self.idx = idx;
+                self.next()
+            }
+            None => {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
+/// All the `n!` permutations by Heap's algorithm, each of which differs
+/// from the previous one by a single swap.
+///
+/// # Examples
+///
+/// ```
+/// use try_1::combinatorics::HeapPermutations;
+///
+/// let perms = HeapPermutations::new([1, 2, 3]).collect::<Vec<_>>();
+/// assert_eq!(perms, [[1, 2, 3], [2, 1, 3], [3, 1, 2], [1, 3, 2], [2, 3, 1], [3, 2, 1]]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HeapPermutations<T> {
+    /// The items in the order of the last permutation.
+    pool: Vec<T>,
+    /// The loop counters of the recursive form, `counts[i]` for the ones
+    /// of `pool[..=i]`.
+    counts: Vec<usize>,
+    /// The level of the recursion, `None` before the first permutation.
+    level: Option<usize>,
+}
+
+impl<T: Clone> HeapPermutations<T> {
+    pub fn new<I: IntoIterator<Item = T>>(items: I) -> Self {
+        let pool = items.into_iter().collect::<Vec<_>>();
+        Self {
+            counts: vec![0; pool.len()],
+            pool,
+            level: None,
+        }
+    }
+}
+
+impl<T: Clone> Iterator for HeapPermutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        let Some(mut level) = self.level else {
+            self.level = Some(1);
+            return Some(self.pool.clone());
+        };
+        while level < self.pool.len() {
+            if self.counts[level] < level {
+                let other = if level % 2 == 0 {
+                    0
+                } else {
+                    self.counts[level]
+                };
+                self.pool.swap(other, level);
+                self.counts[level] += 1;
+                self.level = Some(1);
+                return Some(self.pool.clone());
+            }
+            self.counts[level] = 0;
+            level += 1;
+        }
+        self.level = Some(level);
+        None
+    }
+}
+
+/// All the `n! / (n - k)!` arrangements of `k` items in lexicographic order.
+///
+/// # Examples
+///
+/// ```
+/// use try_1::combinatorics::KPermutations;
+///
+/// let perms = KPermutations::new([1, 2, 3], 2).collect::<Vec<_>>();
+/// assert_eq!(perms, [[1, 2], [1, 3], [2, 1], [2, 3], [3, 1], [3, 2]]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct KPermutations<T> {
+    pool: Vec<T>,
+    k: usize,
+    /// The next arrangement is `idx[..k]`, and the rest is ascending.
+    idx: Vec<usize>,
+    done: bool,
+}
+
+impl<T: Clone> KPermutations<T> {
+    pub fn new<I: IntoIterator<Item = T>>(items: I, k: usize) -> Self {
+        let pool = items.into_iter().collect::<Vec<_>>();
+        Self {
+            k,
+            idx: (0..pool.len()).collect(),
+            done: k > pool.len(),
+            pool,
+        }
+    }
+}
+
+impl<T: Clone> Iterator for KPermutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+        let perm = pick(&self.pool, &self.idx[..self.k]);
+        // The greatest permutation with the same prefix has the rest
+        // descending, so the next one after it has the next prefix.
+        self.idx[self.k..].reverse();
+        self.done = !next_permutation(&mut self.idx);
+        Some(perm)
+    }
+}
+
+/// All the `C(n, k)` combinations of `k` items in lexicographic order.
+///
+/// # Examples
+///
+/// ```
+/// use try_1::combinatorics::Combinations;
+///
+/// let combs = Combinations::new([1, 2, 3, 4], 2).collect::<Vec<_>>();
+/// assert_eq!(combs, [[1, 2], [1, 3], [1, 4], [2, 3], [2, 4], [3, 4]]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Combinations<T> {
+    pool: Vec<T>,
+    /// The ascending positions of the next combination.
+    idx: Vec<usize>,
+    done: bool,
+}
+
+impl<T: Clone> Combinations<T> {
+    pub fn new<I: IntoIterator<Item = T>>(items: I, k: usize) -> Self {
+        let pool = items.into_iter().collect::<Vec<_>>();
+        Self {
+            idx: (0..k).collect(),
+            done: k > pool.len(),
+            pool,
+        }
+    }
+}
+
+impl<T: Clone> Iterator for Combinations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+        let comb = pick(&self.pool, &self.idx);
+        self.done = !next_combination(&mut self.idx, self.pool.len());
+        Some(comb)
+    }
+}
+
+/// Advances the ascending positions `idx` in `0..n` to the next
+/// combination, returns `false` if it's the last one.
+fn next_combination(idx: &mut [usize], n: usize) -> bool {
+    let k = idx.len();
+    // The rightmost position which isn't at its maximum.
+    let Some(i) = (0..k).rev().find(|&i| idx[i] < n - k + i) else {
+        return false;
+    };
+    idx[i] += 1;
+    for j in i + 1..k {
+        idx[j] = idx[j - 1] + 1;
+    }
+    true
+}
+
+/// All the `C(n + k - 1, k)` combinations of `k` items, where an item can
+/// be chosen more than once, in lexicographic order.
+///
+/// # Examples
+///
+/// ```
+/// use try_1::combinatorics::CombinationsWithReplacement;
+///
+/// let combs = CombinationsWithReplacement::new(['x', 'y'], 3).collect::<Vec<_>>();
+/// assert_eq!(combs, [['x', 'x', 'x'], ['x', 'x', 'y'], ['x', 'y', 'y'], ['y', 'y', 'y']]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CombinationsWithReplacement<T> {
+    pool: Vec<T>,
+    /// The non-descending positions of the next combination.
+    idx: Vec<usize>,
+    done: bool,
+}
+
+impl<T: Clone> CombinationsWithReplacement<T> {
+    pub fn new<I: IntoIterator<Item = T>>(items: I, k: usize) -> Self {
+        let pool = items.into_iter().collect::<Vec<_>>();
+        Self {
+            idx: vec![0; k],
+            done: pool.is_empty() && k > 0,
+            pool,
+        }
+    }
+}
+
+impl<T: Clone> Iterator for CombinationsWithReplacement<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+        let comb = pick(&self.pool, &self.idx);
+        let n = self.pool.len();
+        match self.idx.iter().rposition(|&i| i + 1 < n) {
+            Some(i) => {
+                let next = self.idx[i] + 1;
+                self.idx[i..].fill(next);
+            }
+            None => self.done = true,
+        }
+        Some(comb)
+    }
+}
+
+/// All the `2^n` subsets, by the size and then in lexicographic order.
+///
+/// # Examples
+///
+/// ```
+/// use try_1::combinatorics::PowerSet;
+///
+/// let sets = PowerSet::new([1, 2, 3]).collect::<Vec<_>>();
+/// assert_eq!(sets, [vec![], vec![1], vec![2], vec![3], vec![1, 2], vec![1, 3], vec![2, 3], vec![1, 2, 3]]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PowerSet<T> {
+    pool: Vec<T>,
+    /// The ascending positions of the next subset.
+    idx: Vec<usize>,
+    done: bool,
+}
+
+impl<T: Clone> PowerSet<T> {
+    pub fn new<I: IntoIterator<Item = T>>(items: I) -> Self {
+        Self {
+            pool: items.into_iter().collect(),
+            idx: Vec::new(),
+            done: false,
+        }
+    }
+}
+
+impl<T: Clone> Iterator for PowerSet<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+        let set = pick(&self.pool, &self.idx);
+        let n = self.pool.len();
+        if !next_combination(&mut self.idx, n) {
+            // The first subset of the next size.
+            let k = self.idx.len() + 1;
+            self.done = k > n;
+            self.idx = (0..k).collect();
+        }
+        Some(set)
+    }
+}
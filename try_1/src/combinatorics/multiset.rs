@@ -0,0 +1,250 @@
+//! Permutations and combinations of multisets, where equal items are not
+//! told apart, so each distinct arrangement comes exactly once.
+//!
+//! The items are sorted and replaced by the indices of their distinct
+//! values, and the arrangements are in lexicographic order of the values.
+
+use super::{next_permutation, pick};
+
+/// The binomial coefficient `C(n, k)`, or `None` if it overflows.
+pub fn binomial(n: usize, k: usize) -> Option<u128> {
+    if k > n {
+        return Some(0);
+    }
+    let k = k.min(n - k);
+    // Each prefix of the product is a binomial coefficient itself, so the
+    // division is exact, and done first so that only the result overflows.
+    (0..k).try_fold(1u128, |c, i| {
+        let (num, den) = ((n - i) as u128, (i + 1) as u128);
+        let g = gcd(c, den);
+        (c / g).checked_mul(num / (den / g))
+    })
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b > 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The multinomial coefficient `(c_1 + c_2 + ... + c_m)! / (c_1! c_2! ... c_m!)`,
+/// i.e. the number of distinct permutations of a multiset whose values
+/// occur `counts` times, or `None` if it overflows.
+pub fn multinomial(counts: &[usize]) -> Option<u128> {
+    let mut total = 0;
+    counts.iter().try_fold(1u128, |m, &count| {
+        total += count;
+        m.checked_mul(binomial(total, count)?)
+    })
+}
+
+/// Sorts the items into their distinct values, and the indices of the
+/// values for the items.
+fn distinct<T: Ord>(items: impl IntoIterator<Item = T>) -> (Vec<T>, Vec<usize>) {
+    let mut items = items.into_iter().collect::<Vec<_>>();
+    items.sort();
+    let mut ids = Vec::with_capacity(items.len());
+    for i in 0..items.len() {
+        let id = ids
+            .last()
+            .map_or(0, |&id| id + usize::from(items[i - 1] < items[i]));
+        ids.push(id);
+    }
+    items.dedup();
+    (items, ids)
+}
+
+/// How many times each value occurs, where `ids` is sorted.
+fn counts(ids: &[usize]) -> Vec<usize> {
+    ids.chunk_by(|a, b| a == b).map(<[_]>::len).collect()
+}
+
+/// All the distinct arrangements of `k` items of a multiset, in
+/// lexicographic order.
+///
+/// # Examples
+///
+/// ```
+/// use try_1::combinatorics::multiset::MultisetPermutations;
+///
+/// let perms = MultisetPermutations::new([2, 1, 1]);
+/// assert_eq!(perms.total(), Some(3));
+/// assert_eq!(perms.collect::<Vec<_>>(), [[1, 1, 2], [1, 2, 1], [2, 1, 1]]);
+///
+/// let perms = MultisetPermutations::with_k("aab".chars(), 2);
+/// assert_eq!(perms.total(), Some(3));
+/// assert_eq!(perms.collect::<Vec<_>>(), [['a', 'a'], ['a', 'b'], ['b', 'a']]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultisetPermutations<T> {
+    values: Vec<T>,
+    k: usize,
+    /// The next arrangement is `ids[..k]`, and the rest is ascending.
+    ids: Vec<usize>,
+    done: bool,
+}
+
+impl<T: Ord + Clone> MultisetPermutations<T> {
+    /// The permutations of all the items.
+    pub fn new<I: IntoIterator<Item = T>>(items: I) -> Self {
+        let items = items.into_iter().collect::<Vec<_>>();
+        let k = items.len();
+        Self::with_k(items, k)
+    }
+
+    /// The arrangements of `k` items.
+    pub fn with_k<I: IntoIterator<Item = T>>(items: I, k: usize) -> Self {
+        let (values, ids) = distinct(items);
+        Self {
+            values,
+            k,
+            done: k > ids.len(),
+            ids,
+        }
+    }
+}
+
+impl<T> MultisetPermutations<T> {
+    /// The number of all the arrangements, including those yielded
+    /// already, or `None` if it overflows.
+    ///
+    /// It's the multinomial coefficient if all the items are taken, and
+    /// `k!` times the coefficient of `x^k` in the product of
+    /// `1 + x + x^2 / 2! + ... + x^c / c!` for the count `c` of each value
+    /// otherwise.
+    pub fn total(&self) -> Option<u128> {
+        let counts = counts(&self.ids);
+        if self.k > self.ids.len() {
+            return Some(0);
+        } else if self.k == self.ids.len() {
+            return multinomial(&counts);
+        }
+        // `ways[j]` is the number of the arrangements of `j` items of the
+        // values so far, where `t` items of the next value take `C(j, t)`
+        // ways to be placed.
+        let mut ways = vec![0u128; self.k + 1];
+        ways[0] = 1;
+        for count in counts {
+            for j in (1..=self.k).rev() {
+                ways[j] = (0..=count.min(j)).try_fold(0u128, |sum, t| {
+                    sum.checked_add(ways[j - t].checked_mul(binomial(j, t)?)?)
+                })?;
+            }
+        }
+        Some(ways[self.k])
+    }
+}
+
+impl<T: Ord + Clone> Iterator for MultisetPermutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+        let perm = pick(&self.values, &self.ids[..self.k]);
+        self.ids[self.k..].reverse();
+        self.done = !next_permutation(&mut self.ids);
+        Some(perm)
+    }
+}
+
+/// All the distinct combinations of `k` items of a multiset, each of which
+/// is sorted, in lexicographic order.
+///
+/// # Examples
+///
+/// ```
+/// use try_1::combinatorics::multiset::MultisetCombinations;
+///
+/// let combs = MultisetCombinations::new([1, 2, 1, 3], 2);
+/// assert_eq!(combs.total(), Some(4));
+/// assert_eq!(combs.collect::<Vec<_>>(), [[1, 1], [1, 2], [1, 3], [2, 3]]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultisetCombinations<T> {
+    values: Vec<T>,
+    /// The sorted value indices of all the items.
+    ids: Vec<usize>,
+    /// `skip[i]` is the first position with a greater value than `ids[i]`.
+    skip: Vec<usize>,
+    /// The ascending positions of the next combination, the first ones of
+    /// each run of equal values.
+    idx: Vec<usize>,
+    done: bool,
+}
+
+impl<T: Ord + Clone> MultisetCombinations<T> {
+    pub fn new<I: IntoIterator<Item = T>>(items: I, k: usize) -> Self {
+        let (values, ids) = distinct(items);
+        let mut skip = vec![ids.len(); ids.len()];
+        for i in (0..ids.len().saturating_sub(1)).rev() {
+            skip[i] = if ids[i] < ids[i + 1] {
+                i + 1
+            } else {
+                skip[i + 1]
+            };
+        }
+        Self {
+            values,
+            done: k > ids.len(),
+            idx: (0..k).collect(),
+            ids,
+            skip,
+        }
+    }
+}
+
+impl<T> MultisetCombinations<T> {
+    /// The number of all the combinations, including those yielded
+    /// already, or `None` if it overflows.
+    pub fn total(&self) -> Option<u128> {
+        let k = self.idx.len();
+        if k > self.ids.len() {
+            return Some(0);
+        }
+        // `ways[j]` is the number of the combinations of `j` items of the
+        // values so far.
+        let mut ways = vec![0u128; k + 1];
+        ways[0] = 1;
+        for count in counts(&self.ids) {
+            for j in (1..=k).rev() {
+                ways[j] =
+                    (0..=count.min(j)).try_fold(0u128, |sum, t| sum.checked_add(ways[j - t]))?;
+            }
+        }
+        Some(ways[k])
+    }
+}
+
+impl<T: Clone> Iterator for MultisetCombinations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+        let comb = self
+            .idx
+            .iter()
+            .map(|&i| self.values[self.ids[i]].clone())
+            .collect();
+        let (n, k) = (self.ids.len(), self.idx.len());
+        // The rightmost one which can move to a greater value, with room
+        // for the ones after it.
+        match (0..k)
+            .rev()
+            .find(|&i| self.skip[self.idx[i]] + (k - i) <= n)
+        {
+            Some(i) => {
+                let start = self.skip[self.idx[i]];
+                for (j, pos) in (i..k).zip(start..) {
+                    self.idx[j] = pos;
+                }
+            }
+            None => self.done = true,
+        }
+        Some(comb)
+    }
+}
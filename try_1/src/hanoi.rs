@@ -0,0 +1,381 @@
+//! The Tower of Hanoi with move lists, any configurations, and more pegs.
+//!
+//! The disks are numbered from `0`, the smallest one. A configuration
+//! gives the peg of each disk, e.g. `config[0]` is the peg of the smallest
+//! disk, since the disks on a peg are always stacked by size. A move is
+//! `(disk, from, to)`.
+
+use std::fmt::{self, Display, Formatter};
+
+/// A move of `(disk, from, to)`.
+pub type Move = (usize, usize, usize);
+
+/// The peg of three other than `a` and `b`.
+fn third(a: usize, b: usize) -> usize {
+    3 - a - b
+}
+
+/// The moves of a tower of `n` disks from peg `from` to peg `to` of three,
+/// `2^n - 1` in total.
+///
+/// The `m`-th move (from `1`) is of the disk `trailing_zeros(m)`, from peg
+/// `(m & (m - 1)) mod 3` to peg `((m | (m - 1)) + 1) mod 3`, which takes a
+/// tower from peg `0` to peg `2` if `n` is odd, or to peg `1` otherwise,
+/// so the pegs are relabeled.
+///
+/// # Examples
+///
+/// ```
+/// use try_1::hanoi;
+///
+/// let moves = hanoi::moves(2, 0, 2).collect::<Vec<_>>();
+/// assert_eq!(moves, [(0, 0, 1), (1, 0, 2), (0, 1, 2)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Moves {
+    /// The pegs of the labels by the formula.
+    pegs: [usize; 3],
+    next: u128,
+    total: u128,
+}
+
+impl Iterator for Moves {
+    type Item = Move;
+
+    fn next(&mut self) -> Option<Move> {
+        if self.next > self.total {
+            return None;
+        }
+        let m = self.next;
+        self.next += 1;
+        let (from, to) = ((m & (m - 1)) % 3, ((m | (m - 1)) + 1) % 3);
+        Some((
+            m.trailing_zeros() as usize,
+            self.pegs[from as usize],
+            self.pegs[to as usize],
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let rest = self.total + 1 - self.next;
+        let rest = usize::try_from(rest).unwrap_or(usize::MAX);
+        (rest, Some(rest))
+    }
+}
+
+/// Moves a tower of `n` disks from peg `from` to peg `to` of `0..3`.
+///
+/// Panics if `n` is more than `127`, or the pegs are not distinct ones of
+/// `0..3`.
+pub fn moves(n: usize, from: usize, to: usize) -> Moves {
+    assert!(n < 128, "too many disks");
+    assert!(from < 3 && to < 3 && from != to, "invalid pegs");
+    let mut pegs = [third(from, to); 3];
+    pegs[0] = from;
+    pegs[if n % 2 == 1 { 2 } else { 1 }] = to;
+    Moves {
+        pegs,
+        next: 1,
+        total: (1 << n) - 1,
+    }
+}
+
+/// The fewest moves to put the disks `0..k` of `config` onto `peg`.
+///
+/// Working down from the largest one, a disk not on `peg` has to go there
+/// with the smaller ones on the third peg, which then form a tower.
+fn tower_cost(config: &[usize], peg: usize) -> u128 {
+    let mut peg = peg;
+    let mut cost = 0;
+    for (disk, &on) in config.iter().enumerate().rev() {
+        if on != peg {
+            cost += 1 << disk;
+            peg = third(on, peg);
+        }
+    }
+    cost
+}
+
+/// Moves the disks `0..k` of `config` onto `peg` in the fewest moves.
+fn to_tower(config: &mut [usize], k: usize, peg: usize, out: &mut Vec<Move>) {
+    let Some(disk) = (0..k).rev().find(|&disk| config[disk] != peg) else {
+        return;
+    };
+    let from = config[disk];
+    to_tower(config, disk, third(from, peg), out);
+    out.push((disk, from, peg));
+    config[disk] = peg;
+    to_tower(config, disk, peg, out);
+}
+
+/// Moves the tower of the disks `0..k` on `peg` to `target`, which is the
+/// reverse of [`to_tower`] from `target`.
+fn from_tower(target: &[usize], k: usize, peg: usize, out: &mut Vec<Move>) {
+    let mut back = Vec::new();
+    to_tower(&mut target[..k].to_vec(), k, peg, &mut back);
+    out.extend(
+        back.into_iter()
+            .rev()
+            .map(|(disk, from, to)| (disk, to, from)),
+    );
+}
+
+/// How to move the largest disk which is not in place.
+enum Plan {
+    /// Once, with the smaller ones on the third peg.
+    Once,
+    /// Twice, via the third peg, with the smaller ones on the target peg
+    /// and then on the start peg.
+    Twice,
+}
+
+/// The plan for the largest disk `k` of `start` not in place in `target`,
+/// and the number of moves it takes.
+fn plan(start: &[usize], target: &[usize], k: usize) -> (Plan, u128) {
+    let (from, to) = (start[k], target[k]);
+    let via = third(from, to);
+    let once = tower_cost(&start[..k], via) + 1 + tower_cost(&target[..k], via);
+    let twice =
+        tower_cost(&start[..k], to) + 1 + ((1 << k) - 1) + 1 + tower_cost(&target[..k], from);
+    if twice < once {
+        (Plan::Twice, twice)
+    } else {
+        (Plan::Once, once)
+    }
+}
+
+/// Checks the configurations of three pegs, and returns the largest disk
+/// not in place.
+fn largest_diff(start: &[usize], target: &[usize]) -> Option<usize> {
+    assert_eq!(start.len(), target.len(), "different numbers of disks");
+    assert!(start.len() < 128, "too many disks");
+    assert!(
+        start.iter().chain(target).all(|&peg| peg < 3),
+        "invalid pegs"
+    );
+    (0..start.len())
+        .rev()
+        .find(|&disk| start[disk] != target[disk])
+}
+
+/// The fewest moves from the configuration `start` to `target`, on three
+/// pegs.
+///
+/// The disks already in place below the others never move. Of the largest
+/// one which isn't, it's either moved once, or (sometimes shorter) twice,
+/// and moving the smaller ones to a single peg is done greedily.
+///
+/// Panics if there are `128` disks or more, or any peg is not in `0..3`.
+pub fn distance(start: &[usize], target: &[usize]) -> u128 {
+    match largest_diff(start, target) {
+        Some(k) => plan(start, target, k).1,
+        None => 0,
+    }
+}
+
+/// The moves from the configuration `start` to `target` in the fewest
+/// moves, on three pegs, see [`distance`].
+///
+/// # Examples
+///
+/// ```
+/// use try_1::hanoi;
+///
+/// // The disks 0 and 2 on peg 0, and the disk 1 on peg 1, are moved to
+/// // peg 2.
+/// let moves = hanoi::solve(&[0, 1, 0], &[2, 2, 2]);
+/// assert_eq!(moves, [(0, 0, 1), (2, 0, 2), (0, 1, 0), (1, 1, 2), (0, 0, 2)]);
+/// assert_eq!(hanoi::distance(&[0, 1, 0], &[2, 2, 2]), 5);
+/// ```
+pub fn solve(start: &[usize], target: &[usize]) -> Vec<Move> {
+    let mut out = Vec::new();
+    let Some(k) = largest_diff(start, target) else {
+        return out;
+    };
+    let (from, to) = (start[k], target[k]);
+    let via = third(from, to);
+    let mut config = start[..k].to_vec();
+    match plan(start, target, k).0 {
+        Plan::Once => {
+            to_tower(&mut config, k, via, &mut out);
+            out.push((k, from, to));
+            from_tower(target, k, via, &mut out);
+        }
+        Plan::Twice => {
+            to_tower(&mut config, k, to, &mut out);
+            out.push((k, from, via));
+            out.extend(moves(k, to, from));
+            out.push((k, via, to));
+            from_tower(target, k, from, &mut out);
+        }
+    }
+    out
+}
+
+/// The numbers of moves of the Frame–Stewart algorithm, `table[p][n]` for
+/// `n` disks on `p` pegs, and the best number of disks to set aside.
+fn frame_stewart_table(n: usize, pegs: usize) -> Vec<Vec<(u128, usize)>> {
+    let mut table = vec![vec![(0, 0); n + 1]; pegs + 1];
+    for (disks, entry) in table[3].iter_mut().enumerate() {
+        let cost = if disks < 128 {
+            (1 << disks) - 1
+        } else {
+            u128::MAX
+        };
+        *entry = (cost, 0);
+    }
+    for p in 4..=pegs {
+        for disks in 1..=n {
+            table[p][disks] = (1..disks)
+                .map(|k| {
+                    let twice = table[p][k].0.saturating_mul(2);
+                    (twice.saturating_add(table[p - 1][disks - k].0), k)
+                })
+                .min_by_key(|&(cost, _)| cost)
+                .unwrap_or((1, 0));
+        }
+    }
+    table
+}
+
+/// The number of moves of `n` disks on `pegs` pegs by the Frame–Stewart
+/// algorithm, saturated at `u128::MAX`.
+///
+/// For some `k`, the `k` smallest disks are moved to an intermediate peg
+/// with all the pegs, then the rest to the target without that peg, and
+/// then the `k` disks again onto them. It's optimal for three and four
+/// pegs, and conjectured to be for more.
+///
+/// # Examples
+///
+/// ```
+/// use try_1::hanoi;
+///
+/// assert_eq!(hanoi::frame_stewart(10, 3), 1023);
+/// assert_eq!(hanoi::frame_stewart(10, 4), 49);
+/// assert_eq!(hanoi::frame_stewart(10, 5), 31);
+/// ```
+pub fn frame_stewart(n: usize, pegs: usize) -> u128 {
+    assert!(pegs >= 3, "at least three pegs are needed");
+    frame_stewart_table(n, pegs)[pegs][n].0
+}
+
+/// The moves of the Frame–Stewart algorithm for a tower of `n` disks from
+/// peg `from` to peg `to` of `0..pegs`, see [`frame_stewart`].
+pub fn frame_stewart_moves(n: usize, pegs: usize, from: usize, to: usize) -> Vec<Move> {
+    assert!(pegs >= 3, "at least three pegs are needed");
+    assert!(from < pegs && to < pegs && from != to, "invalid pegs");
+    let table = frame_stewart_table(n, pegs);
+    let mut out = Vec::new();
+    let spare = (0..pegs)
+        .filter(|&p| p != from && p != to)
+        .collect::<Vec<_>>();
+    stewart(&table, 0, n, from, to, &spare, &mut out);
+    out
+}
+
+/// Moves the disks `lo..hi` from `from` to `to`, with the `spare` pegs.
+fn stewart(
+    table: &[Vec<(u128, usize)>],
+    lo: usize,
+    hi: usize,
+    from: usize,
+    to: usize,
+    spare: &[usize],
+    out: &mut Vec<Move>,
+) {
+    let n = hi - lo;
+    if n == 0 {
+        return;
+    }
+    if spare.len() == 1 {
+        let pegs = [from, spare[0], to];
+        let relabel = |(disk, a, b): Move| (disk + lo, pegs[a], pegs[b]);
+        out.extend(moves(n, 0, 2).map(relabel));
+        return;
+    }
+    if n == 1 {
+        out.push((lo, from, to));
+        return;
+    }
+    let k = table[spare.len() + 2][n].1;
+    let (mid, rest) = spare.split_first().unwrap();
+    let mut others = rest.to_vec();
+    others.push(to);
+    stewart(table, lo, lo + k, from, *mid, &others, out);
+    stewart(table, lo + k, hi, from, to, rest, out);
+    *others.last_mut().unwrap() = from;
+    stewart(table, lo, lo + k, *mid, to, &others, out);
+}
+
+/// An illegal move, the fields are the index of the move in the list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IllegalMove {
+    /// The disk doesn't exist.
+    NoSuchDisk { index: usize },
+    /// Either peg doesn't exist, or they are the same.
+    InvalidPegs { index: usize },
+    /// The disk is not on top of the peg `from`.
+    NotOnTop { index: usize },
+    /// The disk would go onto a smaller one.
+    OntoSmaller { index: usize },
+}
+
+impl Display for IllegalMove {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Self::NoSuchDisk { index } => write!(f, "move {index} has no such disk"),
+            Self::InvalidPegs { index } => write!(f, "move {index} has invalid pegs"),
+            Self::NotOnTop { index } => write!(f, "move {index} takes a disk not on top"),
+            Self::OntoSmaller { index } => write!(f, "move {index} puts a disk onto a smaller one"),
+        }
+    }
+}
+
+impl std::error::Error for IllegalMove {}
+
+/// Replays `moves` from the configuration `start` of `pegs` pegs, and
+/// returns the final configuration, or the first illegal move.
+///
+/// # Examples
+///
+/// ```
+/// use try_1::hanoi::{self, IllegalMove};
+///
+/// assert_eq!(hanoi::replay(3, &[0, 0], &[(0, 0, 1), (1, 0, 2)]), Ok(vec![1, 2]));
+/// assert_eq!(
+///     hanoi::replay(3, &[0, 0], &[(1, 0, 2)]),
+///     Err(IllegalMove::NotOnTop { index: 0 })
+/// );
+/// ```
+pub fn replay(pegs: usize, start: &[usize], moves: &[Move]) -> Result<Vec<usize>, IllegalMove> {
+    assert!(start.iter().all(|&peg| peg < pegs), "invalid configuration");
+    // The disks on each peg, from the bottom.
+    let mut stacks = vec![Vec::new(); pegs];
+    for (disk, &peg) in start.iter().enumerate().rev() {
+        stacks[peg].push(disk);
+    }
+    for (index, &(disk, from, to)) in moves.iter().enumerate() {
+        if disk >= start.len() {
+            return Err(IllegalMove::NoSuchDisk { index });
+        }
+        if from >= pegs || to >= pegs || from == to {
+            return Err(IllegalMove::InvalidPegs { index });
+        }
+        if stacks[from].last() != Some(&disk) {
+            return Err(IllegalMove::NotOnTop { index });
+        }
+        if stacks[to].last().is_some_and(|&top| top < disk) {
+            return Err(IllegalMove::OntoSmaller { index });
+        }
+        stacks[from].pop();
+        stacks[to].push(disk);
+    }
+    let mut config = vec![0; start.len()];
+    for (peg, stack) in stacks.into_iter().enumerate() {
+        for disk in stack {
+            config[disk] = peg;
+        }
+    }
+    Ok(config)
+}
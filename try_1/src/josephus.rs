@@ -0,0 +1,197 @@
+//! The general Josephus problem.
+//!
+//! `n` people numbered `0..n` stand in a circle. Counting starts from
+//! `start`, and every `k`-th person is eliminated, then counting goes on
+//! from the next one, until only one is left.
+
+/// A Fenwick tree over the people still in the circle, which finds the
+/// `i`-th one of them in O(log n).
+#[derive(Debug, Clone)]
+struct Alive {
+    /// `tree[i]` counts the alive ones in `(i - lowbit(i))..i`, 1-based.
+    tree: Vec<usize>,
+    len: usize,
+}
+
+impl Alive {
+    /// All the `n` people alive.
+    fn new(n: usize) -> Self {
+        let tree = (0..=n).map(|i| i & i.wrapping_neg()).collect();
+        Self { tree, len: n }
+    }
+
+    /// Eliminates the person `p`.
+    fn remove(&mut self, p: usize) {
+        let mut i = p + 1;
+        while i < self.tree.len() {
+            self.tree[i] -= 1;
+            i += i & i.wrapping_neg();
+        }
+        self.len -= 1;
+    }
+
+    /// The person of rank `rank` (0-based) among the alive ones.
+    fn nth(&self, mut rank: usize) -> usize {
+        let mut pos = 0;
+        let mut step = (self.tree.len() - 1)
+            .checked_ilog2()
+            .map_or(0, |log| 1 << log);
+        while step > 0 {
+            if pos + step < self.tree.len() && self.tree[pos + step] <= rank {
+                pos += step;
+                rank -= self.tree[pos];
+            }
+            step >>= 1;
+        }
+        pos
+    }
+}
+
+/// The people in the order they are eliminated, the last one of which is
+/// the survivor. Each one takes O(log n) time.
+///
+/// # Examples
+///
+/// ```
+/// use try_1::josephus;
+///
+/// let order = josephus::elimination_order(7, 3, 0).collect::<Vec<_>>();
+/// assert_eq!(order, [2, 5, 1, 6, 4, 0, 3]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Eliminations {
+    alive: Alive,
+    k: usize,
+    /// The rank among the alive ones where counting starts next.
+    rank: usize,
+}
+
+impl Iterator for Eliminations {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let len = self.alive.len;
+        if len == 0 {
+            return None;
+        }
+        let rank = (self.rank + (self.k - 1) % len) % len;
+        let p = self.alive.nth(rank);
+        self.alive.remove(p);
+        // The next one takes over the rank of the eliminated one.
+        self.rank = if len > 1 { rank % (len - 1) } else { 0 };
+        Some(p)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.alive.len, Some(self.alive.len))
+    }
+}
+
+impl ExactSizeIterator for Eliminations {}
+
+/// Eliminates every `k`-th one of `n` people, counting from `start`.
+///
+/// Panics if `k` is zero, or `start` is not less than `n` (unless `n` is
+/// zero).
+pub fn elimination_order(n: usize, k: usize, start: usize) -> Eliminations {
+    assert!(k > 0, "k must be positive");
+    assert!(start < n || n == 0, "start out of range");
+    Eliminations {
+        alive: Alive::new(n),
+        k,
+        rank: start,
+    }
+}
+
+/// The survivor of `n` people where every `k`-th one is eliminated,
+/// counting from `0`, in O(k log n) time.
+///
+/// For `n >= k`, a round of `n / k` eliminations leaves a circle of
+/// `n - n / k` people starting from the one after the last eliminated, so
+/// its survivor is mapped back by skipping those eliminated. Smaller
+/// circles are solved by the recurrence `J(i) = (J(i - 1) + k) mod i`.
+///
+/// Panics if `n` or `k` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use try_1::josephus;
+///
+/// assert_eq!(josephus::survivor(7, 3), 3);
+/// // For `k = 2`, it's `2 * (n - 2^floor(log2(n)))`.
+/// assert_eq!(josephus::survivor(1_000_000_000_000, 2), 2 * (1_000_000_000_000 - (1 << 39)));
+/// ```
+pub fn survivor(n: usize, k: usize) -> usize {
+    assert!(n > 0, "no one to survive");
+    assert!(k > 0, "k must be positive");
+    if k == 1 {
+        return n - 1;
+    }
+    let mut rounds = Vec::new();
+    let mut m = n;
+    while m >= k {
+        rounds.push(m);
+        m -= m / k;
+    }
+    let mut pos = 0;
+    for i in 2..=m {
+        pos = (pos + k) % i;
+    }
+    for m in rounds.into_iter().rev() {
+        // The smaller circle starts right after the last eliminated one,
+        // at `(m / k) * k`, and wraps around.
+        pos = match pos.checked_sub(m % k) {
+            Some(pos) => pos + pos / (k - 1),
+            None => pos + m - m % k,
+        };
+    }
+    pos
+}
+
+/// The survivor as [`survivor`], counting from `start`.
+pub fn survivor_from(n: usize, k: usize, start: usize) -> usize {
+    assert!(start < n, "start out of range");
+    (survivor(n, k) + start) % n
+}
+
+/// The smallest `k` such that the people `survivors` are the last ones
+/// left of `n`, counting from `start`, if any `k` up to `max_k` does.
+///
+/// The elimination only depends on `k` modulo the lengths of the circle on
+/// the way, so the search stops at their least common multiple as well.
+///
+/// # Examples
+///
+/// ```
+/// use try_1::josephus;
+///
+/// // Of 30 people, every 9th one is thrown overboard until 15 remain.
+/// let order = josephus::elimination_order(30, 9, 0).collect::<Vec<_>>();
+/// assert_eq!(josephus::smallest_k(30, &order[15..], 0, 100), Some(9));
+/// ```
+pub fn smallest_k(n: usize, survivors: &[usize], start: usize, max_k: usize) -> Option<usize> {
+    let mut keep = vec![false; n];
+    for &p in survivors {
+        assert!(p < n, "survivor out of range");
+        keep[p] = true;
+    }
+    let left = keep.iter().filter(|&&keep| keep).count();
+    if left == 0 {
+        return None;
+    }
+    let period = (left + 1..=n).try_fold(1usize, |lcm, len| lcm.checked_mul(len / gcd(lcm, len)));
+    let max_k = period.map_or(max_k, |period| period.min(max_k));
+    (1..=max_k).find(|&k| {
+        elimination_order(n, k, start)
+            .take(n - left)
+            .all(|p| !keep[p])
+    })
+}
+
+fn gcd(mut a: usize, mut b: usize) -> usize {
+    while b > 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
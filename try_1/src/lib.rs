@@ -1,3 +1,8 @@
+pub mod combinatorics;
+pub mod hanoi;
+pub mod josephus;
+pub mod palindrome;
+
 use std::collections::VecDeque;
 
 pub struct Solution;
@@ -6,25 +11,16 @@ impl Solution {
     /// Question 1 - Josephs Ring Problem.
     ///
     /// There are **2n** people, then pop a people per **m** people. It
-    /// should left all good people after pop **n** people. Firstly, We
-    /// guess all people as good people, then setting a pointer `idx`,
-    /// which point to first people after apply an add. Then pop a bad
-    /// people per **m** people. We only minus **m** if a good people,
-    /// this mean the popped bad people are skipped. Finally, return a
-    /// slice contains bad people with false and good people with true.
+    /// should left all good people after pop **n** people. We take the
+    /// first **n** people of the elimination order, which finds each one
+    /// in O(log n) by a Fenwick tree over the people left, see
+    /// [`josephus::elimination_order`]. Finally, return a slice contains
+    /// bad people with false and good people with true.
     pub fn josephs_ring(n: i32, m: i32) -> Vec<bool> {
         let len = (n as usize) << 1;
         let mut result = vec![true; len];
-        let mut idx = -1;
-        for _ in 0..n {
-            let mut m = m;
-            while m > 0 {
-                idx += 1;
-                if result[idx as usize % len] {
-                    m -= 1;
-                }
-            }
-            result[idx as usize % len] = false;
+        for idx in josephus::elimination_order(len, m as usize, 0).take(n as usize) {
+            result[idx] = false;
         }
         result
     }
@@ -36,6 +32,9 @@ impl Solution {
     /// Then we match the front and back value. If they aren't equal,
     /// the sentence is not palindrome. Otherwise, pop the previous
     /// values and match the next front and back.
+    ///
+    /// Only ASCII is considered here, see [`palindrome::is_palindrome`] for
+    /// any language.
     pub fn is_palindrome(sentence: &str) -> bool {
         let mut sentence: VecDeque<u8> = sentence
             .as_bytes()
@@ -65,17 +64,16 @@ impl Solution {
     /// solve this problem, the last one should be place to the `dist`,  so we
     /// firstly move all above to the `tmp`. After the last one in place, we
     /// move `tmp` to `dist`. And ..., the above one also should be placed to
-    /// `dist`, then we do same thing with last one to the above one. Finally,
-    /// we solve this problem by recursion.
+    /// `dist`, then we do same thing with last one to the above one. The moves
+    /// come from [`hanoi::moves`], which yields them one by one without any
+    /// recursion.
     pub fn tower_of_hanoi(src: &mut Vec<i32>, tmp: &mut Vec<i32>, dist: &mut Vec<i32>) {
-        fn mov(src: &mut Vec<i32>, tmp: &mut Vec<i32>, dist: &mut Vec<i32>, cnt: usize) {
-            if cnt > 0 {
-                mov(src, dist, tmp, cnt - 1);
-                dist.push(src.pop().unwrap());
-                mov(tmp, src, dist, cnt - 1);
-            }
+        let len = src.len();
+        let pegs = [src, tmp, dist];
+        for (_, from, to) in hanoi::moves(len, 0, 2) {
+            let disk = pegs[from].pop().unwrap();
+            pegs[to].push(disk);
         }
-        mov(src, tmp, dist, src.len());
     }
 
     /// Question 4 - Full Permutation
@@ -88,7 +86,10 @@ impl Solution {
     /// of permutation, and the second part is the remaining elements of `vec`.
     /// We don't need to use `HashMap` (std) here because the implementation of
     /// `HashMap` (std) is sloooooowly.
-    pub fn permutation<T: Clone>(vec: &Vec<T>, cnt: usize) -> Vec<(Vec<T>, Vec<T>)> {
+    ///
+    /// All the arrangements are kept in memory at once, so for long vectors
+    /// iterate lazily by [`combinatorics::KPermutations`] instead.
+    pub fn permutation<T: Clone>(vec: &[T], cnt: usize) -> Vec<(Vec<T>, Vec<T>)> {
         fn search<T: Clone>(
             mut vec: Vec<T>,
             cnt: usize,
@@ -108,7 +109,7 @@ impl Solution {
         }
         assert!(cnt <= vec.len());
         let mut result = Vec::new();
-        search(vec.clone(), cnt, 0, &mut result);
+        search(vec.to_vec(), cnt, 0, &mut result);
         result
     }
 
@@ -116,7 +117,7 @@ impl Solution {
     ///
     /// This is a wrapper of previous permutation algorithm for
     /// directly get the full permutation from a vector.
-    pub fn full_permutation<T: Clone>(vec: &Vec<T>) -> Vec<Vec<T>> {
+    pub fn full_permutation<T: Clone>(vec: &[T]) -> Vec<Vec<T>> {
         Self::permutation(vec, vec.len())
             .into_iter()
             .map(|v| v.0)
@@ -125,4 +126,5 @@ impl Solution {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison, clippy::useless_vec)]
 mod tests;
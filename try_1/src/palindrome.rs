@@ -0,0 +1,180 @@
+//! Palindromes of Unicode text and of general sequences.
+//!
+//! Text is compared by grapheme clusters, so that a letter with combining
+//! marks (or an emoji with modifiers) counts as a single unit. The other
+//! functions take any slice, e.g. the graphemes of a string.
+
+use std::ops::Range;
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Case folds `c`, which is lowercasing except for the few letters whose
+/// lowercase still has another folded form.
+fn fold(c: char) -> impl Iterator<Item = char> {
+    let special = match c {
+        'ς' => Some("σ"),
+        'ß' | 'ẞ' => Some("ss"),
+        'ſ' => Some("s"),
+        _ => None,
+    };
+    let lower = special.is_none().then(|| c.to_lowercase());
+    special
+        .into_iter()
+        .flat_map(str::chars)
+        .chain(lower.into_iter().flatten())
+}
+
+/// The letters and digits of `text` as grapheme clusters, normalized to
+/// NFC and case folded, without the spaces, punctuation and symbols.
+pub fn normalize(text: &str) -> Vec<String> {
+    let folded = text.nfc().flat_map(fold).nfc().collect::<String>();
+    folded
+        .graphemes(true)
+        .filter(|g| g.chars().next().is_some_and(char::is_alphanumeric))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Whether `text` reads the same backwards, only by its letters and
+/// digits, ignoring the case and how the accents are encoded.
+///
+/// # Examples
+///
+/// ```
+/// use try_1::palindrome;
+///
+/// assert!(palindrome::is_palindrome("Niño — on\u{303}iN"));
+/// assert!(palindrome::is_palindrome("А роза упала на лапу Азора"));
+/// assert!(palindrome::is_palindrome("ΝΙΨΟΝ ΑΝΟΜΗΜΑΤΑ ΜΗ ΜΟΝΑΝ ΟΨΙΝ"));
+/// // The accents count, so "é" is not "e".
+/// assert!(!palindrome::is_palindrome("Ésope reste ici et se repose."));
+/// ```
+pub fn is_palindrome(text: &str) -> bool {
+    let graphemes = normalize(text);
+    graphemes.iter().eq(graphemes.iter().rev())
+}
+
+/// Manacher's algorithm in O(n) time.
+///
+/// Returns `radii` of `2 * len + 1` centers, where center `i` is between
+/// `v[i / 2 - 1]` and `v[i / 2]` if `i` is even, or at `v[i / 2]` otherwise.
+/// `radii[i]` is the length of the longest palindrome around it, which is
+/// `v[(i - radii[i]) / 2..(i + radii[i]) / 2]`.
+pub fn manacher<T: Eq>(v: &[T]) -> Vec<usize> {
+    // The centers are the positions of `v` with a gap before and after
+    // each element, and gaps always match each other.
+    let len = 2 * v.len() + 1;
+    let eq = |a: usize, b: usize| a.is_multiple_of(2) || v[a / 2] == v[b / 2];
+    let mut radii = vec![0; len];
+    // The palindrome reaching the farthest so far, `center ± reach`.
+    let (mut center, mut reach) = (0, 0);
+    for i in 0..len {
+        // The mirror one within the reach is a palindrome here as well.
+        let mut radius = if i < center + reach {
+            radii[2 * center - i].min(center + reach - i)
+        } else {
+            0
+        };
+        while radius < i && i + radius + 1 < len && eq(i - radius - 1, i + radius + 1) {
+            radius += 1;
+        }
+        radii[i] = radius;
+        if i + radius > center + reach {
+            (center, reach) = (i, radius);
+        }
+    }
+    radii
+}
+
+/// The range of the longest palindromic part of `v`, the leftmost one if
+/// there are several.
+pub fn longest_palindrome<T: Eq>(v: &[T]) -> Range<usize> {
+    let radii = manacher(v);
+    let (i, &radius) = radii
+        .iter()
+        .enumerate()
+        .rev()
+        .max_by_key(|&(_, radius)| radius)
+        .unwrap();
+    (i - radius) / 2..(i + radius) / 2
+}
+
+/// The longest palindromic substring of `text` by grapheme clusters, which
+/// are compared as they are.
+///
+/// # Examples
+///
+/// ```
+/// use try_1::palindrome;
+///
+/// assert_eq!(palindrome::longest_palindromic_substring("bananas"), "anana");
+/// assert_eq!(palindrome::longest_palindromic_substring("xnöönx!"), "xnöönx");
+/// ```
+pub fn longest_palindromic_substring(text: &str) -> &str {
+    let graphemes = text.grapheme_indices(true).collect::<Vec<_>>();
+    let range = longest_palindrome(&graphemes.iter().map(|g| g.1).collect::<Vec<_>>());
+    let start = graphemes.get(range.start).map_or(text.len(), |g| g.0);
+    let end = graphemes.get(range.end).map_or(text.len(), |g| g.0);
+    &text[start..end]
+}
+
+/// The number of the non-empty palindromic parts of `v`, counted by their
+/// positions.
+pub fn count_palindromes<T: Eq>(v: &[T]) -> usize {
+    // A palindrome of length `r` contains `ceil(r / 2)` ones of the same
+    // center.
+    manacher(v)
+        .into_iter()
+        .map(|radius| radius.div_ceil(2))
+        .sum()
+}
+
+/// The fewest elements to insert into `v` to make it a palindrome, in
+/// O(n^2) time and O(n) memory.
+///
+/// It's the number of those not in the longest palindromic subsequence,
+/// each of which needs a mirror one inserted.
+pub fn min_insertions<T: Eq>(v: &[T]) -> usize {
+    let n = v.len();
+    // `longest[j]` is the longest palindromic subsequence of `v[i..=j]`.
+    let mut longest = vec![0; n];
+    for i in (0..n).rev() {
+        longest[i] = 1;
+        // The one of `v[i + 1..j]` from the previous row.
+        let mut inner = 0;
+        for j in i + 1..n {
+            let outer = longest[j];
+            longest[j] = if v[i] == v[j] {
+                inner + 2
+            } else {
+                longest[j].max(longest[j - 1])
+            };
+            inner = outer;
+        }
+    }
+    n - longest.last().unwrap_or(&0)
+}
+
+/// The fewest cuts to split `v` into palindromes, in O(n^2) time and O(n)
+/// memory.
+pub fn min_cuts<T: Eq>(v: &[T]) -> usize {
+    let n = v.len();
+    if n == 0 {
+        return 0;
+    }
+    // `parts[e]` is the fewest palindromes to split `v[..e]` into. Every
+    // palindrome `v[s..e]` is found by growing from its center, and those
+    // ending at `s` have the centers before, so `parts[s]` is done then.
+    let mut parts = (0..=n).collect::<Vec<_>>();
+    for center in 0..2 * n - 1 {
+        let (mut s, mut e) = (center / 2, center / 2 + 1 + center % 2);
+        while e <= n && v[s] == v[e - 1] {
+            parts[e] = parts[e].min(parts[s] + 1);
+            if s == 0 {
+                break;
+            }
+            (s, e) = (s - 1, e + 1);
+        }
+    }
+    parts[n] - 1
+}
@@ -57,3 +57,419 @@ fn full_permutation() {
         ]
     );
 }
+
+#[test]
+fn next_permutation() {
+    use crate::combinatorics::next_permutation;
+    let mut vec = vec![1, 2, 3, 4];
+    let mut all = vec![vec.clone()];
+    while next_permutation(&mut vec) {
+        all.push(vec.clone());
+    }
+    assert_eq!(all.len(), 24);
+    assert!(all.is_sorted());
+    assert_eq!(vec, [1, 2, 3, 4]);
+    let mut vec = vec![1, 1, 2];
+    assert!(next_permutation(&mut vec));
+    assert_eq!(vec, [1, 2, 1]);
+    assert!(next_permutation(&mut vec));
+    assert_eq!(vec, [2, 1, 1]);
+    assert!(!next_permutation(&mut vec));
+    assert!(!next_permutation::<i32>(&mut []));
+}
+
+#[test]
+fn permutations() {
+    use crate::combinatorics::{
+        rank_permutation, unrank_permutation, HeapPermutations, KPermutations, Permutations,
+    };
+    for n in 0..7 {
+        let all = Permutations::new(0..n).collect::<Vec<_>>();
+        assert_eq!(all.len(), (1..=n).product::<usize>());
+        assert!(all.windows(2).all(|pair| pair[0] < pair[1]));
+        for (rank, perm) in all.iter().enumerate() {
+            assert_eq!(rank_permutation(perm), Some(rank as u128));
+            assert_eq!(unrank_permutation(n, rank as u128).as_ref(), Some(perm));
+            assert_eq!(Permutations::new(0..n).nth(rank).as_ref(), Some(perm));
+        }
+        assert_eq!(unrank_permutation(n, all.len() as u128), None);
+        let mut heap = HeapPermutations::new(0..n).collect::<Vec<_>>();
+        assert!(heap
+            .windows(2)
+            .all(|pair| { pair[0].iter().zip(&pair[1]).filter(|(a, b)| a != b).count() == 2 }));
+        heap.sort();
+        assert_eq!(heap, all);
+        for k in 0..=n + 1 {
+            let perms = KPermutations::new(0..n, k).collect::<Vec<_>>();
+            let mut expect = all
+                .iter()
+                .map(|perm| perm[..k.min(n)].to_vec())
+                .collect::<Vec<_>>();
+            expect.dedup();
+            if k > n {
+                expect.clear();
+            }
+            assert_eq!(perms, expect);
+        }
+    }
+    let mut perms = Permutations::new(0..3);
+    assert_eq!(perms.nth(2), Some(vec![1, 0, 2]));
+    assert_eq!(perms.nth(2), Some(vec![2, 1, 0]));
+    assert_eq!(perms.next(), None);
+    assert_eq!(Permutations::new(0..3).nth(6), None);
+    let mut perms = Permutations::new(0..40);
+    assert_eq!(perms.nth(1), Some((0..38).chain([39, 38]).collect()));
+    assert_eq!(rank_permutation(&(0..40).rev().collect::<Vec<_>>()), None);
+    let last = unrank_permutation(35, u128::MAX).unwrap();
+    assert_eq!(rank_permutation(&last), Some(u128::MAX));
+}
+
+#[test]
+fn combinations() {
+    use crate::combinatorics::{Combinations, CombinationsWithReplacement, PowerSet};
+    let binomial = |n: usize, k: usize| (0..k).fold(1, |c, i| c * (n - i) / (i + 1));
+    for n in 0..8 {
+        let mut sets = Vec::new();
+        for k in 0..=n + 1 {
+            let combs = Combinations::new(0..n, k).collect::<Vec<_>>();
+            assert_eq!(combs.len(), if k > n { 0 } else { binomial(n, k) });
+            assert!(combs.windows(2).all(|pair| pair[0] < pair[1]));
+            assert!(combs.iter().all(|comb| comb.len() == k && comb.is_sorted()));
+            sets.extend(combs);
+            let combs = CombinationsWithReplacement::new(0..n, k).collect::<Vec<_>>();
+            let expect = if n == 0 {
+                usize::from(k == 0)
+            } else {
+                binomial(n + k - 1, k)
+            };
+            assert_eq!(combs.len(), expect);
+            assert!(combs.windows(2).all(|pair| pair[0] < pair[1]));
+            assert!(combs.iter().all(|comb| comb.len() == k && comb.is_sorted()));
+        }
+        assert_eq!(PowerSet::new(0..n).collect::<Vec<_>>(), sets);
+        assert_eq!(sets.len(), 1 << n);
+    }
+}
+
+#[test]
+fn multiset_1() {
+    use crate::combinatorics::multiset::binomial;
+    assert_eq!(binomial(5, 2), Some(10));
+    assert_eq!(binomial(2, 5), Some(0));
+    assert_eq!(
+        binomial(130, 65),
+        Some(95067625827960698145584333020095113100)
+    );
+    assert_eq!(binomial(200, 100), None);
+    assert_eq!(binomial(100, 50), Some(100891344545564193334812497256));
+}
+
+#[test]
+fn multiset_2() {
+    use crate::combinatorics::multiset::multinomial;
+    assert_eq!(multinomial(&[]), Some(1));
+    assert_eq!(multinomial(&[2, 1]), Some(3));
+    assert_eq!(multinomial(&[1; 34]), Some((1..=34).product()));
+    assert_eq!(multinomial(&[1; 35]), None);
+}
+
+#[test]
+fn multiset_3() {
+    use crate::combinatorics::{multiset::MultisetPermutations, KPermutations};
+    let items = [3, 1, 2, 1, 3, 1];
+    for k in 0..=items.len() + 1 {
+        let mut expect = KPermutations::new(items, k).collect::<Vec<_>>();
+        expect.sort();
+        expect.dedup();
+        let perms = MultisetPermutations::with_k(items, k);
+        assert_eq!(perms.total(), Some(expect.len() as u128));
+        assert_eq!(perms.collect::<Vec<_>>(), expect);
+    }
+}
+
+#[test]
+fn multiset_4() {
+    use crate::combinatorics::{multiset::MultisetCombinations, Combinations};
+    let items = [3, 1, 2, 1, 3, 1];
+    for k in 0..=items.len() + 1 {
+        let mut expect = Combinations::new(items, k)
+            .map(|mut comb| {
+                comb.sort();
+                comb
+            })
+            .collect::<Vec<_>>();
+        expect.sort();
+        expect.dedup();
+        let combs = MultisetCombinations::new(items, k);
+        assert_eq!(combs.total(), Some(expect.len() as u128));
+        assert_eq!(combs.collect::<Vec<_>>(), expect);
+    }
+}
+
+#[test]
+fn multiset_5() {
+    use crate::combinatorics::multiset::{MultisetCombinations, MultisetPermutations};
+    let perms = MultisetPermutations::new([1, 1, 2]);
+    assert_eq!(perms.total(), Some(3));
+    assert_eq!(perms.count(), 3);
+    assert_eq!(MultisetPermutations::new([0; 100]).count(), 1);
+    assert_eq!(MultisetPermutations::<i32>::new([]).count(), 1);
+    assert_eq!(MultisetCombinations::new([0; 100], 50).count(), 1);
+}
+
+/// The Josephus elimination order by removing from a `Vec`.
+fn naive_elimination(n: usize, k: usize, start: usize) -> Vec<usize> {
+    let mut circle = (0..n).collect::<Vec<_>>();
+    let mut idx = start;
+    let mut order = Vec::new();
+    while !circle.is_empty() {
+        idx = (idx + k - 1) % circle.len();
+        order.push(circle.remove(idx));
+    }
+    order
+}
+
+/// The Josephus survivor by the O(n) recurrence.
+fn naive_survivor(n: usize, k: usize) -> usize {
+    (2..=n).fold(0, |pos, i| (pos + k) % i)
+}
+
+#[test]
+fn josephus_1() {
+    use crate::josephus;
+    for n in 1..40 {
+        for k in 1..50 {
+            for start in [0, n / 2, n - 1] {
+                let order = josephus::elimination_order(n, k, start).collect::<Vec<_>>();
+                assert_eq!(order, naive_elimination(n, k, start), "n = {n}, k = {k}");
+                assert_eq!(josephus::survivor_from(n, k, start), order[n - 1]);
+            }
+        }
+    }
+    assert_eq!(josephus::elimination_order(0, 3, 0).next(), None);
+}
+
+#[test]
+fn josephus_2() {
+    use crate::josephus;
+    assert_eq!(josephus::survivor(1, 1_000), 0);
+    assert_eq!(
+        josephus::survivor(5_000_000, 3),
+        naive_survivor(5_000_000, 3)
+    );
+    assert_eq!(
+        josephus::survivor(100_000, 777),
+        naive_survivor(100_000, 777)
+    );
+}
+
+#[test]
+fn josephus_3() {
+    use crate::josephus;
+    assert_eq!(josephus::survivor(usize::MAX, 1), usize::MAX - 1);
+    let n = 1 << 40;
+    assert_eq!(josephus::survivor(n + 5, 2), 10);
+}
+
+#[test]
+fn josephus_4() {
+    use crate::josephus;
+    for (n, k, left) in [(20, 4, 10), (12, 5, 1), (9, 1, 3), (30, 9, 15)] {
+        let order = josephus::elimination_order(n, k, 0).collect::<Vec<_>>();
+        let found = josephus::smallest_k(n, &order[n - left..], 0, 1_000).unwrap();
+        assert!(found <= k);
+        let order = josephus::elimination_order(n, found, 0).collect::<Vec<_>>();
+        let mut expect = josephus::elimination_order(n, k, 0)
+            .skip(n - left)
+            .collect::<Vec<_>>();
+        let mut left = order[n - left..].to_vec();
+        expect.sort();
+        left.sort();
+        assert_eq!(left, expect);
+    }
+}
+
+#[test]
+fn josephus_5() {
+    use crate::josephus;
+    // Of two people, the first one survives for even `k`.
+    assert_eq!(josephus::smallest_k(2, &[0], 0, usize::MAX), Some(2));
+    assert_eq!(josephus::smallest_k(3, &[0, 1], 1, usize::MAX), Some(2));
+    assert_eq!(josephus::smallest_k(4, &[], 0, usize::MAX), None);
+}
+
+#[test]
+fn palindrome() {
+    use crate::palindrome::{self, *};
+    assert!(palindrome::is_palindrome(""));
+    assert!(palindrome::is_palindrome("madam, I'm adam."));
+    assert!(!palindrome::is_palindrome("0P"));
+    assert!(palindrome::is_palindrome("ax\u{303}a"));
+    assert!(!palindrome::is_palindrome("ax\u{303}xa"));
+    assert!(palindrome::is_palindrome("Straße: Essarts"));
+    assert!(palindrome::is_palindrome("上海自来水来自海上"));
+    assert_eq!(normalize("E\u{301}té!"), ["é", "t", "é"]);
+
+    fn naive<T: Eq>(v: &[T]) -> Vec<Range<usize>> {
+        let n = v.len();
+        (0..n)
+            .flat_map(|s| (s + 1..=n).map(move |e| s..e))
+            .filter(|r| v[r.clone()].iter().eq(v[r.clone()].iter().rev()))
+            .collect()
+    }
+    use std::ops::Range;
+    // Every sequence of 0, 1 and 2 up to length 8.
+    for code in 0..3u32.pow(9) {
+        let digits = (0..9).map(|i| code / 3u32.pow(i) % 3);
+        let v = digits.collect::<Vec<_>>();
+        let Some(len) = v.iter().rposition(|&d| d == 0) else {
+            continue;
+        };
+        let v = &v[..len];
+        let all = naive(v);
+        assert_eq!(count_palindromes(v), all.len());
+        let longest = all.iter().map(|r| r.len()).max().unwrap_or(0);
+        let first = all
+            .iter()
+            .find(|r| r.len() == longest)
+            .cloned()
+            .unwrap_or(0..0);
+        assert_eq!(longest_palindrome(v), first);
+        // The fewest palindromes to split each prefix into.
+        let mut parts = vec![0usize; len + 1];
+        for e in 1..=len {
+            parts[e] = all
+                .iter()
+                .filter(|r| r.end == e)
+                .map(|r| parts[r.start] + 1)
+                .min()
+                .unwrap();
+        }
+        assert_eq!(min_cuts(v), parts[len].saturating_sub(1));
+    }
+    assert_eq!(longest_palindromic_substring(""), "");
+    assert_eq!(longest_palindromic_substring("abacdfgdcaba"), "aba");
+    assert_eq!(longest_palindromic_substring("🇯🇵🇫🇷🇯🇵!"), "🇯🇵🇫🇷🇯🇵");
+    assert_eq!(min_insertions(b"" as &[u8]), 0);
+    assert_eq!(min_insertions(b"ab"), 1);
+    assert_eq!(min_insertions(b"mbadm"), 2);
+    assert_eq!(min_insertions(b"leetcode"), 5);
+    assert_eq!(min_insertions(b"racecar"), 0);
+    assert_eq!(min_cuts(b"aab"), 1);
+    assert_eq!(min_cuts(b"abc"), 2);
+    assert_eq!(manacher(b"aba"), [0, 1, 0, 3, 0, 1, 0]);
+}
+
+#[test]
+fn hanoi() {
+    use crate::hanoi::{self, IllegalMove};
+    use std::collections::VecDeque;
+    // The distances from `target` to every configuration of `n` disks on
+    // `pegs` pegs, indexed by the configuration in base `pegs`.
+    fn bfs(n: usize, pegs: usize, target: &[usize]) -> Vec<u128> {
+        let decode = |code: usize| (0..n).map(|d| code / pegs.pow(d as u32) % pegs).collect();
+        let encode = |config: &[usize]| config.iter().rev().fold(0, |code, &p| code * pegs + p);
+        let mut dist = vec![u128::MAX; pegs.pow(n as u32)];
+        dist[encode(target)] = 0;
+        let mut queue = VecDeque::from([encode(target)]);
+        while let Some(code) = queue.pop_front() {
+            let config: Vec<usize> = decode(code);
+            for from in 0..pegs {
+                let Some(disk) = config.iter().position(|&p| p == from) else {
+                    continue;
+                };
+                for to in 0..pegs {
+                    if to == from || config[..disk].contains(&to) {
+                        continue;
+                    }
+                    let mut next = config.clone();
+                    next[disk] = to;
+                    let next = encode(&next);
+                    if dist[next] == u128::MAX {
+                        dist[next] = dist[code] + 1;
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+        dist
+    }
+
+    for n in 0..10 {
+        for (from, to) in [(0, 2), (2, 1), (1, 0)] {
+            let moves = hanoi::moves(n, from, to).collect::<Vec<_>>();
+            assert_eq!(moves.len(), (1 << n) - 1);
+            assert_eq!(hanoi::replay(3, &vec![from; n], &moves), Ok(vec![to; n]));
+        }
+    }
+    assert_eq!(hanoi::moves(127, 0, 1).size_hint().0, usize::MAX);
+    assert_eq!(hanoi::moves(5, 0, 1).count(), 31);
+
+    // Every pair of configurations of up to 4 disks.
+    for n in 0..=4 {
+        let count = 3usize.pow(n as u32);
+        let decode = |code: usize| {
+            (0..n)
+                .map(|d| code / 3usize.pow(d as u32) % 3)
+                .collect::<Vec<_>>()
+        };
+        for t in 0..count {
+            let target = decode(t);
+            let dist = bfs(n, 3, &target);
+            for (s, &expect) in dist.iter().enumerate() {
+                let start = decode(s);
+                assert_eq!(
+                    hanoi::distance(&start, &target),
+                    expect,
+                    "{start:?} to {target:?}"
+                );
+                let moves = hanoi::solve(&start, &target);
+                assert_eq!(moves.len() as u128, expect);
+                assert_eq!(hanoi::replay(3, &start, &moves), Ok(target.clone()));
+            }
+        }
+    }
+    // Moving the largest disk twice is shorter here, once takes 15 moves.
+    let (start, target) = ([2, 2, 2, 0], [0, 0, 0, 2]);
+    assert_eq!(hanoi::distance(&start, &target), 1 + 7 + 1);
+    assert_eq!(hanoi::distance(&[0; 127], &[2; 127]), (1 << 127) - 1);
+
+    // Frame–Stewart is optimal for four pegs.
+    for n in 0..=6 {
+        assert_eq!(hanoi::frame_stewart(n, 4), bfs(n, 4, &vec![3; n])[0]);
+    }
+    for pegs in 3..7 {
+        for n in 0..12 {
+            let moves = hanoi::frame_stewart_moves(n, pegs, 1, 0);
+            assert_eq!(moves.len() as u128, hanoi::frame_stewart(n, pegs));
+            assert_eq!(hanoi::replay(pegs, &vec![1; n], &moves), Ok(vec![0; n]));
+        }
+    }
+    assert_eq!(hanoi::frame_stewart(200, 3), u128::MAX);
+    // 1000 = 44 * 45 / 2 + 10, a triangular number `t (t + 1) / 2` of disks
+    // takes `(t - 1) 2^t + 1` moves, and each one more takes `2^t`.
+    assert_eq!(hanoi::frame_stewart(1000, 4), (43 + 10) * (1 << 44) + 1);
+
+    let start = [0, 0, 1];
+    assert_eq!(hanoi::replay(3, &start, &[]), Ok(start.to_vec()));
+    for (moves, error) in [
+        (vec![(3, 0, 1)], IllegalMove::NoSuchDisk { index: 0 }),
+        (
+            vec![(0, 0, 1), (1, 0, 0)],
+            IllegalMove::InvalidPegs { index: 1 },
+        ),
+        (vec![(0, 0, 3)], IllegalMove::InvalidPegs { index: 0 }),
+        (vec![(1, 0, 2)], IllegalMove::NotOnTop { index: 0 }),
+        (vec![(0, 1, 2)], IllegalMove::NotOnTop { index: 0 }),
+        (
+            vec![(0, 0, 1), (1, 0, 1)],
+            IllegalMove::OntoSmaller { index: 1 },
+        ),
+    ] {
+        assert_eq!(hanoi::replay(3, &start, &moves), Err(error));
+    }
+    assert_eq!(
+        IllegalMove::OntoSmaller { index: 4 }.to_string(),
+        "move 4 puts a disk onto a smaller one"
+    );
+}
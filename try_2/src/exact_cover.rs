@@ -0,0 +1,323 @@
+//! Exact cover by Knuth's Algorithm X with Dancing Links.
+//!
+//! Each row covers some columns, and a solution is a set of rows covering
+//! every primary column exactly once, and every secondary column at most
+//! once. The columns `0..primary` are primary, and the rest secondary.
+//!
+//! The puzzles modelled as exact covers are in [`adapters`].
+
+pub mod adapters;
+
+/// An exact cover problem whose rows are labelled by `R`.
+///
+/// The links of the matrix are kept in arrays, where `0` is the root, the
+/// headers of the columns follow it, and then the nodes of the rows. Each
+/// node is in a circular list of its column, and one of its row.
+///
+/// # Examples
+///
+/// ```
+/// use try_2::exact_cover::ExactCover;
+///
+/// let mut cover = ExactCover::new(4, 0);
+/// cover.add_row('a', &[0, 1]);
+/// cover.add_row('b', &[2, 3]);
+/// cover.add_row('c', &[1, 2]);
+/// cover.add_row('d', &[0, 3]);
+/// cover.add_row('e', &[0, 1, 2]);
+/// let solutions = cover.solutions().collect::<Vec<_>>();
+/// assert_eq!(solutions, [vec![&'b', &'a'], vec![&'d', &'c']]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExactCover<R> {
+    links: Links,
+    labels: Vec<R>,
+}
+
+/// The links of the matrix.
+#[derive(Debug, Clone)]
+struct Links {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    /// The column header of each node.
+    col: Vec<usize>,
+    /// The row of each node, `usize::MAX` for the headers.
+    row: Vec<usize>,
+    /// The number of nodes in each column, by its header.
+    size: Vec<usize>,
+}
+
+impl Links {
+    /// Removes column `c` from the headers, and its rows from the others.
+    fn cover(&mut self, c: usize) {
+        let (left, right) = (self.left[c], self.right[c]);
+        self.right[left] = right;
+        self.left[right] = left;
+        let mut i = self.down[c];
+        while i != c {
+            let mut j = self.right[i];
+            while j != i {
+                let (up, down) = (self.up[j], self.down[j]);
+                self.down[up] = down;
+                self.up[down] = up;
+                self.size[self.col[j]] -= 1;
+                j = self.right[j];
+            }
+            i = self.down[i];
+        }
+    }
+
+    /// Undoes [`Links::cover`] of `c`, in the reverse order.
+    fn uncover(&mut self, c: usize) {
+        let mut i = self.up[c];
+        while i != c {
+            let mut j = self.left[i];
+            while j != i {
+                self.size[self.col[j]] += 1;
+                let (up, down) = (self.up[j], self.down[j]);
+                self.down[up] = j;
+                self.up[down] = j;
+                j = self.left[j];
+            }
+            i = self.up[i];
+        }
+        let (left, right) = (self.left[c], self.right[c]);
+        self.right[left] = c;
+        self.left[right] = c;
+    }
+
+    /// Covers the other columns of the row of node `r`.
+    fn select(&mut self, r: usize) {
+        let mut j = self.right[r];
+        while j != r {
+            self.cover(self.col[j]);
+            j = self.right[j];
+        }
+    }
+
+    /// Undoes [`Links::select`] of `r`.
+    fn unselect(&mut self, r: usize) {
+        let mut j = self.left[r];
+        while j != r {
+            self.uncover(self.col[j]);
+            j = self.left[j];
+        }
+    }
+
+    /// The primary column with the fewest rows left, or `None` if all of
+    /// them are covered.
+    fn choose(&self) -> Option<usize> {
+        let mut best = None;
+        let mut c = self.right[0];
+        while c != 0 {
+            if best.is_none_or(|best| self.size[c] < self.size[best]) {
+                best = Some(c);
+                if self.size[c] == 0 {
+                    break;
+                }
+            }
+            c = self.right[c];
+        }
+        best
+    }
+}
+
+impl<R> ExactCover<R> {
+    /// An empty matrix of `primary` and then `secondary` columns.
+    pub fn new(primary: usize, secondary: usize) -> Self {
+        let columns = primary + secondary;
+        let headers = 0..=columns;
+        // Only the primary headers are linked to the root.
+        let left = headers
+            .clone()
+            .map(|c| match c {
+                0 => primary,
+                c if c <= primary => c - 1,
+                c => c,
+            })
+            .collect();
+        let right = headers
+            .clone()
+            .map(|c| match c {
+                c if c == primary => 0,
+                c if c < primary => c + 1,
+                c => c,
+            })
+            .collect();
+        Self {
+            links: Links {
+                left,
+                right,
+                up: headers.clone().collect(),
+                down: headers.clone().collect(),
+                col: headers.collect(),
+                row: vec![usize::MAX; columns + 1],
+                size: vec![0; columns + 1],
+            },
+            labels: Vec::new(),
+        }
+    }
+
+    /// The number of columns.
+    pub fn columns(&self) -> usize {
+        self.links.size.len() - 1
+    }
+
+    /// The labels of the rows in the order they are added.
+    pub fn rows(&self) -> &[R] {
+        &self.labels
+    }
+
+    /// Adds a row labelled `label` which covers `columns`.
+    ///
+    /// Panics if a column is out of range or repeated.
+    pub fn add_row(&mut self, label: R, columns: &[usize]) {
+        for (i, &c) in columns.iter().enumerate() {
+            assert!(c < self.columns(), "column out of range");
+            assert!(!columns[..i].contains(&c), "repeated column");
+        }
+        let links = &mut self.links;
+        let row = self.labels.len();
+        let first = links.col.len();
+        for (i, &c) in columns.iter().enumerate() {
+            let (node, header) = (first + i, c + 1);
+            links.left.push(if i == 0 {
+                first + columns.len() - 1
+            } else {
+                node - 1
+            });
+            links.right.push(if i + 1 == columns.len() {
+                first
+            } else {
+                node + 1
+            });
+            let last = links.up[header];
+            links.up.push(last);
+            links.down.push(header);
+            links.down[last] = node;
+            links.up[header] = node;
+            links.col.push(header);
+            links.row.push(row);
+            links.size[header] += 1;
+        }
+        self.labels.push(label);
+    }
+
+    /// The solutions one by one, each as the labels of its rows.
+    pub fn solutions(&self) -> Solutions<'_, R> {
+        Solutions {
+            links: self.links.clone(),
+            labels: &self.labels,
+            stack: Vec::new(),
+            descend: true,
+            nodes: 0,
+            budget: u64::MAX,
+            exhausted: false,
+            complete: false,
+        }
+    }
+
+    /// The number of solutions.
+    pub fn count(&self) -> u64 {
+        self.solutions().count() as u64
+    }
+}
+
+/// The solutions of an [`ExactCover`], searched on its own copy of the
+/// links, so it stops anywhere without undoing them.
+///
+/// Every row chosen is a node of the search, and a budget of them stops it
+/// early, after which [`Solutions::is_complete`] tells it apart from the
+/// end of the search.
+///
+/// # Examples
+///
+/// ```
+/// use try_2::exact_cover::adapters;
+///
+/// let cover = adapters::n_queens(12);
+/// let mut solutions = cover.solutions().budget(1_000);
+/// let found = solutions.by_ref().count();
+/// assert!(found < 14200 && !solutions.is_complete());
+/// assert!(solutions.nodes() <= 1_000);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Solutions<'a, R> {
+    links: Links,
+    labels: &'a [R],
+    /// The column chosen on each level, and the node of the row chosen.
+    stack: Vec<(usize, usize)>,
+    /// Whether the next step goes down a level, or on to the next row.
+    descend: bool,
+    nodes: u64,
+    budget: u64,
+    exhausted: bool,
+    complete: bool,
+}
+
+impl<R> Solutions<'_, R> {
+    /// Stops after choosing `nodes` rows in total.
+    pub fn budget(mut self, nodes: u64) -> Self {
+        self.budget = nodes;
+        self
+    }
+
+    /// The number of rows chosen so far.
+    pub fn nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    /// Whether the search has ended without running out of the budget.
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+}
+
+impl<'a, R> Iterator for Solutions<'a, R> {
+    type Item = Vec<&'a R>;
+
+    fn next(&mut self) -> Option<Vec<&'a R>> {
+        let links = &mut self.links;
+        loop {
+            if self.descend {
+                self.descend = false;
+                match links.choose() {
+                    Some(c) => {
+                        links.cover(c);
+                        self.stack.push((c, c));
+                    }
+                    None => {
+                        let rows = self.stack.iter().map(|&(_, r)| &self.labels[links.row[r]]);
+                        return Some(rows.collect());
+                    }
+                }
+                continue;
+            }
+            // Moves on to the next row of the column on top.
+            let Some((c, r)) = self.stack.last_mut() else {
+                self.complete = !self.exhausted;
+                return None;
+            };
+            if *r != *c {
+                links.unselect(*r);
+            }
+            *r = links.down[*r];
+            if *r == *c {
+                links.uncover(*c);
+                self.stack.pop();
+                continue;
+            }
+            if self.nodes == self.budget {
+                // The links are left as they are, the search ends here.
+                self.exhausted = true;
+                self.stack.clear();
+                return None;
+            }
+            self.nodes += 1;
+            links.select(*r);
+            self.descend = true;
+        }
+    }
+}
@@ -0,0 +1,268 @@
+//! Puzzles modelled as exact covers.
+
+use super::ExactCover;
+
+/// N-Queens, a row for each square labelled `(row, col)`.
+///
+/// The rows and the columns of the board are primary columns, as each has
+/// a queen, and the diagonals are secondary, as each has one at most.
+///
+/// # Examples
+///
+/// ```
+/// use try_2::exact_cover::adapters;
+///
+/// assert_eq!(adapters::n_queens(8).count(), 92);
+/// ```
+pub fn n_queens(n: usize) -> ExactCover<(usize, usize)> {
+    let diagonals = (2 * n).saturating_sub(1);
+    let mut cover = ExactCover::new(2 * n, 2 * diagonals);
+    for row in 0..n {
+        for col in 0..n {
+            let sum = 2 * n + row + col;
+            let diff = 2 * n + diagonals + row + (n - 1 - col);
+            cover.add_row((row, col), &[row, n + col, sum, diff]);
+        }
+    }
+    cover
+}
+
+/// A 9 by 9 Sudoku, where `0` is an empty cell, with a row for each digit
+/// which may go to a cell labelled `(row, col, digit)`.
+///
+/// Each cell has a digit, and each row, column and box has each digit, so
+/// there are `4 * 81` primary columns.
+///
+/// Panics if a cell is more than `9`.
+pub fn sudoku(grid: &[[u8; 9]; 9]) -> ExactCover<(usize, usize, u8)> {
+    let mut cover = ExactCover::new(4 * 81, 0);
+    for (row, cells) in grid.iter().enumerate() {
+        for (col, &given) in cells.iter().enumerate() {
+            assert!(given <= 9, "invalid digit");
+            let digits = match given {
+                0 => 1..=9,
+                digit => digit..=digit,
+            };
+            for digit in digits {
+                let d = digit as usize - 1;
+                let block = row / 3 * 3 + col / 3;
+                cover.add_row(
+                    (row, col, digit),
+                    &[
+                        row * 9 + col,
+                        81 + row * 9 + d,
+                        2 * 81 + col * 9 + d,
+                        3 * 81 + block * 9 + d,
+                    ],
+                );
+            }
+        }
+    }
+    cover
+}
+
+/// The first solution of a Sudoku, see [`sudoku`].
+///
+/// # Examples
+///
+/// ```
+/// use try_2::exact_cover::adapters;
+///
+/// let mut grid = [[0; 9]; 9];
+/// grid[0] = [5, 3, 0, 0, 7, 0, 0, 0, 0];
+/// let solved = adapters::solve_sudoku(&grid).unwrap();
+/// assert_eq!(solved[0][..2], [5, 3]);
+/// ```
+pub fn solve_sudoku(grid: &[[u8; 9]; 9]) -> Option<[[u8; 9]; 9]> {
+    let cover = sudoku(grid);
+    let rows = cover.solutions().next()?;
+    let mut solved = [[0; 9]; 9];
+    for &(row, col, digit) in rows {
+        solved[row][col] = digit;
+    }
+    Some(solved)
+}
+
+/// The twelve pentominoes by their names, `#` for the squares.
+const PENTOMINOES: [(char, &str); 12] = [
+    ('F', ".##/##./.#."),
+    ('I', "#####"),
+    ('L', "####/#..."),
+    ('N', "##../.###"),
+    ('P', "##/##/#."),
+    ('T', "###/.#./.#."),
+    ('U', "#.#/###"),
+    ('V', "#../#../###"),
+    ('W', "#../##./.##"),
+    ('X', ".#./###/.#."),
+    ('Y', "####/.#.."),
+    ('Z', "##./.#./.##"),
+];
+
+/// The distinct orientations of a shape, rotated and reflected, each
+/// shifted to the origin and sorted.
+fn orientations(shape: &str) -> Vec<Vec<(usize, usize)>> {
+    let squares = shape
+        .split('/')
+        .enumerate()
+        .flat_map(|(row, line)| {
+            let cols = line.char_indices().filter(|&(_, c)| c == '#');
+            cols.map(move |(col, _)| (row as isize, col as isize))
+        })
+        .collect::<Vec<_>>();
+    let mut all = Vec::new();
+    for turn in 0..8 {
+        let oriented = squares
+            .iter()
+            .map(|&(row, col)| {
+                let (row, col) = if turn & 4 == 0 {
+                    (row, col)
+                } else {
+                    (row, -col)
+                };
+                match turn & 3 {
+                    0 => (row, col),
+                    1 => (col, -row),
+                    2 => (-row, -col),
+                    _ => (-col, row),
+                }
+            })
+            .collect::<Vec<_>>();
+        let top = oriented.iter().map(|&(row, _)| row).min().unwrap();
+        let left = oriented.iter().map(|&(_, col)| col).min().unwrap();
+        let mut shifted = oriented
+            .into_iter()
+            .map(|(row, col)| ((row - top) as usize, (col - left) as usize))
+            .collect::<Vec<_>>();
+        shifted.sort_unstable();
+        if !all.contains(&shifted) {
+            all.push(shifted);
+        }
+    }
+    all
+}
+
+/// The tilings of a `width * height` board without the `holes` by the
+/// twelve pentominoes, with a row for each way to place one, labelled by
+/// its name and its squares `(row, col)`.
+///
+/// Each pentomino and each square of the board is a primary column, so
+/// there are solutions only if the board has 60 squares.
+///
+/// # Examples
+///
+/// ```
+/// use try_2::exact_cover::adapters;
+///
+/// // Two tilings, each mirrored both ways.
+/// assert_eq!(adapters::pentominoes(20, 3, &[]).count(), 8);
+/// ```
+pub fn pentominoes(
+    width: usize,
+    height: usize,
+    holes: &[(usize, usize)],
+) -> ExactCover<(char, Vec<(usize, usize)>)> {
+    // The column of each square, or `None` for the holes.
+    let mut columns = vec![vec![Some(0); width]; height];
+    for &(row, col) in holes {
+        columns[row][col] = None;
+    }
+    let mut count = PENTOMINOES.len();
+    for column in columns.iter_mut().flatten().flatten() {
+        *column = count;
+        count += 1;
+    }
+    let mut cover = ExactCover::new(count, 0);
+    for (piece, &(name, shape)) in PENTOMINOES.iter().enumerate() {
+        for oriented in orientations(shape) {
+            for top in 0..height {
+                for left in 0..width {
+                    let placed = oriented
+                        .iter()
+                        .map(|&(row, col)| (top + row, left + col))
+                        .collect::<Vec<_>>();
+                    let cols = placed
+                        .iter()
+                        .map(|&(row, col)| *columns.get(row)?.get(col)?)
+                        .collect::<Option<Vec<_>>>();
+                    if let Some(mut cols) = cols {
+                        cols.push(piece);
+                        cover.add_row((name, placed), &cols);
+                    }
+                }
+            }
+        }
+    }
+    cover
+}
+
+/// The values of the pentagram of [`crate::Solution::pentagram_game`].
+const PENTAGRAM_VALUES: [i32; 10] = [1, 2, 3, 4, 5, 6, 8, 9, 10, 12];
+
+/// The lines of the pentagram by the indices of the game, each of which
+/// crosses every other one at a single index.
+const PENTAGRAM_LINES: [[usize; 4]; 5] = [
+    [3, 0, 1, 2],
+    [3, 4, 5, 6],
+    [6, 7, 1, 8],
+    [8, 2, 4, 9],
+    [9, 5, 7, 0],
+];
+
+/// The pentagram game, where the values go to the ten indices so that all
+/// the lines have the same sum, with a row for each way to fill a line,
+/// labelled by its indices and values.
+///
+/// Each index is on two lines, which is every value twice, so each line
+/// sums to `2 * 60 / 5 = 24`. The lines are primary columns, and so is
+/// `(index, value)` for each pair, which the first line of the index
+/// covers except for its value there, and the second only for that one,
+/// so that both agree. The second one also covers the primary column of
+/// the value, so each value is used once.
+///
+/// # Examples
+///
+/// ```
+/// use try_2::exact_cover::adapters;
+///
+/// // The 12 solutions of the game turn and mirror in 10 ways each.
+/// assert_eq!(adapters::pentagram().count(), 120);
+/// ```
+pub fn pentagram() -> ExactCover<[(usize, i32); 4]> {
+    let values = PENTAGRAM_VALUES.len();
+    let (lines, pairs) = (PENTAGRAM_LINES.len(), 10 * values);
+    let sum = 2 * PENTAGRAM_VALUES.iter().sum::<i32>() / lines as i32;
+    let mut cover = ExactCover::new(lines + pairs + values, 0);
+    let first = |index: usize| {
+        PENTAGRAM_LINES
+            .iter()
+            .position(|line| line.contains(&index))
+    };
+    for (line, indices) in PENTAGRAM_LINES.iter().enumerate() {
+        // Every ordered choice of four distinct values.
+        for code in 0..values.pow(4) {
+            let picks = [
+                code % values,
+                code / values % values,
+                code / values.pow(2) % values,
+                code / values.pow(3),
+            ];
+            let distinct = (0..4).all(|i| !picks[..i].contains(&picks[i]));
+            if !distinct || picks.iter().map(|&v| PENTAGRAM_VALUES[v]).sum::<i32>() != sum {
+                continue;
+            }
+            let mut cols = vec![line];
+            for (&index, &v) in indices.iter().zip(&picks) {
+                let pair = lines + index * values;
+                if first(index) == Some(line) {
+                    cols.extend((0..values).filter(|&w| w != v).map(|w| pair + w));
+                } else {
+                    cols.extend([pair + v, lines + pairs + v]);
+                }
+            }
+            let label = std::array::from_fn(|i| (indices[i], PENTAGRAM_VALUES[picks[i]]));
+            cover.add_row(label, &cols);
+        }
+    }
+    cover
+}
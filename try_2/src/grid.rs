@@ -0,0 +1,340 @@
+//! Grids with flood fill and connected components, for mazes and images.
+//!
+//! A position is `(row, col)`.
+
+use std::{
+    collections::VecDeque,
+    ops::{Index, IndexMut},
+};
+
+/// Which cells are next to each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Connectivity {
+    /// Up, down, left and right.
+    Four,
+    /// The four ones and the diagonals.
+    Eight,
+}
+
+impl Connectivity {
+    /// The offsets of the neighbors.
+    fn offsets(self) -> &'static [(isize, isize)] {
+        const EIGHT: [(isize, isize); 8] = [
+            (-1, 0),
+            (1, 0),
+            (0, -1),
+            (0, 1),
+            (-1, -1),
+            (-1, 1),
+            (1, -1),
+            (1, 1),
+        ];
+        match self {
+            Connectivity::Four => &EIGHT[..4],
+            Connectivity::Eight => &EIGHT,
+        }
+    }
+}
+
+/// The order to visit the cells in a flood fill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Search {
+    /// Nearer ones first, by a queue.
+    Breadth,
+    /// The latest found first, by a stack.
+    Depth,
+}
+
+/// A `width * height` grid stored row by row.
+///
+/// # Examples
+///
+/// ```
+/// use try_2::grid::{Connectivity, Grid, Search};
+///
+/// let maze = Grid::from_rows(vec![
+///     "..#".chars().collect(),
+///     "#.#".chars().collect(),
+///     "#..".chars().collect(),
+/// ]);
+/// let reached = maze.flood_fill((0, 0), Connectivity::Four, Search::Breadth, |&c| c == '.');
+/// assert_eq!(reached, [(0, 0), (0, 1), (1, 1), (2, 1), (2, 2)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// The grid of `cells` row by row.
+    ///
+    /// Panics if there are not `width * height` cells.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            Some(cells.len()),
+            width.checked_mul(height),
+            "wrong number of cells"
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// The grid of `rows`.
+    ///
+    /// Panics if the rows have different lengths.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        assert!(rows.iter().all(|row| row.len() == width), "ragged rows");
+        Self::new(width, height, rows.into_iter().flatten().collect())
+    }
+
+    /// The grid of `width * height` copies of `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    /// The number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The cell at `pos`, if it's in the grid.
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        (pos.0 < self.height && pos.1 < self.width).then(|| &self.cells[pos.0 * self.width + pos.1])
+    }
+
+    /// The rows from the top.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|row| &self.cells[row * self.width..(row + 1) * self.width])
+    }
+
+    /// The grid of `f` of each cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    /// The positions next to `pos` in the grid.
+    pub fn neighbors(
+        &self,
+        pos: (usize, usize),
+        connectivity: Connectivity,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        connectivity.offsets().iter().filter_map(move |&(dr, dc)| {
+            let row = pos.0.checked_add_signed(dr)?;
+            let col = pos.1.checked_add_signed(dc)?;
+            (row < self.height && col < self.width).then_some((row, col))
+        })
+    }
+
+    /// The positions reachable from `start` through the `passable` cells,
+    /// in the order they are visited, or none if `start` isn't passable.
+    ///
+    /// Every cell is visited once, so it takes O(width * height) at most.
+    pub fn flood_fill(
+        &self,
+        start: (usize, usize),
+        connectivity: Connectivity,
+        search: Search,
+        mut passable: impl FnMut(&T) -> bool,
+    ) -> Vec<(usize, usize)> {
+        let mut seen = Grid::filled(self.width, self.height, false);
+        let mut order = Vec::new();
+        if !self.get(start).is_some_and(&mut passable) {
+            return order;
+        }
+        seen[start] = true;
+        let mut pending = VecDeque::from([start]);
+        while let Some(pos) = match search {
+            Search::Breadth => pending.pop_front(),
+            Search::Depth => pending.pop_back(),
+        } {
+            order.push(pos);
+            for next in self.neighbors(pos, connectivity) {
+                if !seen[next] && passable(&self[next]) {
+                    seen[next] = true;
+                    pending.push_back(next);
+                }
+            }
+        }
+        order
+    }
+
+    /// Labels the connected components, where the cells next to each other
+    /// are connected if they are the `same`.
+    pub fn components(
+        &self,
+        connectivity: Connectivity,
+        mut same: impl FnMut(&T, &T) -> bool,
+    ) -> Components {
+        let mut labels = Grid::filled(self.width, self.height, usize::MAX);
+        let mut sizes = Vec::new();
+        let mut stack = Vec::new();
+        for start in (0..self.height).flat_map(|row| (0..self.width).map(move |col| (row, col))) {
+            if labels[start] != usize::MAX {
+                continue;
+            }
+            let label = sizes.len();
+            let mut size = 0;
+            labels[start] = label;
+            stack.push(start);
+            while let Some(pos) = stack.pop() {
+                size += 1;
+                for next in self.neighbors(pos, connectivity) {
+                    if labels[next] == usize::MAX && same(&self[pos], &self[next]) {
+                        labels[next] = label;
+                        stack.push(next);
+                    }
+                }
+            }
+            sizes.push(size);
+        }
+        Components { labels, sizes }
+    }
+
+    /// Replaces the cells connected to `start` which are equal to it by
+    /// `value`, a span of a row at a time, and returns how many they are,
+    /// or `0` if `start` isn't in the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use try_2::grid::{Connectivity, Grid};
+    ///
+    /// let mut image = Grid::new(3, 3, vec![1, 1, 0, 0, 1, 0, 1, 0, 1]);
+    /// let mut four = image.clone();
+    /// assert_eq!(four.scanline_fill((0, 0), Connectivity::Four, 2), 3);
+    /// assert_eq!(four, Grid::new(3, 3, vec![2, 2, 0, 0, 2, 0, 1, 0, 1]));
+    /// assert_eq!(image.scanline_fill((0, 0), Connectivity::Eight, 2), 5);
+    /// ```
+    pub fn scanline_fill(
+        &mut self,
+        start: (usize, usize),
+        connectivity: Connectivity,
+        value: T,
+    ) -> usize
+    where
+        T: Clone + PartialEq,
+    {
+        let Some(target) = self.get(start).cloned() else {
+            return 0;
+        };
+        if target == value {
+            let same = |cell: &T| *cell == target;
+            return self
+                .flood_fill(start, connectivity, Search::Depth, same)
+                .len();
+        }
+        // The spans of the rows next to a span are one cell wider for the
+        // diagonals.
+        let wider = (connectivity == Connectivity::Eight) as usize;
+        let mut filled = 0;
+        let mut seeds = vec![start];
+        while let Some((row, col)) = seeds.pop() {
+            if self[(row, col)] != target {
+                continue;
+            }
+            let (mut lo, mut hi) = (col, col);
+            while lo > 0 && self[(row, lo - 1)] == target {
+                lo -= 1;
+            }
+            while hi + 1 < self.width && self[(row, hi + 1)] == target {
+                hi += 1;
+            }
+            for col in lo..=hi {
+                self[(row, col)] = value.clone();
+            }
+            filled += hi - lo + 1;
+            let (lo, hi) = (lo.saturating_sub(wider), (hi + wider).min(self.width - 1));
+            for next in [row.wrapping_sub(1), row + 1] {
+                if next >= self.height {
+                    continue;
+                }
+                // A seed for each run of the target cells.
+                let mut col = lo;
+                while col <= hi {
+                    if self[(next, col)] == target {
+                        seeds.push((next, col));
+                        while col <= hi && self[(next, col)] == target {
+                            col += 1;
+                        }
+                    } else {
+                        col += 1;
+                    }
+                }
+            }
+        }
+        filled
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &T {
+        assert!(pos.0 < self.height && pos.1 < self.width, "out of the grid");
+        &self.cells[pos.0 * self.width + pos.1]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut T {
+        assert!(pos.0 < self.height && pos.1 < self.width, "out of the grid");
+        &mut self.cells[pos.0 * self.width + pos.1]
+    }
+}
+
+/// The connected components of a grid from [`Grid::components`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Components {
+    labels: Grid<usize>,
+    sizes: Vec<usize>,
+}
+
+impl Components {
+    /// The label of each cell, `0..count` in the order of their first cells.
+    pub fn labels(&self) -> &Grid<usize> {
+        &self.labels
+    }
+
+    /// The number of components.
+    pub fn count(&self) -> usize {
+        self.sizes.len()
+    }
+
+    /// The number of cells of each component.
+    pub fn sizes(&self) -> &[usize] {
+        &self.sizes
+    }
+
+    /// The perimeter of each component, the number of the cell edges
+    /// between it and the others or the border.
+    pub fn perimeters(&self) -> Vec<usize> {
+        let labels = &self.labels;
+        let mut perimeters = vec![0; self.count()];
+        for row in 0..labels.height {
+            for col in 0..labels.width {
+                let label = labels[(row, col)];
+                let inner = labels
+                    .neighbors((row, col), Connectivity::Four)
+                    .filter(|&next| labels[next] == label)
+                    .count();
+                perimeters[label] += 4 - inner;
+            }
+        }
+        perimeters
+    }
+}
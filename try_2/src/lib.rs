@@ -1,9 +1,50 @@
 use std::{
     collections::{HashMap, HashSet, VecDeque},
     hash::Hash,
-    mem, ptr,
+    mem,
 };
 
+pub mod exact_cover;
+pub mod grid;
+pub mod puzzle;
+pub mod queens;
+
+use grid::{Connectivity, Grid, Search};
+use puzzle::Move;
+
+/// A board of the sliding puzzle, the position of the null slot, and the
+/// move to it.
+type Step<T> = (Vec<Vec<T>>, (usize, usize), Option<Move>);
+
+/// The moves of the null slot from `src` to `dist` of
+/// [`Solution::sliding_puzzle`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlidingPath<T> {
+    /// The moves of the null slot.
+    pub moves: Vec<Move>,
+    /// The boards from `src` to `dist`, one more than the moves.
+    pub boards: Vec<Vec<Vec<T>>>,
+}
+
+/// Moves the null slot at `pos` of `vec` by `direct`, and returns where it
+/// ends up.
+fn slide<T>(vec: &mut [Vec<T>], pos: (usize, usize), direct: Move) -> (usize, usize) {
+    let next = match direct {
+        Move::Up => (pos.0 - 1, pos.1),
+        Move::Down => (pos.0 + 1, pos.1),
+        Move::Left => (pos.0, pos.1 - 1),
+        Move::Right => (pos.0, pos.1 + 1),
+    };
+    if pos.0 == next.0 {
+        vec[pos.0].swap(pos.1, next.1);
+    } else {
+        let (above, below) = (pos.min(next), pos.max(next));
+        let (head, tail) = vec.split_at_mut(below.0);
+        mem::swap(&mut head[above.0][above.1], &mut tail[0][below.1]);
+    }
+    next
+}
+
 pub struct Solution;
 
 impl Solution {
@@ -89,29 +130,25 @@ impl Solution {
     /// Question 2 - Red and Black
     ///
     /// '.' is a black block, '#' is a red block, '@' is a human. The human can only
-    /// move between the black blocks. This method use a simple `DFS` algorithm, but
-    /// it's sloooooowly.
+    /// move between the black blocks. This method counts the blocks the human can
+    /// reach, including the one at `pos`, by a flood fill of the [`grid::Grid`].
+    /// The rows may have different lengths, the missing blocks are red.
+    #[allow(clippy::ptr_arg)]
     pub fn red_and_black(room: &Vec<Vec<char>>, pos: (usize, usize)) -> usize {
-        let mut result = 1;
-        let mut goto = |pos: (usize, usize)| {
-            let mut room = room.clone();
-            if let Some(vec) = room.get_mut(pos.0) {
-                if let Some(c) = vec.get_mut(pos.1) {
-                    if mem::replace(c, '#') == '.' {
-                        result = result.max(Self::red_and_black(&room, pos) + 1);
-                    }
-                }
-            }
-        };
-        if pos.0 > 0 {
-            goto((pos.0 - 1, pos.1));
-        }
-        if pos.1 > 0 {
-            goto((pos.0, pos.1 - 1));
-        }
-        goto((pos.0 + 1, pos.1));
-        goto((pos.0, pos.1 + 1));
-        result
+        let width = room.iter().map(Vec::len).max().unwrap_or(0);
+        let rows = room
+            .iter()
+            .map(|row| {
+                let mut row = row.clone();
+                row.resize(width, '#');
+                row
+            })
+            .collect();
+        Grid::from_rows(rows)
+            .flood_fill(pos, Connectivity::Four, Search::Breadth, |&c| {
+                matches!(c, '.' | '@')
+            })
+            .len()
     }
 
     /// Question 3 - Sliding Puzzle Problem
@@ -123,23 +160,28 @@ impl Solution {
     /// _"magical number"_ (a.k.a. `inverse number`) for verifying
     /// whether the board is valid. The detail is that we have to
     /// map the `src` -> `dist` for the math inverse number. If the
-    /// puzzle is valid (inverse number (`cnt`) is the power of 2),
+    /// puzzle is valid (inverse number (`cnt`) is the power of 2,
+    /// after adding the rows between both `null slot`s if the width
+    /// is even, since a vertical move passes over `width - 1` blocks),
     /// we create double deque for Two-way `BFS`, one start by `src`
     /// and the other start by `dist`. We can simply setup a `hash
-    /// map` to record whether a step is repeated and who passby it.
-    /// For moving the blocks, the valid directions are filtered by
-    /// the `bound` of the board. Due to the borrowed rules, I swap
-    /// the raw ptr to avoid double mutable borrow (which actually
-    /// safe sometime).
+    /// map` to record whether a step is repeated, who passby it and
+    /// the move to it from the previous one. When both sides meet,
+    /// the path is traced back to `src` and `dist` by undoing those
+    /// moves. For moving the blocks, the valid directions are
+    /// filtered by the `bound` of the board.
+    ///
+    /// It's only feasible for small boards, see [`puzzle::Puzzle`] for
+    /// the boards of up to 16 cells with distinct tiles.
     pub fn sliding_puzzle<T: Clone + Eq + Hash>(
-        src: &Vec<Vec<T>>,
-        dist: &Vec<Vec<T>>,
+        src: &[Vec<T>],
+        dist: &[Vec<T>],
         nul: T,
-    ) -> Option<usize> {
+    ) -> Option<SlidingPath<T>> {
         assert_eq!(src.len(), dist.len());
         assert_eq!(
-            src.get(0).expect("Get bound failed!").len(),
-            dist.get(0).expect("Get bound failed!").len()
+            src.first().expect("Get bound failed!").len(),
+            dist.first().expect("Get bound failed!").len()
         );
         #[rustfmt::skip]
         let bound = (
@@ -149,16 +191,16 @@ impl Solution {
         let direct = |pos: (usize, usize)| {
             let mut direct = Vec::new();
             if pos.0 > bound.0 .0 {
-                direct.push((pos.0 - 1, pos.1));
+                direct.push(Move::Up);
             }
             if pos.0 < bound.0 .1 {
-                direct.push((pos.0 + 1, pos.1));
+                direct.push(Move::Down);
             }
             if pos.1 > bound.1 .0 {
-                direct.push((pos.0, pos.1 - 1));
+                direct.push(Move::Left);
             }
             if pos.1 < bound.1 .1 {
-                direct.push((pos.0, pos.1 + 1));
+                direct.push(Move::Right);
             }
             direct
         };
@@ -206,34 +248,56 @@ impl Solution {
                 cnt,
             )
         };
+        let cnt = match src[0].len() & 1 {
+            0 => cnt + pos1.0.abs_diff(pos2.0),
+            _ => cnt,
+        };
         if cnt & 1 == 0 {
             let mut map = HashMap::new();
             let mut deque1 = VecDeque::new();
             let mut deque2 = VecDeque::new();
-            deque1.push_back((src.clone(), pos1, 0));
-            deque2.push_back((dist.clone(), pos2, 0));
-            let search = |vec: &Vec<Vec<T>>,
-                          deque: &mut VecDeque<(Vec<Vec<T>>, (usize, usize), usize)>,
-                          pos: (usize, usize),
-                          step: usize| {
-                for next in direct(pos) {
+            deque1.push_back((src.to_vec(), pos1, None));
+            deque2.push_back((dist.to_vec(), pos2, None));
+            let search = |vec: &Vec<Vec<T>>, deque: &mut VecDeque<Step<T>>, pos: (usize, usize)| {
+                for direct in direct(pos) {
                     let mut vec = vec.clone();
-                    unsafe {
-                        ptr::swap(
-                            &mut vec[next.0][next.1] as *mut T,
-                            &mut vec[pos.0][pos.1] as *mut T,
-                        );
-                    }
-                    deque.push_back((vec, next, step + 1));
+                    let next = slide(&mut vec, pos, direct);
+                    deque.push_back((vec, next, Some(direct)));
+                }
+            };
+            // Undoes the moves from `vec` back to either `src` or `dist`,
+            // and returns them with the boards on the way.
+            let trace = |mut vec: Vec<Vec<T>>,
+                         mut pos: (usize, usize),
+                         mut last: Option<Move>,
+                         map: &HashMap<Vec<Vec<T>>, (i32, Option<Move>)>| {
+                let mut moves = Vec::new();
+                let mut boards = Vec::new();
+                while let Some(direct) = last {
+                    moves.push(direct);
+                    pos = slide(&mut vec, pos, direct.reverse());
+                    last = map[&vec].1;
+                    boards.push(vec.clone());
                 }
+                (moves, boards)
+            };
+            let meet = |vec: Vec<Vec<T>>, pos, last1, last2, map: &HashMap<_, _>| {
+                let (mut moves, mut boards) = trace(vec.clone(), pos, last1, map);
+                moves.reverse();
+                boards.reverse();
+                boards.push(vec.clone());
+                let (moves2, boards2) = trace(vec, pos, last2, map);
+                moves.extend(moves2.into_iter().map(Move::reverse));
+                boards.extend(boards2);
+                SlidingPath { moves, boards }
             };
             while match deque1.pop_front() {
-                Some((src, pos1, step1)) => {
+                Some((src, pos1, last1)) => {
                     match map.get(&src) {
-                        Some((2, step2)) => return Some(step1 + step2),
+                        Some(&(2, last2)) => return Some(meet(src, pos1, last1, last2, &map)),
                         None => {
-                            map.insert(src.clone(), (1, step1));
-                            search(&src, &mut deque1, pos1, step1);
+                            map.insert(src.clone(), (1, last1));
+                            search(&src, &mut deque1, pos1);
                         }
                         _ => (),
                     }
@@ -241,12 +305,12 @@ impl Solution {
                 }
                 None => false,
             } && match deque2.pop_front() {
-                Some((dist, pos2, step2)) => {
+                Some((dist, pos2, last2)) => {
                     match map.get(&dist) {
-                        Some((1, step1)) => return Some(step1 + step2),
+                        Some(&(1, last1)) => return Some(meet(dist, pos2, last1, last2, &map)),
                         None => {
-                            map.insert(dist.clone(), (2, step2));
-                            search(&dist, &mut deque2, pos2, step2);
+                            map.insert(dist.clone(), (2, last2));
+                            search(&dist, &mut deque2, pos2);
                         }
                         _ => (),
                     }
@@ -258,82 +322,68 @@ impl Solution {
         None
     }
 
+    /// Whether the `moves` of the null slot take `src` to `dist`, without
+    /// moving it off the board.
+    pub fn verify_path<T: Clone + Eq>(
+        src: &[Vec<T>],
+        dist: &[Vec<T>],
+        nul: T,
+        moves: &[Move],
+    ) -> bool {
+        let mut vec = src.to_vec();
+        let Some(mut pos) = vec
+            .iter()
+            .enumerate()
+            .find_map(|(x, row)| row.iter().position(|v| *v == nul).map(|y| (x, y)))
+        else {
+            return false;
+        };
+        for &direct in moves {
+            let (x, y) = pos;
+            let next = match direct {
+                Move::Up => x.checked_sub(1).map(|x| (x, y)),
+                Move::Down => Some((x + 1, y)),
+                Move::Left => y.checked_sub(1).map(|y| (x, y)),
+                Move::Right => Some((x, y + 1)),
+            };
+            match next {
+                Some((x, y)) if vec.get(x).is_some_and(|row| y < row.len()) => {
+                    pos = slide(&mut vec, pos, direct);
+                }
+                _ => return false,
+            }
+        }
+        vec == dist
+    }
+
     /// Question 4 - Eight Gueens Problem (0 ms 2.3 MB)
     ///
     /// Write an algorithm to print all ways of arranging n queens
-    /// on an n x n chess board so that none of them share the same
+    /// on an n x n chess board so that none of them share the same
     /// row, column, or diagonal. In this case, "diagonal" means all
     /// diagonals, not just the two that bisect the board.
     ///
     /// <https://leetcode-cn.com/problems/eight-queens-lcci>
     ///
-    /// This problem can be simply solved by `DFS`. Firstly, we build
-    /// a "map" with each line to it's column, then we fork the "map"
-    /// each different choose (column). The only thing we should pay
-    /// attention is cut the fork invalid (line 319 ~ 328). When the
-    /// recursion on the top (map forked is full), we convert the map
-    /// to result's element (line 307 ~ 316).
-    ///
-    /// # Issues
-    ///
-    /// Add this if FromIterator didn't preluded by your compiler.
-    ///
-    /// ``` rust
-    /// use core::iter::FromIterator;
-    /// ```
-    ///
-    /// If you are using a `stable` Rust and have some issues with the
-    /// `abs_diff`. Simply add this following function into the body,
-    /// then modify the line 322.
-    ///
-    /// ``` rust
-    /// use std::{cmp::Ordering, ops::Sub};
-    ///
-    /// fn abs_diff<T: Default + Ord + Sub<Output = T>>(a: T, b: T) -> T {
-    ///     match a.cmp(&b) {
-    ///         Ordering::Equal => T::default(),
-    ///         Ordering::Greater => a - b,
-    ///         Ordering::Less => b - a,
-    ///     }
-    /// }
-    /// ```
+    /// This problem can be simply solved by `DFS`. We place the queens
+    /// line by line, and keep the columns blocked by the queens above
+    /// as a bitmask of each line below, so the columns left are found
+    /// without checking every queen placed (see [`queens::Placement`]).
+    /// Then we convert each "map" of the columns to the result's
+    /// element.
     pub fn solve_n_queens(n: usize) -> Vec<Vec<String>> {
-        fn search(map: Vec<usize>, x: usize, result: &mut Vec<Vec<String>>) {
-            if x == map.len() {
-                result.push(
-                    vec![vec!['.'; map.len()]; map.len()]
-                        .iter_mut()
-                        .enumerate()
-                        .map(|(x, vec)| {
-                            vec[map[x]] = 'Q';
-                            String::from_iter(vec.iter())
-                        })
-                        .collect(),
-                );
-            } else {
-                for y in 0..map.len() {
-                    if {
-                        let mut check = true;
-                        for i in 0..x {
-                            if map[i] == y || i.abs_diff(x) == map[i].abs_diff(y) {
-                                check = false;
-                                break;
-                            }
-                        }
-                        check
-                    } {
-                        let mut map = map.clone();
-                        map[x] = y;
-                        search(map, x + 1, result);
-                    }
-                }
-            }
-        }
-        let mut result = Vec::new();
-        search(vec![0usize; n], 0, &mut result);
-        result
+        queens::Placement::new(n, queens::Piece::QUEEN)
+            .solutions()
+            .into_iter()
+            .map(|map| {
+                map.into_iter()
+                    .map(|y| (0..n).map(|x| if x == y { 'Q' } else { '.' }).collect())
+                    .collect()
+            })
+            .collect()
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::useless_vec)]
 mod tests;
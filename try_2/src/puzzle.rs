@@ -0,0 +1,522 @@
+//! Sliding puzzles of up to 16 cells, solved in the fewest moves by A* and
+//! IDA*.
+//!
+//! A board is packed into a `u64`, a nibble per cell in row-major order,
+//! where the tile `0` is the blank. The heuristic is the Manhattan distance
+//! plus the linear conflicts, and optionally additive pattern databases,
+//! which make 15-puzzles feasible.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+/// A move of the blank, e.g. `Up` slides the tile above it down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Move {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Move {
+    /// All the moves.
+    pub const ALL: [Move; 4] = [Move::Up, Move::Down, Move::Left, Move::Right];
+
+    /// The move which undoes this one.
+    pub fn reverse(self) -> Move {
+        match self {
+            Move::Up => Move::Down,
+            Move::Down => Move::Up,
+            Move::Left => Move::Right,
+            Move::Right => Move::Left,
+        }
+    }
+}
+
+/// A board of up to 16 cells, a nibble per cell.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Board(pub u64);
+
+impl Board {
+    /// Packs the tiles in row-major order.
+    ///
+    /// Panics if there are more than 16 tiles, or a tile is not in `0..16`.
+    pub fn new(tiles: &[u8]) -> Self {
+        assert!(tiles.len() <= 16, "too many cells");
+        assert!(tiles.iter().all(|&tile| tile < 16), "invalid tile");
+        Self(
+            tiles
+                .iter()
+                .rev()
+                .fold(0, |packed, &tile| packed << 4 | tile as u64),
+        )
+    }
+
+    /// The tile at `cell`.
+    pub fn get(self, cell: usize) -> u8 {
+        (self.0 >> (4 * cell) & 0xf) as u8
+    }
+
+    /// The tiles of the first `cells` cells.
+    pub fn tiles(self, cells: usize) -> Vec<u8> {
+        (0..cells).map(|cell| self.get(cell)).collect()
+    }
+
+    /// Slides the tile at `cell` into the blank at `blank`.
+    fn slide(self, blank: usize, cell: usize) -> Self {
+        let tile = self.get(cell) as u64;
+        Self(self.0 & !(0xf << (4 * cell)) | tile << (4 * blank))
+    }
+}
+
+/// An additive pattern database, the fewest moves of the tiles of a group
+/// to their goal cells, where moving the other tiles is free.
+#[derive(Debug, Clone)]
+struct Pattern {
+    tiles: Vec<u8>,
+    /// Indexed by the cells of the tiles, in base `cells`.
+    costs: Vec<u8>,
+}
+
+/// A sliding puzzle of `width * height` cells with the tiles `0..cells`,
+/// and the goal board.
+///
+/// # Examples
+///
+/// ```
+/// use try_2::puzzle::{Board, Move, Puzzle};
+///
+/// let puzzle = Puzzle::new(3, 3, &[1, 2, 3, 4, 5, 6, 7, 8, 0]);
+/// let start = Board::new(&[1, 2, 3, 4, 0, 6, 7, 5, 8]);
+/// assert_eq!(puzzle.ida_star(start), Some(vec![Move::Down, Move::Right]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    width: usize,
+    height: usize,
+    goal: Board,
+    /// The goal cell of each tile.
+    home: [usize; 16],
+    /// The row and column of each cell.
+    coords: [(usize, usize); 16],
+    patterns: Vec<Pattern>,
+}
+
+impl Puzzle {
+    /// The puzzle of `width * height` cells, to be solved into `goal`.
+    ///
+    /// Panics if there are less than 2 or more than 16 cells, or `goal` is
+    /// not a permutation of `0..cells`.
+    pub fn new(width: usize, height: usize, goal: &[u8]) -> Self {
+        let cells = width * height;
+        assert!((2..=16).contains(&cells), "invalid size");
+        assert_eq!(goal.len(), cells, "invalid goal");
+        let mut home = [usize::MAX; 16];
+        for (cell, &tile) in goal.iter().enumerate() {
+            assert!((tile as usize) < cells, "invalid goal");
+            assert_eq!(home[tile as usize], usize::MAX, "invalid goal");
+            home[tile as usize] = cell;
+        }
+        Self {
+            width,
+            height,
+            goal: Board::new(goal),
+            home,
+            coords: std::array::from_fn(|cell| (cell / width, cell % width)),
+            patterns: Vec::new(),
+        }
+    }
+
+    /// The puzzle of moving `src` to `dist`, where `nul` is the blank, and
+    /// the board of `src`.
+    ///
+    /// It's `None` unless both are the same rectangle of up to 16 cells, with
+    /// the same distinct tiles and a single blank.
+    pub fn from_grids<T: Eq + Hash>(
+        src: &[Vec<T>],
+        dist: &[Vec<T>],
+        nul: &T,
+    ) -> Option<(Self, Board)> {
+        let height = src.len();
+        let width = src.first()?.len();
+        let rectangle =
+            |grid: &[Vec<T>]| grid.len() == height && grid.iter().all(|row| row.len() == width);
+        if !rectangle(src) || !rectangle(dist) || !(2..=16).contains(&(width * height)) {
+            return None;
+        }
+        // The tiles are numbered in the order of `dist`.
+        let mut tiles = HashMap::new();
+        let mut goal = Vec::new();
+        for value in dist.iter().flatten() {
+            if value == nul {
+                goal.push(0);
+            } else {
+                let tile = tiles.len() as u8 + 1;
+                tiles.insert(value, tile).is_none().then_some(())?;
+                goal.push(tile);
+            }
+        }
+        // Every value is distinct, so both have a single blank if either does.
+        (tiles.len() + 1 == goal.len()).then_some(())?;
+        let mut start = Vec::new();
+        let mut seen = [false; 16];
+        for value in src.iter().flatten() {
+            let tile = if value == nul { 0 } else { *tiles.get(value)? };
+            (!seen[tile as usize]).then_some(())?;
+            seen[tile as usize] = true;
+            start.push(tile);
+        }
+        Some((Self::new(width, height, &goal), Board::new(&start)))
+    }
+
+    /// Adds an additive pattern database of each group of tiles, which are
+    /// disjoint and don't include the blank.
+    ///
+    /// A group of `k` tiles takes `cells^(k + 1)` bytes to build, e.g. 5 of
+    /// a 15-puzzle take 16 MB.
+    ///
+    /// Panics if the groups are not disjoint, have the blank or a tile not
+    /// in the puzzle, or are too large.
+    pub fn with_patterns(mut self, groups: &[&[u8]]) -> Self {
+        let mut used = [false; 16];
+        used[0] = true;
+        for group in groups {
+            for &tile in *group {
+                assert!((tile as usize) < self.cells(), "invalid tile");
+                assert!(!used[tile as usize], "the groups overlap");
+                used[tile as usize] = true;
+            }
+        }
+        let patterns = groups.iter().map(|group| self.pattern(group)).collect();
+        self.patterns = patterns;
+        self
+    }
+
+    /// Builds the pattern database of `tiles` by a 0-1 BFS from the goal,
+    /// over the cells of the tiles and the blank.
+    fn pattern(&self, tiles: &[u8]) -> Pattern {
+        let cells = self.cells();
+        let size = u32::try_from(tiles.len())
+            .ok()
+            .and_then(|k| cells.checked_pow(k))
+            .filter(|size| size.checked_mul(cells).is_some_and(|all| all <= 1 << 28))
+            .expect("the pattern is too large");
+        let encode = |at: &[usize]| at.iter().rev().fold(0, |index, &cell| index * cells + cell);
+        let mut dist = vec![u8::MAX; size * cells];
+        let mut at = tiles
+            .iter()
+            .map(|&tile| self.home[tile as usize])
+            .collect::<Vec<_>>();
+        let start = encode(&at) + size * self.home[0];
+        dist[start] = 0;
+        let mut deque = VecDeque::from([(start, 0)]);
+        while let Some((state, cost)) = deque.pop_front() {
+            if cost > dist[state] {
+                continue;
+            }
+            let blank = state / size;
+            let mut index = state % size;
+            for cell in &mut at {
+                *cell = index % cells;
+                index /= cells;
+            }
+            for mv in Move::ALL {
+                let Some(next) = self.neighbor(blank, mv) else {
+                    continue;
+                };
+                let moved = at.iter().position(|&cell| cell == next);
+                let (index, cost) = match moved {
+                    Some(i) => {
+                        at[i] = blank;
+                        let index = encode(&at);
+                        at[i] = next;
+                        (index, cost + 1)
+                    }
+                    None => (state % size, cost),
+                };
+                let state = index + size * next;
+                if cost < dist[state] {
+                    dist[state] = cost;
+                    match moved {
+                        Some(_) => deque.push_back((state, cost)),
+                        None => deque.push_front((state, cost)),
+                    }
+                }
+            }
+        }
+        let costs = (0..size)
+            .map(|index| {
+                (0..cells)
+                    .map(|blank| dist[index + size * blank])
+                    .min()
+                    .unwrap()
+            })
+            .collect();
+        Pattern {
+            tiles: tiles.to_vec(),
+            costs,
+        }
+    }
+
+    /// The number of cells.
+    pub fn cells(&self) -> usize {
+        self.width * self.height
+    }
+
+    /// The goal board.
+    pub fn goal(&self) -> Board {
+        self.goal
+    }
+
+    /// The cell of the blank.
+    fn blank(&self, board: Board) -> usize {
+        (0..self.cells())
+            .find(|&cell| board.get(cell) == 0)
+            .unwrap()
+    }
+
+    /// The cell next to `cell` in the direction of `mv`.
+    fn neighbor(&self, cell: usize, mv: Move) -> Option<usize> {
+        let (row, col) = (cell / self.width, cell % self.width);
+        match mv {
+            Move::Up => (row > 0).then(|| cell - self.width),
+            Move::Down => (row + 1 < self.height).then(|| cell + self.width),
+            Move::Left => (col > 0).then(|| cell - 1),
+            Move::Right => (col + 1 < self.width).then(|| cell + 1),
+        }
+    }
+
+    /// The board after `mv`, unless it moves the blank off the board.
+    pub fn apply(&self, board: Board, mv: Move) -> Option<Board> {
+        let blank = self.blank(board);
+        Some(board.slide(blank, self.neighbor(blank, mv)?))
+    }
+
+    /// The board after all the `moves`, unless any of them is off the board.
+    pub fn apply_all(&self, board: Board, moves: &[Move]) -> Option<Board> {
+        moves
+            .iter()
+            .try_fold(board, |board, &mv| self.apply(board, mv))
+    }
+
+    /// Whether `board`, a permutation of the tiles, can be solved.
+    ///
+    /// Every move swaps the blank with a tile, so the parity of the
+    /// permutation to the goal is that of the distance of the blank. On a
+    /// board of a single row or column, the tiles never pass each other, so
+    /// they have to be in the order of the goal instead.
+    pub fn is_solvable(&self, board: Board) -> bool {
+        let cells = self.cells();
+        if self.width == 1 || self.height == 1 {
+            let order = |board: Board| {
+                (0..cells)
+                    .map(move |cell| board.get(cell))
+                    .filter(|&tile| tile != 0)
+            };
+            return order(board).eq(order(self.goal));
+        }
+        let mut target = (0..cells)
+            .map(|cell| self.home[board.get(cell) as usize])
+            .collect::<Vec<_>>();
+        let mut swaps = 0;
+        for cell in 0..cells {
+            while target[cell] != cell {
+                let next = target[cell];
+                target.swap(cell, next);
+                swaps += 1;
+            }
+        }
+        let (from, to) = (self.blank(board), self.home[0]);
+        let distance = (from / self.width).abs_diff(to / self.width)
+            + (from % self.width).abs_diff(to % self.width);
+        swaps % 2 == distance % 2
+    }
+
+    /// The Manhattan distance of the tiles except `skip` ones.
+    fn manhattan(&self, board: Board, skip: &[bool; 16]) -> u32 {
+        let mut sum = 0;
+        for cell in 0..self.cells() {
+            let tile = board.get(cell) as usize;
+            if tile != 0 && !skip[tile] {
+                let ((row, col), (goal_row, goal_col)) =
+                    (self.coords[cell], self.coords[self.home[tile]]);
+                sum += row.abs_diff(goal_row) + col.abs_diff(goal_col);
+            }
+        }
+        sum as u32
+    }
+
+    /// Two extra moves for each tile which has to leave its goal line to
+    /// let the others pass, over all rows and columns.
+    fn linear_conflicts(&self, board: Board) -> u32 {
+        // The least number of `line` to remove for the rest to be in order,
+        // which is the length minus the longest increasing subsequence.
+        fn removals(line: &[usize]) -> usize {
+            let mut longest = [1; 16];
+            for i in 0..line.len() {
+                for j in 0..i {
+                    if line[j] < line[i] {
+                        longest[i] = longest[i].max(longest[j] + 1);
+                    }
+                }
+            }
+            line.len() - longest[..line.len()].iter().max().unwrap_or(&0)
+        }
+        let (width, height) = (self.width, self.height);
+        let mut conflicts = 0;
+        // The goal columns of the tiles in each row whose goal is that row,
+        // and then the same of the columns.
+        let mut lines = [[0; 16]; 17];
+        let mut lens = [0; 17];
+        for cell in 0..self.cells() {
+            let tile = board.get(cell) as usize;
+            if tile == 0 {
+                continue;
+            }
+            let ((row, col), (goal_row, goal_col)) =
+                (self.coords[cell], self.coords[self.home[tile]]);
+            if row == goal_row {
+                lines[row][lens[row]] = goal_col;
+                lens[row] += 1;
+            }
+            if col == goal_col {
+                // Cells are visited row by row, so a column is in order too.
+                lines[height + col][lens[height + col]] = goal_row;
+                lens[height + col] += 1;
+            }
+        }
+        for (line, &len) in lines.iter().zip(&lens).take(width + height) {
+            conflicts += removals(&line[..len]);
+        }
+        2 * conflicts as u32
+    }
+
+    /// The lower bound of the moves to solve `board`.
+    ///
+    /// It's the Manhattan distance plus the linear conflicts, or the sum of
+    /// the pattern databases and the Manhattan distance of the other tiles
+    /// if that's greater.
+    pub fn heuristic(&self, board: Board) -> u32 {
+        let estimate = self.manhattan(board, &[false; 16]) + self.linear_conflicts(board);
+        if self.patterns.is_empty() {
+            return estimate;
+        }
+        let cells = self.cells();
+        let mut at = [0; 16];
+        for cell in 0..cells {
+            at[board.get(cell) as usize] = cell;
+        }
+        let mut skip = [false; 16];
+        let mut sum = 0;
+        for pattern in &self.patterns {
+            let index = pattern.tiles.iter().rev().fold(0, |index, &tile| {
+                skip[tile as usize] = true;
+                index * cells + at[tile as usize]
+            });
+            sum += pattern.costs[index] as u32;
+        }
+        estimate.max(sum + self.manhattan(board, &skip))
+    }
+
+    /// The fewest moves to solve `start` by A*, unless it's unsolvable.
+    ///
+    /// It keeps every board it reaches, so it's quick but memory hungry,
+    /// see [`Puzzle::ida_star`] for the harder ones.
+    pub fn astar(&self, start: Board) -> Option<Vec<Move>> {
+        if !self.is_solvable(start) {
+            return None;
+        }
+        // The fewest moves to each board, and the last one of them.
+        let mut best = HashMap::from([(start, (0, None))]);
+        // Ordered by the estimate, then the deeper ones first.
+        let mut open = BinaryHeap::from([Reverse((self.heuristic(start), Reverse(0), start))]);
+        while let Some(Reverse((_, Reverse(g), board))) = open.pop() {
+            if board == self.goal {
+                break;
+            }
+            if best[&board].0 < g {
+                continue;
+            }
+            let blank = self.blank(board);
+            for mv in Move::ALL {
+                let Some(cell) = self.neighbor(blank, mv) else {
+                    continue;
+                };
+                let next = board.slide(blank, cell);
+                if best.get(&next).is_some_and(|&(cost, _)| cost <= g + 1) {
+                    continue;
+                }
+                best.insert(next, (g + 1, Some(mv)));
+                let h = self.heuristic(next);
+                open.push(Reverse((g + 1 + h, Reverse(g + 1), next)));
+            }
+        }
+        // The goal is missing if the search runs out of boards first.
+        let mut moves = Vec::new();
+        let mut board = self.goal;
+        while let Some(mv) = best.get(&board)?.1 {
+            moves.push(mv);
+            board = self.apply(board, mv.reverse()).unwrap();
+        }
+        moves.reverse();
+        Some(moves)
+    }
+
+    /// The fewest moves to solve `start` by IDA*, unless it's unsolvable.
+    ///
+    /// It's a depth-first search bounded by the estimate, raised to the
+    /// least one over it each round, so it only keeps the current path.
+    pub fn ida_star(&self, start: Board) -> Option<Vec<Move>> {
+        if !self.is_solvable(start) {
+            return None;
+        }
+        let mut bound = self.heuristic(start);
+        let mut moves = Vec::new();
+        loop {
+            match self.search(start, self.blank(start), 0, bound, &mut moves) {
+                Ok(()) => return Some(moves),
+                // Every path is a dead end.
+                Err(u32::MAX) => return None,
+                Err(next) => bound = next,
+            }
+        }
+    }
+
+    /// Searches from `board` after the `moves` of `g` in total, and returns
+    /// the least estimate over `bound` if the goal isn't found.
+    fn search(
+        &self,
+        board: Board,
+        blank: usize,
+        g: u32,
+        bound: u32,
+        moves: &mut Vec<Move>,
+    ) -> Result<(), u32> {
+        let f = g + self.heuristic(board);
+        if f > bound {
+            return Err(f);
+        }
+        if board == self.goal {
+            return Ok(());
+        }
+        let mut least = u32::MAX;
+        for mv in Move::ALL {
+            if moves.last() == Some(&mv.reverse()) {
+                continue;
+            }
+            let Some(cell) = self.neighbor(blank, mv) else {
+                continue;
+            };
+            moves.push(mv);
+            match self.search(board.slide(blank, cell), cell, g + 1, bound, moves) {
+                Ok(()) => return Ok(()),
+                Err(f) => least = least.min(f),
+            }
+            moves.pop();
+        }
+        Err(least)
+    }
+}
@@ -0,0 +1,362 @@
+//! N-Queens on bitboards, with its symmetries and other pieces.
+//!
+//! A solution places a piece in each row, and is given by the column of
+//! each row. The columns of a row are the bits of a `u32`, so the board is
+//! up to 32 by 32.
+
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+/// A piece which attacks the squares `distance` rows up or down and
+/// `offset` columns left or right if `(attacks)(distance, offset)`.
+///
+/// # Examples
+///
+/// ```
+/// use try_2::queens::{Piece, Placement};
+///
+/// // Nobody attacks anyone, so a piece goes anywhere in its row.
+/// let pawn = Piece { attacks: |_, _| false };
+/// assert_eq!(Placement::new(3, pawn).count(), 27);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Piece {
+    pub attacks: fn(distance: usize, offset: usize) -> bool,
+}
+
+impl Piece {
+    /// Along the column.
+    pub const ROOK: Piece = Piece {
+        attacks: |_, offset| offset == 0,
+    };
+    /// Along the column and the diagonals.
+    pub const QUEEN: Piece = Piece {
+        attacks: |distance, offset| offset == 0 || offset == distance,
+    };
+    /// As a queen or a knight.
+    pub const SUPERQUEEN: Piece = Piece {
+        attacks: |distance, offset| {
+            (Piece::QUEEN.attacks)(distance, offset) || (Piece::KNIGHT.attacks)(distance, offset)
+        },
+    };
+    /// Two squares one way and one square the other.
+    pub const KNIGHT: Piece = Piece {
+        attacks: |distance, offset| matches!((distance, offset), (1, 2) | (2, 1)),
+    };
+}
+
+/// Places a piece in each row of an `n * n` board, none of which attacks
+/// another, with some of them placed already.
+///
+/// Each level of the search keeps the blocked columns of the rows below,
+/// so it takes O(n) per piece.
+///
+/// # Examples
+///
+/// ```
+/// use try_2::queens::{Piece, Placement};
+///
+/// assert_eq!(Placement::new(8, Piece::QUEEN).count(), 92);
+/// assert_eq!(Placement::new(8, Piece::QUEEN).fix(0, 0).count(), 4);
+/// assert_eq!(Placement::new(10, Piece::SUPERQUEEN).count(), 4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Placement {
+    n: usize,
+    piece: Piece,
+    fixed: Vec<Option<usize>>,
+}
+
+impl Placement {
+    /// Panics if `n` is more than 32.
+    pub fn new(n: usize, piece: Piece) -> Self {
+        assert!(n <= 32, "the board is too large");
+        Self {
+            n,
+            piece,
+            fixed: vec![None; n],
+        }
+    }
+
+    /// Places a piece at `(row, col)` already.
+    ///
+    /// Panics if it's out of the board.
+    pub fn fix(mut self, row: usize, col: usize) -> Self {
+        assert!(row < self.n && col < self.n, "out of the board");
+        self.fixed[row] = Some(col);
+        self
+    }
+
+    /// Calls `found` with each solution, in lexicographic order, until it
+    /// returns `false`.
+    fn search(&self, mut found: impl FnMut(&[usize]) -> bool) {
+        let n = self.n;
+        // The columns attacked `distance` rows away from `col`, at
+        // `distance * n + col`.
+        let mut masks = vec![0u32; n * n];
+        for distance in 1..n {
+            for col in 0..n {
+                masks[distance * n + col] = (0..n)
+                    .filter(|&c| (self.piece.attacks)(distance, c.abs_diff(col)))
+                    .fold(0, |mask, c| mask | 1 << c);
+            }
+        }
+        let attacks = |distance: usize, col: usize| masks[distance * n + col];
+        let full = u32::MAX >> (32 - n.max(1));
+        // `levels[row][r]` is the blocked columns of row `r` with the rows
+        // above `row` placed.
+        let mut levels = vec![vec![0u32; n]; n + 1];
+        for (row, col) in self.fixed.iter().enumerate() {
+            let Some(col) = *col else {
+                continue;
+            };
+            for (r, blocked) in levels[0].iter_mut().enumerate() {
+                if r != row {
+                    *blocked |= attacks(row.abs_diff(r), col);
+                }
+            }
+        }
+        let mut cols = vec![0; n];
+        // The columns left to try in each row, from the lowest bit.
+        let mut pending = vec![0u32; n + 1];
+        let mut row = 0;
+        let candidates = |levels: &[Vec<u32>], row: usize| match self.fixed[row] {
+            Some(col) => !levels[row][row] & 1 << col,
+            None => !levels[row][row] & full,
+        };
+        if n == 0 {
+            found(&[]);
+            return;
+        }
+        pending[0] = candidates(&levels, 0);
+        loop {
+            if pending[row] == 0 {
+                if row == 0 {
+                    return;
+                }
+                row -= 1;
+                continue;
+            }
+            let col = pending[row].trailing_zeros() as usize;
+            pending[row] &= pending[row] - 1;
+            cols[row] = col;
+            if row + 1 == n {
+                if !found(&cols) {
+                    return;
+                }
+                continue;
+            }
+            let (above, below) = levels.split_at_mut(row + 1);
+            for r in row + 1..n {
+                below[0][r] = above[row][r] | attacks(r - row, col);
+            }
+            row += 1;
+            pending[row] = candidates(&levels, row);
+        }
+    }
+
+    /// The number of solutions.
+    pub fn count(&self) -> u64 {
+        let mut count = 0;
+        self.search(|_| {
+            count += 1;
+            true
+        });
+        count
+    }
+
+    /// All the solutions in lexicographic order.
+    pub fn solutions(&self) -> Vec<Vec<usize>> {
+        let mut solutions = Vec::new();
+        self.search(|cols| {
+            solutions.push(cols.to_vec());
+            true
+        });
+        solutions
+    }
+
+    /// The first solution in lexicographic order, if any.
+    pub fn first(&self) -> Option<Vec<usize>> {
+        let mut first = None;
+        self.search(|cols| {
+            first = Some(cols.to_vec());
+            false
+        });
+        first
+    }
+}
+
+/// The number of solutions of N-Queens, for `n` up to 32.
+///
+/// The queens are placed row by row, with the blocked columns and
+/// diagonals as three bitmasks shifted along. A solution and its mirror
+/// image have their first queens on either half, so only the left half is
+/// searched, and the middle column of an odd board on its own. The boards
+/// after the first two rows are shared out among the available threads.
+///
+/// # Examples
+///
+/// ```
+/// use try_2::queens;
+///
+/// assert_eq!(queens::count(8), 92);
+/// assert_eq!(queens::count(14), 365_596);
+/// ```
+pub fn count(n: usize) -> u64 {
+    fn place(full: u32, cols: u32, left: u32, right: u32) -> u64 {
+        if cols == full {
+            return 1;
+        }
+        let mut free = !(cols | left | right) & full;
+        let mut count = 0;
+        while free != 0 {
+            let bit = free & free.wrapping_neg();
+            free ^= bit;
+            count += place(full, cols | bit, (left | bit) << 1, (right | bit) >> 1);
+        }
+        count
+    }
+    assert!(n <= 32, "the board is too large");
+    if n == 0 {
+        return 1;
+    }
+    let full = u32::MAX >> (32 - n);
+    let next = |(weight, cols, left, right): (u64, u32, u32, u32), bit: u32| {
+        (weight, cols | bit, (left | bit) << 1, (right | bit) >> 1)
+    };
+    // The boards after the first two rows, each counted twice for its
+    // mirror image unless its first queen is in the middle.
+    let mut boards = Vec::new();
+    for col in 0..n.div_ceil(2) {
+        let weight = if 2 * col + 1 == n { 1 } else { 2 };
+        let first = next((weight, 0, 0, 0), 1 << col);
+        let (_, cols, left, right) = first;
+        let free = !(cols | left | right) & full;
+        if cols == full {
+            boards.push(first);
+        }
+        boards.extend(
+            (0..n)
+                .filter(|&c| free >> c & 1 == 1)
+                .map(|c| next(first, 1 << c)),
+        );
+    }
+    let solve = |(weight, cols, left, right)| weight * place(full, cols, left, right);
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    // Small boards aren't worth the threads.
+    if threads <= 1 || n < 12 {
+        return boards.into_iter().map(solve).sum();
+    }
+    let taken = AtomicUsize::new(0);
+    thread::scope(|scope| {
+        let workers = (0..threads.min(boards.len()))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut count = 0;
+                    while let Some(&board) = boards.get(taken.fetch_add(1, Ordering::Relaxed)) {
+                        count += solve(board);
+                    }
+                    count
+                })
+            })
+            .collect::<Vec<_>>();
+        workers
+            .into_iter()
+            .map(|worker| worker.join().unwrap())
+            .sum()
+    })
+}
+
+/// The symmetries of a solution under the rotations and reflections of the
+/// board.
+///
+/// A solution of N-Queens for `n > 1` is never the same when reflected.
+/// Across the middle column, all the queens would be in it, and across a
+/// diagonal, any two mirrored queens would share the other diagonal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+    /// Every symmetry, only the single queen on a single square.
+    Full,
+    /// The quarter turns, so it has 2 distinct images.
+    QuarterTurn,
+    /// The half turn, so it has 4 distinct images.
+    HalfTurn,
+    /// None, so it has 8 distinct images.
+    None,
+}
+
+impl Symmetry {
+    /// The number of distinct solutions by rotating and reflecting it.
+    pub fn images(self) -> usize {
+        match self {
+            Symmetry::Full => 1,
+            Symmetry::QuarterTurn => 2,
+            Symmetry::HalfTurn => 4,
+            Symmetry::None => 8,
+        }
+    }
+}
+
+/// The solution turned a quarter clockwise, where `(row, col)` goes to
+/// `(col, n - 1 - row)`.
+fn quarter_turn(cols: &[usize]) -> Vec<usize> {
+    let n = cols.len();
+    let mut turned = vec![0; n];
+    for (row, &col) in cols.iter().enumerate() {
+        turned[col] = n - 1 - row;
+    }
+    turned
+}
+
+/// The eight images of a solution under the rotations and reflections.
+fn images(cols: &[usize]) -> Vec<Vec<usize>> {
+    let n = cols.len();
+    let mut images = Vec::with_capacity(8);
+    let mut turned = cols.to_vec();
+    for _ in 0..4 {
+        let mirrored = turned.iter().map(|&col| n - 1 - col).collect();
+        let next = quarter_turn(&turned);
+        images.push(turned);
+        images.push(mirrored);
+        turned = next;
+    }
+    images
+}
+
+/// The fundamental solutions of N-Queens, the lexicographically least one
+/// of each class under the rotations and reflections, with its symmetry.
+///
+/// The images of all of them add up to the number of solutions.
+///
+/// # Examples
+///
+/// ```
+/// use try_2::queens::{self, Symmetry};
+///
+/// let fundamental = queens::fundamental(8);
+/// assert_eq!(fundamental.len(), 12);
+/// assert_eq!(fundamental.iter().map(|(_, sym)| sym.images()).sum::<usize>(), 92);
+/// assert_eq!(fundamental[0], (vec![0, 4, 7, 5, 2, 6, 1, 3], Symmetry::None));
+/// ```
+pub fn fundamental(n: usize) -> Vec<(Vec<usize>, Symmetry)> {
+    let mut fundamental = Vec::new();
+    Placement::new(n, Piece::QUEEN).search(|cols| {
+        let images = images(cols);
+        if images.iter().all(|image| cols <= &image[..]) {
+            let symmetry = if n <= 1 {
+                Symmetry::Full
+            } else if images[2] == cols {
+                Symmetry::QuarterTurn
+            } else if images[4] == cols {
+                Symmetry::HalfTurn
+            } else {
+                Symmetry::None
+            };
+            fundamental.push((cols.to_vec(), symmetry));
+        }
+        true
+    });
+    fundamental
+}
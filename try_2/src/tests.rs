@@ -1,4 +1,5 @@
 use crate::Solution;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 #[test]
 fn pentagram_game() {
@@ -53,7 +54,8 @@ fn sliding_puzzle_1() {
                 vec![7, 6, 5],
             ],
             0,
-        ),
+        )
+        .map(|path| path.moves.len()),
         Some(2),
     );
 }
@@ -74,7 +76,8 @@ fn sliding_puzzle_2() {
                 vec![7, 8, 0],
             ],
             0,
-        ),
+        )
+        .map(|path| path.moves.len()),
         Some(16),
     );
 }
@@ -137,7 +140,8 @@ fn sliding_puzzle_5() {
                 vec!['.', '.', '.'],
             ],
             ' ',
-        ),
+        )
+        .map(|path| path.moves.len()),
         Some(4),
     );
 }
@@ -160,7 +164,8 @@ fn sliding_puzzle_6() {
                 vec![13, 14, 15,  0],
             ],
             0,
-        ),
+        )
+        .map(|path| path.moves.len()),
         Some(36),
     );
 }
@@ -184,14 +189,808 @@ fn sliding_puzzle_7() {
                 vec![13, 14, 15,  0],
             ],
             0,
-        ),
+        )
+        .map(|path| path.moves.len()),
         Some(0),
     );
 }
 
+#[test]
+#[rustfmt::skip]
+fn sliding_puzzle_8() {
+    assert_eq!(
+        Solution::sliding_puzzle(
+            &vec![
+                vec![1, 2],
+                vec![3, 0],
+            ],
+            &vec![
+                vec![1, 0],
+                vec![3, 2],
+            ],
+            0,
+        )
+        .map(|path| path.moves),
+        Some(vec![crate::puzzle::Move::Up]),
+    );
+}
+
 #[test]
 fn solve_n_queens() {
     let result = Solution::solve_n_queens(8);
     assert_eq!(result.len(), 92);
     println!("{:#?}", result);
 }
+
+/// The distances of every solvable board of `puzzle` from the goal, by BFS.
+fn puzzle_distances(puzzle: &crate::puzzle::Puzzle) -> HashMap<crate::puzzle::Board, usize> {
+    use crate::puzzle::Move;
+    let mut dist = HashMap::from([(puzzle.goal(), 0)]);
+    let mut deque = VecDeque::from([puzzle.goal()]);
+    while let Some(board) = deque.pop_front() {
+        let step = dist[&board];
+        for mv in Move::ALL {
+            if let Some(next) = puzzle.apply(board, mv) {
+                dist.entry(next).or_insert_with(|| {
+                    deque.push_back(next);
+                    step + 1
+                });
+            }
+        }
+    }
+    dist
+}
+
+/// Small puzzles, each as `(plain, with patterns)`.
+fn small_puzzles() -> Vec<(crate::puzzle::Puzzle, crate::puzzle::Puzzle)> {
+    use crate::puzzle::Puzzle;
+    let puzzle = |width, height, goal: &[u8], groups: &[&[u8]]| {
+        let plain = Puzzle::new(width, height, goal);
+        let patterns = plain.clone().with_patterns(groups);
+        (plain, patterns)
+    };
+    vec![
+        puzzle(
+            3,
+            3,
+            &[1, 2, 3, 4, 5, 6, 7, 8, 0],
+            &[&[1, 2, 4, 5], &[3, 6, 7, 8]],
+        ),
+        puzzle(
+            4,
+            2,
+            &[0, 1, 2, 3, 4, 5, 6, 7],
+            &[&[1, 2, 5, 6], &[3, 4, 7]],
+        ),
+        puzzle(2, 3, &[5, 4, 3, 2, 1, 0], &[&[1, 2, 3]]),
+    ]
+}
+
+#[test]
+fn puzzle_1() {
+    use crate::puzzle::Board;
+    let board = Board::new(&[3, 1, 0, 2]);
+    assert_eq!(board, Board(0x2013));
+    assert_eq!(board.tiles(4), [3, 1, 0, 2]);
+}
+
+#[test]
+fn puzzle_2() {
+    for (plain, patterns) in small_puzzles() {
+        let dist = puzzle_distances(&plain);
+        let factorial = (1..=plain.cells()).product::<usize>();
+        assert_eq!(dist.len(), factorial / 2);
+        for (&board, &step) in &dist {
+            assert!(plain.is_solvable(board));
+            assert!(plain.heuristic(board) as usize <= step);
+            assert!(patterns.heuristic(board) as usize <= step);
+            assert!(plain.heuristic(board) <= patterns.heuristic(board));
+        }
+    }
+}
+
+#[test]
+fn puzzle_3() {
+    for (plain, patterns) in small_puzzles() {
+        let mut boards = puzzle_distances(&plain).into_iter().collect::<Vec<_>>();
+        boards.sort();
+        for (board, step) in boards.into_iter().step_by(997) {
+            for moves in [
+                plain.astar(board),
+                plain.ida_star(board),
+                patterns.astar(board),
+                patterns.ida_star(board),
+            ] {
+                let moves = moves.unwrap();
+                assert_eq!(moves.len(), step);
+                assert_eq!(plain.apply_all(board, &moves), Some(plain.goal()));
+            }
+        }
+    }
+}
+
+#[test]
+fn puzzle_4() {
+    use crate::puzzle::Board;
+    for (plain, patterns) in small_puzzles() {
+        let mut boards = puzzle_distances(&plain).into_keys().collect::<Vec<_>>();
+        boards.sort();
+        for board in boards.into_iter().step_by(997) {
+            // Swapping two tiles makes it unsolvable.
+            let mut tiles = board.tiles(plain.cells());
+            let (a, b) = match tiles.iter().position(|&tile| tile == 0).unwrap() {
+                0 | 1 => (2, 3),
+                _ => (0, 1),
+            };
+            tiles.swap(a, b);
+            let board = Board::new(&tiles);
+            assert!(!plain.is_solvable(board));
+            assert_eq!(plain.astar(board), None);
+            assert_eq!(patterns.ida_star(board), None);
+        }
+    }
+}
+
+#[test]
+fn puzzle_5() {
+    use crate::puzzle::{Board, Puzzle};
+    // The tiles of a single row or column never pass each other.
+    for (width, height) in [(4, 1), (1, 4)] {
+        let puzzle = Puzzle::new(width, height, &[1, 2, 3, 0]);
+        assert_eq!(puzzle_distances(&puzzle).len(), 4);
+        let start = Board::new(&[2, 3, 1, 0]);
+        assert!(!puzzle.is_solvable(start));
+        assert_eq!(puzzle.astar(start), None);
+        assert_eq!(puzzle.ida_star(start), None);
+        let start = Board::new(&[1, 0, 2, 3]);
+        assert!(puzzle.is_solvable(start));
+        assert_eq!(puzzle.astar(start).unwrap().len(), 2);
+        assert_eq!(puzzle.ida_star(start).unwrap().len(), 2);
+    }
+}
+
+#[test]
+fn puzzle_6() {
+    use crate::puzzle::{Board, Move, Puzzle};
+    let puzzle = Puzzle::new(2, 2, &[1, 2, 3, 0]);
+    assert_eq!(puzzle.astar(puzzle.goal()), Some(vec![]));
+    let start = Board::new(&[0, 1, 3, 2]);
+    assert_eq!(puzzle.apply(start, Move::Up), None);
+    assert_eq!(
+        puzzle.apply_all(start, &[Move::Right, Move::Down]),
+        Some(puzzle.goal())
+    );
+}
+
+#[test]
+#[rustfmt::skip]
+fn puzzle_7() {
+    use crate::puzzle::{Board, Puzzle};
+    let (puzzle, start) = Puzzle::from_grids(
+        &[
+            vec![ 1, 14,  3,  4],
+            vec![ 7,  0,  5,  8],
+            vec![ 9, 11, 10, 12],
+            vec![13,  2, 15,  6],
+        ],
+        &[
+            vec![ 1,  2,  3,  4],
+            vec![ 5,  6,  7,  8],
+            vec![ 9, 10, 11, 12],
+            vec![13, 14, 15,  0],
+        ],
+        &0,
+    )
+    .unwrap();
+    assert_eq!(puzzle.goal(), Board(0x0fed_cba9_8765_4321));
+    let moves = puzzle.ida_star(start).unwrap();
+    assert_eq!(moves.len(), 36);
+    assert_eq!(puzzle.apply_all(start, &moves), Some(puzzle.goal()));
+    assert_eq!(puzzle.astar(start).unwrap().len(), 36);
+}
+
+#[test]
+fn puzzle_8() {
+    use crate::puzzle::{Board, Puzzle};
+    let goal = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 0];
+    let puzzle = Puzzle::new(4, 4, &goal).with_patterns(&[
+        &[1, 5, 6, 9, 10],
+        &[7, 8, 11, 12, 15],
+        &[2, 3, 4, 13, 14],
+    ]);
+    let start = Board::new(&[12, 14, 11, 4, 7, 0, 9, 8, 15, 10, 3, 13, 1, 2, 5, 6]);
+    let moves = puzzle.ida_star(start).unwrap();
+    assert_eq!(puzzle.apply_all(start, &moves), Some(puzzle.goal()));
+    assert_eq!(moves.len(), 58);
+}
+
+#[test]
+fn puzzle_9() {
+    use crate::puzzle::Puzzle;
+    let grid = |rows: &[&str]| {
+        rows.iter()
+            .map(|row| row.chars().collect())
+            .collect::<Vec<Vec<_>>>()
+    };
+    let src = grid(&["ab", "c "]);
+    assert!(Puzzle::from_grids(&src, &grid(&["ba", " c"]), &' ').is_some());
+    assert!(Puzzle::from_grids(&src, &grid(&["bb", " c"]), &' ').is_none());
+    assert!(Puzzle::from_grids(&src, &grid(&["ba", "cd"]), &' ').is_none());
+    assert!(Puzzle::from_grids(&src, &grid(&["ba ", " c "]), &' ').is_none());
+    assert!(Puzzle::from_grids(&grid(&["aa", "c "]), &grid(&["ba", " c"]), &' ').is_none());
+    let full = grid(&["abcd", "efgh", "ijkl", "mnop"]);
+    assert!(Puzzle::from_grids(&full, &full, &' ').is_none());
+}
+
+#[test]
+fn sliding_puzzle_path() {
+    use crate::puzzle::{Move, Puzzle};
+    let dist = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 0]];
+    for src in [
+        vec![vec![3, 8, 5], vec![2, 0, 6], vec![1, 4, 7]],
+        vec![vec![8, 6, 7], vec![2, 5, 4], vec![3, 0, 1]],
+        vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 0]],
+        vec![vec![0, 1, 2], vec![3, 4, 5], vec![6, 7, 8]],
+    ] {
+        let path = Solution::sliding_puzzle(&src, &dist, 0).unwrap();
+        assert!(Solution::verify_path(&src, &dist, 0, &path.moves));
+        assert_eq!(path.boards.len(), path.moves.len() + 1);
+        assert_eq!(path.boards.first(), Some(&src));
+        assert_eq!(path.boards.last(), Some(&dist));
+        for (i, &direct) in path.moves.iter().enumerate() {
+            let (vec, next) = (&path.boards[i], &path.boards[i + 1]);
+            assert!(Solution::verify_path(vec, next, 0, &[direct]));
+        }
+        let (puzzle, start) = Puzzle::from_grids(&src, &dist, &0).unwrap();
+        assert_eq!(path.moves.len(), puzzle.ida_star(start).unwrap().len());
+    }
+
+    let src = vec![vec!['a', 'b'], vec!['c', ' ']];
+    let dist = vec![vec![' ', 'a'], vec!['c', 'b']];
+    let path = Solution::sliding_puzzle(&src, &dist, ' ').unwrap();
+    assert_eq!(path.moves, [Move::Up, Move::Left]);
+    assert!(Solution::verify_path(&src, &dist, ' ', &path.moves));
+    assert!(!Solution::verify_path(
+        &src,
+        &dist,
+        ' ',
+        &[Move::Left, Move::Up]
+    ));
+    assert!(!Solution::verify_path(&src, &dist, ' ', &[Move::Right]));
+    assert!(!Solution::verify_path(&src, &dist, ' ', &[Move::Down]));
+    assert!(!Solution::verify_path(&src, &dist, '_', &[]));
+    assert!(Solution::verify_path(&src, &src, ' ', &[]));
+}
+
+#[test]
+fn red_and_black_3() {
+    // Every black block counts, though no single walk passes all of them.
+    let room = vec![
+        vec!['#', '.', '#'],
+        vec!['.', '@', '.'],
+        vec!['#', '.', '#'],
+    ];
+    assert_eq!(Solution::red_and_black(&room, (1, 1)), 5);
+}
+
+#[test]
+fn red_and_black_4() {
+    // Only the black blocks are walkable, and the short rows end in red.
+    let room = vec![
+        vec!['.', '.', '.'],
+        vec!['@', 'x', '.', '.', '.'],
+        vec!['.'],
+        vec!['.', '.', '.', '.'],
+    ];
+    assert_eq!(Solution::red_and_black(&room, (1, 0)), 12);
+}
+
+/// Pseudo-random grids of a few colors, from a fixed xorshift seed.
+fn random_grids() -> Vec<crate::grid::Grid<u8>> {
+    use crate::grid::Grid;
+    let mut seed = 0x2545_f491_4f6c_dd1du64;
+    let mut random = move || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        seed
+    };
+    [
+        (1, 1, 1),
+        (7, 1, 2),
+        (1, 9, 2),
+        (8, 6, 2),
+        (13, 11, 3),
+        (20, 20, 2),
+    ]
+    .into_iter()
+    .map(|(width, height, colors)| {
+        let cells = (0..width * height)
+            .map(|_| (random() % colors) as u8)
+            .collect();
+        Grid::new(width, height, cells)
+    })
+    .collect()
+}
+
+/// The positions of `grid` row by row.
+fn grid_positions<T>(grid: &crate::grid::Grid<T>) -> impl Iterator<Item = (usize, usize)> + Clone {
+    let width = grid.width();
+    (0..grid.height()).flat_map(move |row| (0..width).map(move |col| (row, col)))
+}
+
+/// The root of the component of each cell, by a naive union-find over every
+/// pair of neighbors of the same color.
+fn grid_roots(grid: &crate::grid::Grid<u8>, connectivity: crate::grid::Connectivity) -> Vec<usize> {
+    fn find(parent: &mut [usize], i: usize) -> usize {
+        if parent[i] != i {
+            parent[i] = find(parent, parent[i]);
+        }
+        parent[i]
+    }
+    let width = grid.width();
+    let mut parent = (0..width * grid.height()).collect::<Vec<_>>();
+    for pos in grid_positions(grid) {
+        for next in grid.neighbors(pos, connectivity) {
+            if grid[pos] == grid[next] {
+                let a = find(&mut parent, pos.0 * width + pos.1);
+                let b = find(&mut parent, next.0 * width + next.1);
+                parent[a] = b;
+            }
+        }
+    }
+    (0..parent.len()).map(|i| find(&mut parent, i)).collect()
+}
+
+#[test]
+fn grid_1() {
+    use crate::grid::Connectivity;
+    for grid in random_grids() {
+        for connectivity in [Connectivity::Four, Connectivity::Eight] {
+            let components = grid.components(connectivity, |a, b| a == b);
+            let labels = components.labels();
+            let roots = grid_roots(&grid, connectivity);
+            let positions = grid_positions(&grid);
+            for (i, a) in positions.clone().enumerate() {
+                for (j, b) in positions.clone().enumerate() {
+                    assert_eq!(labels[a] == labels[b], roots[i] == roots[j]);
+                }
+            }
+            assert_eq!(
+                components.count(),
+                roots.iter().collect::<HashSet<_>>().len()
+            );
+            let cells = grid.width() * grid.height();
+            assert_eq!(components.sizes().iter().sum::<usize>(), cells);
+        }
+    }
+}
+
+#[test]
+fn grid_2() {
+    use crate::grid::{Connectivity, Search};
+    for grid in random_grids() {
+        for connectivity in [Connectivity::Four, Connectivity::Eight] {
+            let components = grid.components(connectivity, |a, b| a == b);
+            let labels = components.labels();
+            for (label, &size) in components.sizes().iter().enumerate() {
+                let start = grid_positions(&grid)
+                    .find(|&pos| labels[pos] == label)
+                    .unwrap();
+                let color = grid[start];
+                for search in [Search::Breadth, Search::Depth] {
+                    let reached = grid.flood_fill(start, connectivity, search, |&c| c == color);
+                    assert_eq!(reached[0], start);
+                    assert_eq!(reached.len(), size);
+                    assert!(reached.iter().all(|&pos| labels[pos] == label));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn grid_3() {
+    use crate::grid::Connectivity;
+    for grid in random_grids() {
+        for connectivity in [Connectivity::Four, Connectivity::Eight] {
+            let components = grid.components(connectivity, |a, b| a == b);
+            let labels = components.labels();
+            for (label, &perimeter) in components.perimeters().iter().enumerate() {
+                let edges = grid_positions(&grid)
+                    .filter(|&pos| labels[pos] == label)
+                    .map(|(row, col)| {
+                        let inside = |pos: Option<(usize, usize)>| {
+                            pos.and_then(|pos| labels.get(pos)) == Some(&label)
+                        };
+                        [
+                            row.checked_sub(1).map(|row| (row, col)),
+                            Some((row + 1, col)),
+                            col.checked_sub(1).map(|col| (row, col)),
+                            Some((row, col + 1)),
+                        ]
+                        .into_iter()
+                        .filter(|&pos| !inside(pos))
+                        .count()
+                    })
+                    .sum::<usize>();
+                assert_eq!(perimeter, edges);
+            }
+        }
+    }
+}
+
+#[test]
+fn grid_4() {
+    use crate::grid::{Connectivity, Grid};
+    for grid in random_grids() {
+        for connectivity in [Connectivity::Four, Connectivity::Eight] {
+            let components = grid.components(connectivity, |a, b| a == b);
+            let labels = components.labels();
+            for (label, &size) in components.sizes().iter().enumerate() {
+                let start = grid_positions(&grid)
+                    .find(|&pos| labels[pos] == label)
+                    .unwrap();
+                let mut filled = grid.clone();
+                assert_eq!(filled.scanline_fill(start, connectivity, u8::MAX), size);
+                let expect = grid_positions(&grid)
+                    .map(|pos| {
+                        if labels[pos] == label {
+                            u8::MAX
+                        } else {
+                            grid[pos]
+                        }
+                    })
+                    .collect();
+                assert_eq!(filled, Grid::new(grid.width(), grid.height(), expect));
+                // Filling with the same value changes nothing.
+                assert_eq!(
+                    filled.clone().scanline_fill(start, connectivity, u8::MAX),
+                    size
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn grid_5() {
+    use crate::grid::{Connectivity, Grid};
+    // A ring around a hole.
+    let ring = Grid::from_rows(vec![vec![1, 1, 1], vec![1, 0, 1], vec![1, 1, 1]]);
+    let components = ring.components(Connectivity::Four, |a, b| a == b);
+    assert_eq!(components.sizes(), [8, 1]);
+    assert_eq!(components.perimeters(), [16, 4]);
+    let components = ring.components(Connectivity::Eight, |_, _| true);
+    assert_eq!(components.sizes(), [9]);
+    assert_eq!(components.perimeters(), [12]);
+}
+
+#[test]
+fn grid_6() {
+    use crate::grid::{Connectivity, Grid, Search};
+    let empty = Grid::<u8>::new(0, 0, vec![]);
+    assert_eq!(empty.components(Connectivity::Four, |_, _| true).count(), 0);
+    assert!(empty
+        .flood_fill((0, 0), Connectivity::Four, Search::Depth, |_| true)
+        .is_empty());
+    assert_eq!(
+        empty.clone().scanline_fill((0, 0), Connectivity::Four, 1),
+        0
+    );
+    let mut ring = Grid::from_rows(vec![vec![1, 1, 1], vec![1, 0, 1], vec![1, 1, 1]]);
+    assert_eq!(ring.scanline_fill((3, 0), Connectivity::Eight, 1), 0);
+    assert_eq!(ring.get((3, 0)), None);
+}
+
+#[test]
+fn grid_7() {
+    use crate::grid::Grid;
+    let ring = Grid::from_rows(vec![vec![1, 1, 1], vec![1, 0, 1], vec![1, 1, 1]]);
+    assert_eq!(
+        ring.rows().collect::<Vec<_>>(),
+        [[1, 1, 1], [1, 0, 1], [1, 1, 1]]
+    );
+    assert_eq!(ring.map(|&c| c * 2)[(1, 2)], 2);
+}
+
+/// Every placement of a piece per row, checking each pair.
+fn naive_placements(n: usize, piece: crate::queens::Piece) -> Vec<Vec<usize>> {
+    let mut all = Vec::new();
+    for code in 0..n.pow(n as u32) {
+        let cols = (0..n)
+            .map(|row| code / n.pow(row as u32) % n)
+            .collect::<Vec<_>>();
+        let safe =
+            (0..n).all(|a| (a + 1..n).all(|b| !(piece.attacks)(b - a, cols[a].abs_diff(cols[b]))));
+        if safe {
+            all.push(cols);
+        }
+    }
+    all.sort();
+    all
+}
+
+#[test]
+fn queens_1() {
+    use crate::queens::{Piece, Placement};
+    let pieces = [Piece::ROOK, Piece::QUEEN, Piece::SUPERQUEEN, Piece::KNIGHT];
+    for n in 0..=6 {
+        for piece in pieces {
+            let all = naive_placements(n, piece);
+            let placement = Placement::new(n, piece);
+            assert_eq!(placement.solutions(), all);
+            assert_eq!(placement.count(), all.len() as u64);
+            assert_eq!(placement.first().as_ref(), all.first());
+        }
+    }
+}
+
+#[test]
+fn queens_2() {
+    use crate::queens::{Piece, Placement};
+    let pieces = [Piece::ROOK, Piece::QUEEN, Piece::SUPERQUEEN, Piece::KNIGHT];
+    for n in 1..=6 {
+        for piece in pieces {
+            let all = naive_placements(n, piece);
+            for (row, col) in [(0, 0), (n / 2, n - 1), (n - 1, 1)] {
+                if col >= n {
+                    continue;
+                }
+                let fixed = all
+                    .iter()
+                    .filter(|cols| cols[row] == col)
+                    .cloned()
+                    .collect::<Vec<_>>();
+                let placement = Placement::new(n, piece).fix(row, col);
+                assert_eq!(placement.solutions(), fixed);
+            }
+        }
+    }
+}
+
+#[test]
+fn queens_3() {
+    use crate::queens::{Piece, Placement};
+    assert_eq!(Placement::new(7, Piece::ROOK).count(), 5040);
+    let placement = Placement::new(8, Piece::QUEEN).fix(7, 3).fix(2, 1);
+    assert!(placement
+        .solutions()
+        .iter()
+        .all(|cols| cols[7] == 3 && cols[2] == 1));
+    assert_eq!(placement.clone().fix(5, 5).count(), 0);
+    assert_eq!(
+        placement.fix(7, 2).first(),
+        Some(vec![5, 3, 1, 7, 4, 6, 0, 2])
+    );
+}
+
+#[test]
+fn queens_4() {
+    use crate::queens::{self, Piece, Placement};
+    let expect = [
+        1, 1, 0, 0, 2, 10, 4, 40, 92, 352, 724, 2680, 14200, 73712, 365596, 2279184, 14772512,
+    ];
+    for (n, &count) in expect.iter().enumerate() {
+        assert_eq!(queens::count(n), count);
+        if n <= 12 {
+            assert_eq!(Placement::new(n, Piece::QUEEN).count(), count);
+        }
+    }
+}
+
+#[test]
+fn queens_5() {
+    use crate::queens::{self, Piece, Placement};
+    let expect = [1, 1, 0, 0, 1, 2, 1, 6, 12, 46, 92, 341];
+    for (n, &len) in expect.iter().enumerate() {
+        let fundamental = queens::fundamental(n);
+        assert_eq!(fundamental.len(), len);
+        let images = fundamental.iter().map(|(_, symmetry)| symmetry.images());
+        assert_eq!(images.sum::<usize>() as u64, queens::count(n));
+        for (cols, _) in &fundamental {
+            let mut fixed = Placement::new(n, Piece::QUEEN);
+            for (row, &col) in cols.iter().enumerate() {
+                fixed = fixed.fix(row, col);
+            }
+            assert_eq!(fixed.count(), 1);
+        }
+    }
+}
+
+#[test]
+fn queens_6() {
+    use crate::queens::{self, Symmetry};
+    assert_eq!(queens::fundamental(1), [(vec![0], Symmetry::Full)]);
+    assert_eq!(
+        queens::fundamental(4),
+        [(vec![1, 3, 0, 2], Symmetry::QuarterTurn)]
+    );
+    let symmetries = queens::fundamental(10)
+        .into_iter()
+        .filter(|(_, symmetry)| *symmetry == Symmetry::HalfTurn)
+        .count();
+    assert_eq!(symmetries, 3);
+}
+
+#[test]
+fn queens_7() {
+    use crate::queens::{Piece, Placement};
+    let expect = [1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 4, 44, 156];
+    for (n, &count) in expect.iter().enumerate() {
+        assert_eq!(Placement::new(n, Piece::SUPERQUEEN).count(), count);
+    }
+}
+
+#[test]
+fn exact_cover_1() {
+    use crate::exact_cover::ExactCover;
+    // Knuth's example, with the rows labelled from `a`.
+    let mut cover = ExactCover::new(7, 0);
+    for (label, columns) in [
+        ('a', &[2, 4, 5][..]),
+        ('b', &[0, 3, 6]),
+        ('c', &[1, 2, 5]),
+        ('d', &[0, 3]),
+        ('e', &[1, 6]),
+        ('f', &[3, 4, 6]),
+    ] {
+        cover.add_row(label, columns);
+    }
+    let mut solutions = cover.solutions().collect::<Vec<_>>();
+    assert_eq!(solutions.len(), 1);
+    solutions[0].sort();
+    assert_eq!(solutions[0], [&'a', &'d', &'e']);
+    assert_eq!(cover.rows().len(), 6);
+    assert_eq!(cover.columns(), 7);
+}
+
+#[test]
+fn exact_cover_2() {
+    use crate::exact_cover::ExactCover;
+    // A secondary column may be left uncovered, but not covered twice.
+    let mut cover = ExactCover::new(2, 1);
+    cover.add_row(0, &[0, 2]);
+    cover.add_row(1, &[1, 2]);
+    cover.add_row(2, &[0]);
+    cover.add_row(3, &[1]);
+    let mut all = cover.solutions().collect::<Vec<_>>();
+    all.iter_mut().for_each(|rows| rows.sort());
+    all.sort();
+    assert_eq!(all, [vec![&0, &3], vec![&1, &2], vec![&2, &3]]);
+}
+
+#[test]
+fn exact_cover_3() {
+    use crate::exact_cover::ExactCover;
+    assert_eq!(ExactCover::<()>::new(0, 3).count(), 1);
+    let mut cover = ExactCover::new(2, 0);
+    cover.add_row((), &[0]);
+    cover.add_row((), &[]);
+    assert_eq!(cover.count(), 0);
+}
+
+#[test]
+fn exact_cover_4() {
+    use crate::exact_cover::adapters;
+    for n in 0..10 {
+        let cover = adapters::n_queens(n);
+        let mut solutions = cover.solutions();
+        assert_eq!(solutions.by_ref().count() as u64, crate::queens::count(n));
+        assert!(solutions.is_complete());
+    }
+}
+
+#[test]
+fn exact_cover_5() {
+    use crate::exact_cover::adapters;
+    for n in 0..10 {
+        let cover = adapters::n_queens(n);
+        let mut solutions = cover.solutions();
+        let all = solutions.by_ref().count();
+        let nodes = solutions.nodes();
+        let mut limited = cover.solutions().budget(nodes.saturating_sub(1));
+        assert!(limited.by_ref().count() <= all);
+        assert_eq!(limited.is_complete(), nodes == 0);
+        assert_eq!(limited.next(), None);
+        assert_eq!(limited.nodes(), nodes.saturating_sub(1));
+    }
+}
+
+/// A Sudoku with only 17 givens.
+fn sudoku_puzzle() -> [[u8; 9]; 9] {
+    [
+        "000000010",
+        "400000000",
+        "020000000",
+        "000050407",
+        "008000300",
+        "001090000",
+        "300400200",
+        "050100000",
+        "000806000",
+    ]
+    .map(|row| {
+        let mut digits = [0; 9];
+        for (digit, c) in digits.iter_mut().zip(row.bytes()) {
+            *digit = c - b'0';
+        }
+        digits
+    })
+}
+
+#[test]
+fn exact_cover_6() {
+    use crate::exact_cover::adapters;
+    let puzzle = sudoku_puzzle();
+    let solved = adapters::solve_sudoku(&puzzle).unwrap();
+    assert_eq!(adapters::sudoku(&puzzle).count(), 1);
+    for i in 0..9 {
+        let row = solved[i].iter().collect::<HashSet<_>>();
+        let col = solved.iter().map(|row| row[i]).collect::<HashSet<_>>();
+        let block = (0..9)
+            .map(|j| solved[i / 3 * 3 + j / 3][i % 3 * 3 + j % 3])
+            .collect::<HashSet<_>>();
+        assert!(row.len() == 9 && col.len() == 9 && block.len() == 9);
+        for j in 0..9 {
+            assert!(puzzle[i][j] == 0 || puzzle[i][j] == solved[i][j]);
+        }
+    }
+}
+
+#[test]
+fn exact_cover_7() {
+    use crate::exact_cover::adapters;
+    let mut wrong = sudoku_puzzle();
+    wrong[0][0] = 1;
+    assert_eq!(adapters::solve_sudoku(&wrong), None);
+}
+
+#[test]
+fn exact_cover_8() {
+    use crate::exact_cover::adapters;
+    let cover = adapters::pentominoes(20, 3, &[]);
+    assert_eq!(
+        cover.rows().iter().filter(|(name, _)| *name == 'X').count(),
+        18
+    );
+    for tiling in cover.solutions() {
+        let names = tiling.iter().map(|(name, _)| name).collect::<HashSet<_>>();
+        let squares = tiling
+            .iter()
+            .flat_map(|(_, squares)| squares)
+            .collect::<HashSet<_>>();
+        assert_eq!((names.len(), squares.len()), (12, 60));
+    }
+}
+
+#[test]
+fn exact_cover_9() {
+    use crate::exact_cover::adapters;
+    // Dana Scott's board, with a hole of 2 by 2 in the middle.
+    let holes = [(3, 3), (3, 4), (4, 3), (4, 4)];
+    assert_eq!(adapters::pentominoes(8, 8, &holes).count(), 520);
+    assert_eq!(adapters::pentominoes(5, 5, &[]).count(), 0);
+}
+
+#[test]
+fn exact_cover_10() {
+    use crate::exact_cover::adapters;
+    let solutions = adapters::pentagram()
+        .solutions()
+        .map(|lines| {
+            let mut vec = vec![0; 10];
+            for line in lines {
+                assert_eq!(line.iter().map(|&(_, value)| value).sum::<i32>(), 24);
+                for &(index, value) in line {
+                    vec[index] = value;
+                }
+            }
+            vec
+        })
+        .collect::<HashSet<_>>();
+    assert_eq!(solutions.len(), 120);
+    for vec in Solution::pentagram_game() {
+        assert!(solutions.contains(&vec));
+    }
+}
@@ -1,30 +1,454 @@
 use core::{
     cmp::Ordering,
-    fmt::{Debug, Formatter, Result},
-    ops::{Add, AddAssign, Deref, DerefMut, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
+    fmt::{self, Debug, Display, Formatter},
+    ops::{
+        Add, AddAssign, Deref, DerefMut, Div, DivAssign, Mul, MulAssign, Rem, RemAssign, Sub,
+        SubAssign,
+    },
 };
 
-#[derive(Clone, Default, Hash, PartialEq, Eq)]
+mod number_theory;
+mod ops;
+
+#[derive(Clone, Hash, PartialEq, Eq)]
 pub struct BigInteger {
     base: Vec<u8>,
     sign: bool,
 }
 
+/// The error type returned when a conversion from or to a [`BigInteger`]
+/// fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConversionError {
+    /// The value is greater than the maximum of the target type.
+    PosOverflow,
+    /// The value is less than the minimum of the target type.
+    NegOverflow,
+    /// The float to be converted is NaN.
+    NaN,
+    /// The float to be converted is infinite.
+    Infinite,
+}
+
+impl Display for ConversionError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::PosOverflow => "big integer too large to fit in target type",
+            Self::NegOverflow => "big integer too small to fit in target type",
+            Self::NaN => "cannot convert NaN to big integer",
+            Self::Infinite => "cannot convert infinite float to big integer",
+        })
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
 impl BigInteger {
     pub fn get_bit(&self, i: usize) -> u8 {
-        self[i >> 3] >> i % 8 & 1
+        self[i >> 3] >> (i % 8) & 1
     }
 
     pub fn is_zero(&self) -> bool {
         self.iter().all(|&v| v == 0)
     }
 
+    pub fn is_negative(&self) -> bool {
+        !self.sign && !self.is_zero()
+    }
+
     pub fn bit_length(&self) -> usize {
         match self.last() {
             Some(last) => (self.len() << 3) - last.leading_zeros() as usize,
             None => 0,
         }
     }
+
+    /// Returns the absolute value of `self`.
+    pub fn abs(&self) -> Self {
+        Self {
+            base: self.base.clone(),
+            sign: true,
+        }
+    }
+
+    /// Truncated division, returns the quotient and the remainder at once.
+    ///
+    /// Same as the primitive integers, the quotient rounds towards zero and
+    /// the remainder takes the sign of `self`. Panics if `rhs` is zero.
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        self.div_rem_signed(&rhs.base, rhs.sign)
+    }
+
+    /// The least non-negative remainder of `self (mod rhs)`.
+    pub fn rem_euclid(&self, rhs: &Self) -> Self {
+        let (_, rem) = div_rem_magnitude(&self.base, &rhs.base);
+        if !self.sign && !rem.is_empty() {
+            let mut base = trim(&rhs.base).to_vec();
+            sub_magnitude(&mut base, &rem);
+            Self::from_parts(base, true)
+        } else {
+            Self::from_parts(rem, true)
+        }
+    }
+
+    /// Writes `self` in the given radix with lowercase digits, prefixed by a
+    /// `-` if negative. Panics if `radix` is not in `2..=36`.
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        let digits = to_radix(&self.base, radix);
+        if self.is_negative() {
+            format!("-{digits}")
+        } else {
+            digits
+        }
+    }
+
+    /// Converts to the nearest `f64`, ties to even, and out of range values
+    /// become infinite.
+    pub fn to_f64(&self) -> f64 {
+        let (mantissa, shift) = self.round_to_bits(f64::MANTISSA_DIGITS as usize);
+        let value = if shift == 0 {
+            mantissa as f64
+        } else {
+            let exp = (shift + f64::MANTISSA_DIGITS as usize - 2) as u64 + 1024;
+            if exp >= 0x7ff {
+                f64::INFINITY
+            } else {
+                f64::from_bits(exp << 52 | mantissa & ((1 << 52) - 1))
+            }
+        };
+        if self.is_negative() {
+            -value
+        } else {
+            value
+        }
+    }
+
+    /// Converts to the nearest `f32`, ties to even, and out of range values
+    /// become infinite.
+    pub fn to_f32(&self) -> f32 {
+        let (mantissa, shift) = self.round_to_bits(f32::MANTISSA_DIGITS as usize);
+        let value = if shift == 0 {
+            mantissa as f32
+        } else {
+            let exp = (shift + f32::MANTISSA_DIGITS as usize - 2) as u32 + 128;
+            if exp >= 0xff {
+                f32::INFINITY
+            } else {
+                f32::from_bits(exp << 23 | mantissa as u32 & ((1 << 23) - 1))
+            }
+        };
+        if self.is_negative() {
+            -value
+        } else {
+            value
+        }
+    }
+
+    /// Converts to `i128` by keeping the lowest 128 bits of the two's
+    /// complement representation, which is the same as an `as` cast.
+    pub fn to_i128_wrapping(&self) -> i128 {
+        let value = self
+            .iter()
+            .take(16)
+            .rev()
+            .fold(0u128, |v, &b| v << 8 | b as u128) as i128;
+        if self.sign {
+            value
+        } else {
+            value.wrapping_neg()
+        }
+    }
+
+    /// Rounds the magnitude to `digits` significant bits, ties to even.
+    ///
+    /// Returns `(mantissa, shift)` where the rounded value is `mantissa <<
+    /// shift`, and the `shift` is zero only if the magnitude is exact.
+    fn round_to_bits(&self, digits: usize) -> (u64, usize) {
+        let bits = self.bit_length();
+        let Some(mut shift) = bits.checked_sub(digits).filter(|&v| v > 0) else {
+            return (to_u128(trim(&self.base)).unwrap_or_default() as u64, 0);
+        };
+        let mut mantissa = to_u128(&shr_magnitude(&self.base, shift)).unwrap_or_default() as u64;
+        let half = self.get_bit(shift - 1) == 1;
+        if half && (trailing_zeros(&self.base) < shift - 1 || mantissa & 1 == 1) {
+            mantissa += 1;
+            if mantissa >> digits > 0 {
+                mantissa >>= 1;
+                shift += 1;
+            }
+        }
+        (mantissa, shift)
+    }
+
+    fn from_parts(base: Vec<u8>, sign: bool) -> Self {
+        let mut this = Self { base, sign };
+        this.normalize();
+        this
+    }
+
+    /// Removes the leading zero bytes, and zero is always non-negative.
+    fn normalize(&mut self) {
+        while let Some(0) = self.last() {
+            self.pop();
+        }
+        if self.is_empty() {
+            self.sign = true;
+        }
+    }
+
+    fn mul_signed(&self, rhs: &[u8], sign: bool) -> Self {
+        Self::from_parts(mul_magnitude(&self.base, rhs), self.sign == sign)
+    }
+
+    fn div_rem_signed(&self, rhs: &[u8], sign: bool) -> (Self, Self) {
+        let (quo, rem) = div_rem_magnitude(&self.base, rhs);
+        (
+            Self::from_parts(quo, self.sign == sign),
+            Self::from_parts(rem, self.sign),
+        )
+    }
+
+    fn cmp_signed(&self, rhs: &[u8], sign: bool) -> Ordering {
+        let sign = sign || trim(rhs).is_empty();
+        match self.sign.cmp(&sign) {
+            Ordering::Equal if self.sign => cmp_magnitude(&self.base, rhs),
+            Ordering::Equal => cmp_magnitude(rhs, &self.base),
+            ord => ord,
+        }
+    }
+
+    fn add_signed(&mut self, rhs: &[u8], sign: bool) {
+        if self.sign == sign {
+            add_magnitude(&mut self.base, rhs);
+        } else if cmp_magnitude(&self.base, rhs) == Ordering::Less {
+            let mut base = trim(rhs).to_vec();
+            sub_magnitude(&mut base, &self.base);
+            self.base = base;
+            self.sign = sign;
+        } else {
+            sub_magnitude(&mut self.base, rhs);
+        }
+        self.normalize();
+    }
+}
+
+/// Strips the leading (most significant) zero bytes of a magnitude.
+fn trim(base: &[u8]) -> &[u8] {
+    let len = base.len() - base.iter().rev().take_while(|&&v| v == 0).count();
+    &base[..len]
+}
+
+/// Collects a magnitude into an `u128`, or `None` if it is too large.
+fn to_u128(base: &[u8]) -> Option<u128> {
+    let base = trim(base);
+    (base.len() <= 16).then(|| base.iter().rev().fold(0, |v, &b| v << 8 | b as u128))
+}
+
+/// Writes a magnitude in the given radix, without any sign or prefix.
+///
+/// The magnitude is divided by the largest power of `radix` which fits in a
+/// byte at a time, so every short division produces several digits.
+fn to_radix(base: &[u8], radix: u32) -> String {
+    assert!((2..=36).contains(&radix), "radix must be in 2..=36");
+    let (mut chunk, mut width) = (radix as u16, 1);
+    while chunk * radix as u16 <= 0x100 {
+        chunk *= radix as u16;
+        width += 1;
+    }
+    let mut base = trim(base).to_vec();
+    let mut digits = Vec::new();
+    while !base.is_empty() {
+        let mut rem = 0u16;
+        for value in base.iter_mut().rev() {
+            let next = rem << 8 | *value as u16;
+            *value = (next / chunk) as u8;
+            rem = next % chunk;
+        }
+        while let Some(0) = base.last() {
+            base.pop();
+        }
+        for _ in 0..width {
+            digits.push(char::from_digit((rem % radix as u16) as u32, radix).unwrap());
+            rem /= radix as u16;
+        }
+    }
+    while digits.len() > 1 && digits.last() == Some(&'0') {
+        digits.pop();
+    }
+    if digits.is_empty() {
+        digits.push('0');
+    }
+    digits.into_iter().rev().collect()
+}
+
+fn trailing_zeros(base: &[u8]) -> usize {
+    match base.iter().position(|&v| v != 0) {
+        Some(idx) => (idx << 3) + base[idx].trailing_zeros() as usize,
+        None => 0,
+    }
+}
+
+fn cmp_magnitude(lhs: &[u8], rhs: &[u8]) -> Ordering {
+    let (lhs, rhs) = (trim(lhs), trim(rhs));
+    match lhs.len().cmp(&rhs.len()) {
+        Ordering::Equal => lhs.iter().rev().cmp(rhs.iter().rev()),
+        ord => ord,
+    }
+}
+
+fn add_magnitude(lhs: &mut Vec<u8>, rhs: &[u8]) {
+    if lhs.len() < rhs.len() {
+        lhs.resize(rhs.len(), 0);
+    }
+    let mut iter = rhs.iter();
+    let mut carry = false;
+    for next in lhs.iter_mut() {
+        let rhs = iter.next().copied().unwrap_or_default();
+        (*next, carry) = next.carrying_add(rhs, carry);
+        if !carry && iter.len() == 0 {
+            break;
+        }
+    }
+    if carry {
+        lhs.push(1);
+    }
+}
+
+/// Subtracts `rhs` from `lhs` in place, which requires `lhs >= rhs`.
+fn sub_magnitude(lhs: &mut [u8], rhs: &[u8]) {
+    let mut iter = rhs.iter();
+    let mut borrow = false;
+    for next in lhs.iter_mut() {
+        let rhs = iter.next().copied().unwrap_or_default();
+        (*next, borrow) = next.borrowing_sub(rhs, borrow);
+        if !borrow && iter.len() == 0 {
+            break;
+        }
+    }
+    debug_assert!(!borrow && iter.all(|&v| v == 0));
+}
+
+/// Schoolbook multiplication, each partial product fits in an `u16`.
+fn mul_magnitude(lhs: &[u8], rhs: &[u8]) -> Vec<u8> {
+    let (lhs, rhs) = (trim(lhs), trim(rhs));
+    if lhs.is_empty() || rhs.is_empty() {
+        return Vec::new();
+    }
+    let mut base = vec![0u8; lhs.len() + rhs.len()];
+    for (i, &lhs) in lhs.iter().enumerate() {
+        let mut carry = 0u16;
+        for (j, &rhs) in rhs.iter().enumerate() {
+            let value = lhs as u16 * rhs as u16 + base[i + j] as u16 + carry;
+            base[i + j] = value as u8;
+            carry = value >> 8;
+        }
+        base[i + rhs.len()] = carry as u8;
+    }
+    let len = trim(&base).len();
+    base.truncate(len);
+    base
+}
+
+/// Knuth's Algorithm D (TAOCP Vol. 2, 4.3.1) over base 256 digits.
+fn div_rem_magnitude(lhs: &[u8], rhs: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let (lhs, rhs) = (trim(lhs), trim(rhs));
+    assert!(!rhs.is_empty(), "attempt to divide by zero");
+    if cmp_magnitude(lhs, rhs) == Ordering::Less {
+        return (Vec::new(), lhs.to_vec());
+    }
+    if let [rhs] = rhs {
+        let rhs = *rhs as u16;
+        let mut rem = 0u16;
+        let mut quo = vec![0u8; lhs.len()];
+        for (quo, &lhs) in quo.iter_mut().zip(lhs).rev() {
+            let value = rem << 8 | lhs as u16;
+            *quo = (value / rhs) as u8;
+            rem = value % rhs;
+        }
+        let len = trim(&quo).len();
+        quo.truncate(len);
+        return (quo, if rem > 0 { vec![rem as u8] } else { Vec::new() });
+    }
+    let shift = rhs[rhs.len() - 1].leading_zeros() as usize;
+    let rhs = shl_magnitude(rhs, shift);
+    let mut lhs = {
+        let len = lhs.len() + 1;
+        let mut lhs = shl_magnitude(lhs, shift);
+        lhs.resize(len, 0);
+        lhs
+    };
+    let n = rhs.len();
+    let m = lhs.len() - n - 1;
+    let (top, next) = (rhs[n - 1] as u32, rhs[n - 2] as u32);
+    let mut quo = vec![0u8; m + 1];
+    for j in (0..=m).rev() {
+        let value = (lhs[j + n] as u32) << 8 | lhs[j + n - 1] as u32;
+        let (mut qhat, mut rhat) = (value / top, value % top);
+        while qhat > 0xff || qhat * next > (rhat << 8 | lhs[j + n - 2] as u32) {
+            qhat -= 1;
+            rhat += top;
+            if rhat > 0xff {
+                break;
+            }
+        }
+        let (mut borrow, mut carry) = (0i32, 0u32);
+        for i in 0..n {
+            let product = qhat * rhs[i] as u32 + carry;
+            carry = product >> 8;
+            let value = lhs[i + j] as i32 - borrow - (product & 0xff) as i32;
+            lhs[i + j] = value as u8;
+            borrow = (value < 0).into();
+        }
+        let value = lhs[j + n] as i32 - borrow - carry as i32;
+        lhs[j + n] = value as u8;
+        if value < 0 {
+            qhat -= 1;
+            let mut carry = false;
+            for i in 0..n {
+                (lhs[i + j], carry) = lhs[i + j].carrying_add(rhs[i], carry);
+            }
+            lhs[j + n] = lhs[j + n].wrapping_add(carry.into());
+        }
+        quo[j] = qhat as u8;
+    }
+    let len = trim(&quo).len();
+    quo.truncate(len);
+    let mut rem = shr_magnitude(&lhs[..n], shift);
+    let len = trim(&rem).len();
+    rem.truncate(len);
+    (quo, rem)
+}
+
+fn shl_magnitude(base: &[u8], bits: usize) -> Vec<u8> {
+    let (bytes, bits) = (bits >> 3, bits % 8);
+    let mut result = vec![0u8; bytes];
+    if bits == 0 {
+        result.extend_from_slice(base);
+    } else {
+        let mut carry = 0u8;
+        for &value in base {
+            result.push(value << bits | carry);
+            carry = value >> (8 - bits);
+        }
+        if carry > 0 {
+            result.push(carry);
+        }
+    }
+    result
+}
+
+fn shr_magnitude(base: &[u8], bits: usize) -> Vec<u8> {
+    let (bytes, bits) = (bits >> 3, bits % 8);
+    let base = base.get(bytes..).unwrap_or_default();
+    if bits == 0 {
+        return base.to_vec();
+    }
+    let mut result = Vec::with_capacity(base.len());
+    for (idx, &value) in base.iter().enumerate() {
+        let next = base.get(idx + 1).copied().unwrap_or_default();
+        result.push(value >> bits | next << (8 - bits));
+    }
+    result
 }
 
 impl Add for BigInteger {
@@ -37,35 +461,13 @@ impl Add for BigInteger {
 }
 
 impl AddAssign for BigInteger {
-    fn add_assign(&mut self, mut rhs: Self) {
-        if rhs.sign == self.sign {
-            let Self { base, .. } = rhs;
-            let mut rhs = base.into_iter();
-            let mut this = self.iter_mut();
-            if {
-                let mut carry = false;
-                while let Some(next) = this.next() {
-                    let rhs = rhs.next().unwrap_or_default();
-                    (*next, carry) = next.carrying_add(rhs, carry);
-                }
-                let mut value;
-                while let Some(next) = rhs.next() {
-                    (value, carry) = next.overflowing_add(carry.into());
-                    self.push(value);
-                }
-                carry
-            } {
-                self.push(1);
-            }
-        } else {
-            rhs.sign = self.sign;
-            self.sub_assign(rhs);
-        }
+    fn add_assign(&mut self, rhs: Self) {
+        self.add_signed(&rhs.base, rhs.sign);
     }
 }
 
 impl Debug for BigInteger {
-    fn fmt(&self, f: &mut Formatter) -> Result {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         let sign = if self.sign { '+' } else { '-' };
         let mut iter = self.iter().rev();
         write!(f, "{sign}{:#04x}", iter.next().unwrap_or(&0))?;
@@ -74,6 +476,15 @@ impl Debug for BigInteger {
     }
 }
 
+impl Default for BigInteger {
+    fn default() -> Self {
+        Self {
+            base: Vec::new(),
+            sign: true,
+        }
+    }
+}
+
 impl Deref for BigInteger {
     type Target = Vec<u8>;
 
@@ -88,31 +499,37 @@ impl DerefMut for BigInteger {
     }
 }
 
+impl Display for BigInteger {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.pad_integral(!self.is_negative(), "", &to_radix(&self.base, 10))
+    }
+}
+
 impl Div for BigInteger {
     type Output = Self;
 
-    fn div(self, _rhs: Self) -> Self::Output {
-        todo!()
+    fn div(self, rhs: Self) -> Self::Output {
+        self.div_rem(&rhs).0
     }
 }
 
 impl DivAssign for BigInteger {
-    fn div_assign(&mut self, _rhs: Self) {
-        todo!()
+    fn div_assign(&mut self, rhs: Self) {
+        *self = self.div_rem(&rhs).0;
     }
 }
 
 impl From<i8> for BigInteger {
     fn from(value: i8) -> Self {
         let base = vec![value.unsigned_abs()];
-        let sign = value.is_positive();
-        Self { base, sign }
+        let sign = !value.is_negative();
+        Self::from_parts(base, sign)
     }
 }
 
 impl From<i16> for BigInteger {
     fn from(value: i16) -> Self {
-        let sign = value.is_positive();
+        let sign = !value.is_negative();
         let mut base = Vec::new();
         let mut value = value.unsigned_abs();
         while value > 0 {
@@ -125,7 +542,7 @@ impl From<i16> for BigInteger {
 
 impl From<i32> for BigInteger {
     fn from(value: i32) -> Self {
-        let sign = value.is_positive();
+        let sign = !value.is_negative();
         let mut base = Vec::new();
         let mut value = value.unsigned_abs();
         while value > 0 {
@@ -138,7 +555,7 @@ impl From<i32> for BigInteger {
 
 impl From<i64> for BigInteger {
     fn from(value: i64) -> Self {
-        let sign = value.is_positive();
+        let sign = !value.is_negative();
         let mut base = Vec::new();
         let mut value = value.unsigned_abs();
         while value > 0 {
@@ -151,7 +568,7 @@ impl From<i64> for BigInteger {
 
 impl From<i128> for BigInteger {
     fn from(value: i128) -> Self {
-        let sign = value.is_positive();
+        let sign = !value.is_negative();
         let mut base = Vec::new();
         let mut value = value.unsigned_abs();
         while value > 0 {
@@ -164,7 +581,7 @@ impl From<i128> for BigInteger {
 
 impl From<isize> for BigInteger {
     fn from(value: isize) -> Self {
-        let sign = value.is_positive();
+        let sign = !value.is_negative();
         let mut base = Vec::new();
         let mut value = value.unsigned_abs();
         while value > 0 {
@@ -177,10 +594,7 @@ impl From<isize> for BigInteger {
 
 impl From<u8> for BigInteger {
     fn from(value: u8) -> Self {
-        Self {
-            base: vec![value],
-            sign: true,
-        }
+        Self::from_parts(vec![value], true)
     }
 }
 
@@ -242,14 +656,15 @@ impl From<usize> for BigInteger {
 impl Mul for BigInteger {
     type Output = Self;
 
-    fn mul(self, _rhs: Self) -> Self::Output {
-        todo!()
+    fn mul(mut self, rhs: Self) -> Self::Output {
+        self.mul_assign(rhs);
+        self
     }
 }
 
 impl MulAssign for BigInteger {
-    fn mul_assign(&mut self, _rhs: Self) {
-        todo!()
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = self.mul_signed(&rhs.base, rhs.sign);
     }
 }
 
@@ -274,20 +689,21 @@ impl Ord for BigInteger {
 
 impl PartialOrd for BigInteger {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        match self.sign.partial_cmp(&other.sign) {
-            Some(Ordering::Equal) => {
-                let (lhs, rhs) = if self.sign {
-                    (self, other)
-                } else {
-                    (other, self)
-                };
-                match lhs.len().partial_cmp(&rhs.len()) {
-                    Some(Ordering::Equal) => lhs.iter().rev().partial_cmp(rhs.iter().rev()),
-                    ord => ord,
-                }
-            }
-            ord => ord,
-        }
+        Some(self.cmp(other))
+    }
+}
+
+impl Rem for BigInteger {
+    type Output = Self;
+
+    fn rem(self, rhs: Self) -> Self::Output {
+        self.div_rem(&rhs).1
+    }
+}
+
+impl RemAssign for BigInteger {
+    fn rem_assign(&mut self, rhs: Self) {
+        *self = self.div_rem(&rhs).1;
     }
 }
 
@@ -301,34 +717,86 @@ impl Sub for BigInteger {
 }
 
 impl SubAssign for BigInteger {
-    fn sub_assign(&mut self, mut rhs: Self) {
-        if rhs.sign == self.sign {
-            let Self { base, .. } = rhs;
-            let mut rhs = base.into_iter();
-            let mut this = self.iter_mut();
-            if {
-                let mut noborrow = true;
-                while let Some(next) = this.next() {
-                    let rhs = !rhs.next().unwrap_or_default();
-                    (*next, noborrow) = next.carrying_add(rhs, noborrow);
-                }
-                while let Some(0) = self.last() {
-                    self.pop();
+    fn sub_assign(&mut self, rhs: Self) {
+        self.add_signed(&rhs.base, !rhs.sign);
+    }
+}
+
+impl TryFrom<f32> for BigInteger {
+    type Error = ConversionError;
+
+    /// Truncates the fractional part, same as an `as` cast but fallible.
+    fn try_from(value: f32) -> Result<Self, Self::Error> {
+        Self::try_from(value as f64)
+    }
+}
+
+impl TryFrom<f64> for BigInteger {
+    type Error = ConversionError;
+
+    /// Truncates the fractional part, same as an `as` cast but fallible.
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        if value.is_nan() {
+            return Err(ConversionError::NaN);
+        } else if value.is_infinite() {
+            return Err(ConversionError::Infinite);
+        }
+        let bits = value.to_bits();
+        let exp = (bits >> 52 & 0x7ff) as usize;
+        let mantissa = match exp {
+            0 => bits << 1 & ((1 << 53) - 1),
+            _ => bits & ((1 << 52) - 1) | 1 << 52,
+        };
+        let base = mantissa.to_le_bytes();
+        let base = match exp.checked_sub(1075) {
+            Some(shift) => shl_magnitude(&base, shift),
+            None => shr_magnitude(&base, 1075 - exp),
+        };
+        Ok(Self::from_parts(base, value.is_sign_positive()))
+    }
+}
+
+macro_rules! try_from_big_integer {
+    ($($unsigned:ty, $signed:ty);* $(;)?) => {$(
+        impl TryFrom<&BigInteger> for $unsigned {
+            type Error = ConversionError;
+
+            fn try_from(value: &BigInteger) -> Result<Self, Self::Error> {
+                if value.is_negative() {
+                    return Err(ConversionError::NegOverflow);
                 }
-                let mut value;
-                while let Some(b) = rhs.next() {
-                    (value, noborrow) = (!b).overflowing_add(noborrow.into());
-                    self.push(value);
+                to_u128(&value.base)
+                    .and_then(|v| v.try_into().ok())
+                    .ok_or(ConversionError::PosOverflow)
+            }
+        }
+
+        impl TryFrom<&BigInteger> for $signed {
+            type Error = ConversionError;
+
+            fn try_from(value: &BigInteger) -> Result<Self, Self::Error> {
+                if value.is_negative() {
+                    to_u128(&value.base)
+                        .filter(|&v| v <= <$signed>::MIN.unsigned_abs() as u128)
+                        .map(|v| (v as $signed).wrapping_neg())
+                        .ok_or(ConversionError::NegOverflow)
+                } else {
+                    to_u128(&value.base)
+                        .and_then(|v| v.try_into().ok())
+                        .ok_or(ConversionError::PosOverflow)
                 }
-                !noborrow
-            } {
-                self.sign = !self.sign;
             }
-        } else {
-            rhs.sign = self.sign;
-            self.add_assign(rhs);
         }
-    }
+    )*};
+}
+
+try_from_big_integer! {
+    u8, i8;
+    u16, i16;
+    u32, i32;
+    u64, i64;
+    u128, i128;
+    usize, isize;
 }
 
 #[test]
@@ -346,3 +814,29 @@ fn test_sub() {
         BigInteger::from(i32::MIN as i64 - u32::MAX as i64)
     );
 }
+
+#[test]
+fn test_mul() {
+    assert_eq!(
+        Mul::mul(BigInteger::from(i64::MIN), BigInteger::from(u64::MAX)),
+        BigInteger::from(i64::MIN as i128 * u64::MAX as i128)
+    );
+}
+
+#[test]
+fn test_div() {
+    assert_eq!(
+        Div::div(
+            BigInteger::from(i128::MIN),
+            BigInteger::from(0x1234_5678_9abc_u64)
+        ),
+        BigInteger::from(i128::MIN / 0x1234_5678_9abc_i128)
+    );
+    assert_eq!(
+        Rem::rem(
+            BigInteger::from(i64::MIN),
+            BigInteger::from(0x1234_5678_u32)
+        ),
+        BigInteger::from(i64::MIN % 0x1234_5678_i64)
+    );
+}
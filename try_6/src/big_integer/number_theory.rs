@@ -0,0 +1,296 @@
+use super::{
+    cmp_magnitude, div_rem_magnitude, mul_magnitude, shl_magnitude, shr_magnitude, sub_magnitude,
+    trailing_zeros, trim, BigInteger,
+};
+use std::{
+    cmp::Ordering,
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    mem,
+};
+
+/// Small primes for trial division before the Miller-Rabin test, the first
+/// twelve of them are also the deterministic witnesses for `n < 2^64`.
+const SMALL_PRIMES: [u8; 18] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61,
+];
+
+impl BigInteger {
+    /// Raises `self` to the power of `exp`, using exponentiation by squaring.
+    pub fn pow(&self, mut exp: u32) -> Self {
+        let sign = self.sign || exp & 1 == 0;
+        let mut base = trim(&self.base).to_vec();
+        let mut result = vec![1];
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = mul_magnitude(&result, &base);
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = mul_magnitude(&base, &base);
+            }
+        }
+        Self::from_parts(result, sign)
+    }
+
+    /// Modular exponentiation `self ^ exp (mod modulus)`.
+    ///
+    /// The result is always in `[0, |modulus|)`. For an odd modulus all the
+    /// products are computed in the Montgomery form, which replaces the long
+    /// divisions by the cheaper Montgomery reduction. Panics if `exp` is
+    /// negative or `modulus` is zero.
+    pub fn modpow(&self, exp: &Self, modulus: &Self) -> Self {
+        assert!(!exp.is_negative(), "exponent must be non-negative");
+        assert!(!modulus.is_zero(), "modulus must be non-zero");
+        let modulus = trim(&modulus.base);
+        let base = self.rem_euclid(&Self::from_parts(modulus.to_vec(), true));
+        if modulus == [1] {
+            return Self::default();
+        }
+        let exp = trim(&exp.base);
+        let bits = exp.len() << 3;
+        let bit = |i: usize| exp[i >> 3] >> (i % 8) & 1 == 1;
+        let result = if modulus[0] & 1 == 1 {
+            let montgomery = Montgomery::new(modulus);
+            let base = montgomery.convert(&base.base);
+            let mut result = montgomery.convert(&[1]);
+            for i in (0..bits).rev() {
+                result = montgomery.mul(&result, &result);
+                if bit(i) {
+                    result = montgomery.mul(&result, &base);
+                }
+            }
+            montgomery.reduce(&result)
+        } else {
+            let mut result = vec![1];
+            for i in (0..bits).rev() {
+                result = div_rem_magnitude(&mul_magnitude(&result, &result), modulus).1;
+                if bit(i) {
+                    result = div_rem_magnitude(&mul_magnitude(&result, &base.base), modulus).1;
+                }
+            }
+            result
+        };
+        Self::from_parts(result, true)
+    }
+
+    /// Greatest common divisor by the binary GCD (Stein's) algorithm.
+    ///
+    /// Only shifts and subtractions are involved, and the result is always
+    /// non-negative, `gcd(0, 0)` is zero.
+    pub fn gcd(&self, other: &Self) -> Self {
+        let (mut lhs, mut rhs) = (trim(&self.base).to_vec(), trim(&other.base).to_vec());
+        if lhs.is_empty() || rhs.is_empty() {
+            lhs.append(&mut rhs);
+            return Self::from_parts(lhs, true);
+        }
+        let (zeros1, zeros2) = (trailing_zeros(&lhs), trailing_zeros(&rhs));
+        lhs = shr_magnitude(&lhs, zeros1);
+        rhs = shr_magnitude(&rhs, zeros2);
+        loop {
+            match cmp_magnitude(&lhs, &rhs) {
+                Ordering::Equal => break,
+                Ordering::Less => mem::swap(&mut lhs, &mut rhs),
+                Ordering::Greater => (),
+            }
+            sub_magnitude(&mut lhs, &rhs);
+            lhs = shr_magnitude(&lhs, trailing_zeros(&lhs));
+        }
+        Self::from_parts(shl_magnitude(&lhs, zeros1.min(zeros2)), true)
+    }
+
+    /// Extended Euclidean algorithm.
+    ///
+    /// Returns `(g, x, y)` such that `self * x + other * y == g`, where `g`
+    /// is the non-negative greatest common divisor.
+    pub fn extended_gcd(&self, other: &Self) -> (Self, Self, Self) {
+        let (mut old_r, mut r) = (self.clone(), other.clone());
+        let (mut old_s, mut s) = (Self::from(1u8), Self::default());
+        let (mut old_t, mut t) = (Self::default(), Self::from(1u8));
+        while !r.is_zero() {
+            let (quo, rem) = old_r.div_rem(&r);
+            old_r = mem::replace(&mut r, rem);
+            let next = old_s - &quo * &s;
+            old_s = mem::replace(&mut s, next);
+            let next = old_t - &quo * &t;
+            old_t = mem::replace(&mut t, next);
+        }
+        if old_r.is_negative() {
+            for value in [&mut old_r, &mut old_s, &mut old_t] {
+                value.sign = !value.sign;
+                value.normalize();
+            }
+        }
+        (old_r, old_s, old_t)
+    }
+
+    /// The inverse of `self (mod modulus)` in `[0, |modulus|)`, or `None`
+    /// if `self` and `modulus` are not coprime.
+    pub fn mod_inverse(&self, modulus: &Self) -> Option<Self> {
+        let modulus = modulus.abs();
+        let (gcd, x, _) = self.rem_euclid(&modulus).extended_gcd(&modulus);
+        (gcd == 1u8).then(|| x.rem_euclid(&modulus))
+    }
+
+    /// Integer square root, the largest `r` such that `r * r <= self`.
+    ///
+    /// Panics if `self` is negative.
+    pub fn sqrt(&self) -> Self {
+        assert!(!self.is_negative(), "square root of negative number");
+        self.nth_root(2)
+    }
+
+    /// Integer `n`-th root by the Newton's method, truncated towards zero.
+    ///
+    /// For a negative `self` the odd roots are negative, and the even roots
+    /// panic, so does `n == 0`.
+    pub fn nth_root(&self, n: u32) -> Self {
+        assert!(n > 0, "root degree must be positive");
+        assert!(
+            n & 1 == 1 || !self.is_negative(),
+            "even root of negative number"
+        );
+        let value = trim(&self.base);
+        if value.is_empty() || n == 1 {
+            return self.clone();
+        }
+        let bits = self.bit_length().div_ceil(n as usize);
+        let (degree, prev) = (Self::from(n), Self::from(n - 1));
+        let value = Self::from_parts(value.to_vec(), true);
+        let mut root = Self::from_parts(shl_magnitude(&[1], bits), true);
+        loop {
+            let power = root.pow(n - 1);
+            let next = (&prev * &root + value.div_rem(&power).0).div_rem(&degree).0;
+            if next >= root {
+                break;
+            }
+            root = next;
+        }
+        root.sign = self.sign;
+        root.normalize();
+        root
+    }
+
+    /// Miller-Rabin probabilistic primality test.
+    ///
+    /// The test is deterministic for `self < 2^64` with the first twelve
+    /// primes as witnesses, otherwise `rounds` random witnesses are drawn
+    /// and a composite passes with a probability at most `4^-rounds`.
+    pub fn is_probable_prime(&self, rounds: usize) -> bool {
+        if self.is_negative() {
+            return false;
+        }
+        let value = trim(&self.base);
+        for prime in SMALL_PRIMES {
+            match div_rem_magnitude(value, &[prime]) {
+                (quo, _) if quo.is_empty() => return value == [prime],
+                (_, rem) if rem.is_empty() => return value == [prime],
+                _ => (),
+            }
+        }
+        let prev = self - 1u8;
+        let zeros = trailing_zeros(&prev.base);
+        let odd = Self::from_parts(shr_magnitude(&prev.base, zeros), true);
+        let witness = |base: &Self| {
+            let mut x = base.modpow(&odd, self);
+            if x == 1u8 || x == prev {
+                return true;
+            }
+            for _ in 1..zeros {
+                x = (&x * &x).rem_euclid(self);
+                if x == prev {
+                    return true;
+                } else if x == 1u8 {
+                    return false;
+                }
+            }
+            false
+        };
+        if self.bit_length() <= 64 {
+            SMALL_PRIMES[..12]
+                .iter()
+                .all(|&prime| witness(&Self::from(prime)))
+        } else {
+            let range = &prev - 2u8;
+            let mut random = RandomState::new().build_hasher();
+            (0..rounds).all(|_| {
+                let base = (0..value.len())
+                    .map(|_| {
+                        random.write_u8(0);
+                        random.finish() as u8
+                    })
+                    .collect();
+                witness(&(Self::from_parts(base, true).rem_euclid(&range) + 2u8))
+            })
+        }
+    }
+}
+
+/// Montgomery multiplication context for an odd modulus `m` of `n` bytes,
+/// where `R = 256^n` and `inv = -m^-1 (mod 256)`.
+struct Montgomery<'a> {
+    modulus: &'a [u8],
+    inv: u8,
+}
+
+impl<'a> Montgomery<'a> {
+    fn new(modulus: &'a [u8]) -> Self {
+        let mut inv = modulus[0];
+        for _ in 0..3 {
+            inv = inv.wrapping_mul(2u8.wrapping_sub(modulus[0].wrapping_mul(inv)));
+        }
+        Self {
+            modulus,
+            inv: inv.wrapping_neg(),
+        }
+    }
+
+    /// Converts `value` (less than `m`) into the Montgomery form `value * R`.
+    fn convert(&self, value: &[u8]) -> Vec<u8> {
+        let value = shl_magnitude(value, self.modulus.len() << 3);
+        let mut result = div_rem_magnitude(&value, self.modulus).1;
+        result.resize(self.modulus.len(), 0);
+        result
+    }
+
+    /// Converts back from the Montgomery form, `value * R^-1 (mod m)`.
+    fn reduce(&self, value: &[u8]) -> Vec<u8> {
+        self.mul(value, &[1])
+    }
+
+    /// Computes `lhs * rhs * R^-1 (mod m)` by the interleaved (CIOS) method.
+    fn mul(&self, lhs: &[u8], rhs: &[u8]) -> Vec<u8> {
+        let (modulus, n) = (self.modulus, self.modulus.len());
+        let digit = |value: &[u8], idx: usize| value.get(idx).copied().unwrap_or_default() as u16;
+        let mut result = vec![0u8; n + 2];
+        for i in 0..n {
+            let lhs = digit(lhs, i);
+            let mut carry = 0u16;
+            for (j, next) in result[..n].iter_mut().enumerate() {
+                let value = *next as u16 + lhs * digit(rhs, j) + carry;
+                *next = value as u8;
+                carry = value >> 8;
+            }
+            let value = result[n] as u16 + carry;
+            result[n] = value as u8;
+            result[n + 1] = (value >> 8) as u8;
+            let factor = result[0].wrapping_mul(self.inv) as u16;
+            let mut carry = (result[0] as u16 + factor * modulus[0] as u16) >> 8;
+            for j in 1..n {
+                let value = result[j] as u16 + factor * modulus[j] as u16 + carry;
+                result[j - 1] = value as u8;
+                carry = value >> 8;
+            }
+            let value = result[n] as u16 + carry;
+            result[n - 1] = value as u8;
+            result[n] = result[n + 1] + (value >> 8) as u8;
+            result[n + 1] = 0;
+        }
+        result.truncate(n + 1);
+        if cmp_magnitude(&result, modulus) != Ordering::Less {
+            sub_magnitude(&mut result, modulus);
+        }
+        result.truncate(n);
+        result
+    }
+}
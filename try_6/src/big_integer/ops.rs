@@ -0,0 +1,239 @@
+use super::BigInteger;
+use core::{
+    cmp::Ordering,
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign},
+};
+
+macro_rules! forward_binop {
+    ($($Op:ident::$op:ident, $OpAssign:ident::$op_assign:ident => |$this:ident, $rhs:ident, $sign:ident|
+        $assign:expr, $binary:expr;)*) => {$(
+        impl $Op<&BigInteger> for BigInteger {
+            type Output = BigInteger;
+
+            fn $op(mut self, rhs: &BigInteger) -> Self::Output {
+                let ($this, $rhs, $sign) = (&mut self, &rhs.base[..], rhs.sign);
+                $assign;
+                self
+            }
+        }
+
+        impl $Op<BigInteger> for &BigInteger {
+            type Output = BigInteger;
+
+            fn $op(self, rhs: BigInteger) -> Self::Output {
+                let ($this, $rhs, $sign) = (self, &rhs.base[..], rhs.sign);
+                $binary
+            }
+        }
+
+        impl $Op<&BigInteger> for &BigInteger {
+            type Output = BigInteger;
+
+            fn $op(self, rhs: &BigInteger) -> Self::Output {
+                let ($this, $rhs, $sign) = (self, &rhs.base[..], rhs.sign);
+                $binary
+            }
+        }
+
+        impl $OpAssign<&BigInteger> for BigInteger {
+            fn $op_assign(&mut self, rhs: &BigInteger) {
+                let ($this, $rhs, $sign) = (self, &rhs.base[..], rhs.sign);
+                $assign;
+            }
+        }
+
+        forward_binop!(@primitive $Op::$op, $OpAssign::$op_assign => |$this, $rhs, $sign| $assign, $binary;
+            i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+    )*};
+    (@primitive $Op:ident::$op:ident, $OpAssign:ident::$op_assign:ident => |$this:ident, $rhs:ident, $sign:ident|
+        $assign:expr, $binary:expr; $($primitive:ty),*) => {$(
+        impl $Op<$primitive> for BigInteger {
+            type Output = BigInteger;
+
+            fn $op(mut self, rhs: $primitive) -> Self::Output {
+                let (base, sign) = rhs.to_parts();
+                let ($this, $rhs, $sign) = (&mut self, &base[..], sign);
+                $assign;
+                self
+            }
+        }
+
+        impl $Op<$primitive> for &BigInteger {
+            type Output = BigInteger;
+
+            fn $op(self, rhs: $primitive) -> Self::Output {
+                let (base, sign) = rhs.to_parts();
+                let ($this, $rhs, $sign) = (self, &base[..], sign);
+                $binary
+            }
+        }
+
+        impl $OpAssign<$primitive> for BigInteger {
+            fn $op_assign(&mut self, rhs: $primitive) {
+                let (base, sign) = rhs.to_parts();
+                let ($this, $rhs, $sign) = (self, &base[..], sign);
+                $assign;
+            }
+        }
+
+        impl $Op<BigInteger> for $primitive {
+            type Output = BigInteger;
+
+            fn $op(self, rhs: BigInteger) -> Self::Output {
+                let mut lhs = BigInteger::from(self);
+                let ($this, $rhs, $sign) = (&mut lhs, &rhs.base[..], rhs.sign);
+                $assign;
+                lhs
+            }
+        }
+
+        impl $Op<&BigInteger> for $primitive {
+            type Output = BigInteger;
+
+            fn $op(self, rhs: &BigInteger) -> Self::Output {
+                let mut lhs = BigInteger::from(self);
+                let ($this, $rhs, $sign) = (&mut lhs, &rhs.base[..], rhs.sign);
+                $assign;
+                lhs
+            }
+        }
+    )*};
+}
+
+forward_binop! {
+    Add::add, AddAssign::add_assign => |this, rhs, sign|
+        this.add_signed(rhs, sign),
+        {
+            let mut sum = this.clone();
+            sum.add_signed(rhs, sign);
+            sum
+        };
+    Sub::sub, SubAssign::sub_assign => |this, rhs, sign|
+        this.add_signed(rhs, !sign),
+        {
+            let mut difference = this.clone();
+            difference.add_signed(rhs, !sign);
+            difference
+        };
+    Mul::mul, MulAssign::mul_assign => |this, rhs, sign|
+        *this = this.mul_signed(rhs, sign),
+        this.mul_signed(rhs, sign);
+    Div::div, DivAssign::div_assign => |this, rhs, sign|
+        *this = this.div_rem_signed(rhs, sign).0,
+        this.div_rem_signed(rhs, sign).0;
+    Rem::rem, RemAssign::rem_assign => |this, rhs, sign|
+        *this = this.div_rem_signed(rhs, sign).1,
+        this.div_rem_signed(rhs, sign).1;
+}
+
+/// Splits a primitive integer into its little endian magnitude bytes and
+/// sign, which is exactly the layout of a [`BigInteger`], so the primitive
+/// operands never have to be converted into a temporary big integer.
+trait ToParts {
+    type Bytes: AsRef<[u8]>;
+
+    fn to_parts(self) -> (Self::Bytes, bool);
+}
+
+macro_rules! to_parts {
+    (signed: $($signed:ty),*; unsigned: $($unsigned:ty),*) => {
+        $(
+            impl ToParts for $signed {
+                type Bytes = [u8; core::mem::size_of::<$signed>()];
+
+                fn to_parts(self) -> (Self::Bytes, bool) {
+                    (self.unsigned_abs().to_le_bytes(), !self.is_negative())
+                }
+            }
+        )*
+        $(
+            impl ToParts for $unsigned {
+                type Bytes = [u8; core::mem::size_of::<$unsigned>()];
+
+                fn to_parts(self) -> (Self::Bytes, bool) {
+                    (self.to_le_bytes(), true)
+                }
+            }
+        )*
+    };
+}
+
+to_parts! {
+    signed: i8, i16, i32, i64, i128, isize;
+    unsigned: u8, u16, u32, u64, u128, usize
+}
+
+macro_rules! primitive_cmp {
+    ($($primitive:ty),*) => {$(
+        impl PartialEq<$primitive> for BigInteger {
+            fn eq(&self, other: &$primitive) -> bool {
+                let (base, sign) = other.to_parts();
+                self.cmp_signed(&base, sign) == Ordering::Equal
+            }
+        }
+
+        impl PartialEq<BigInteger> for $primitive {
+            fn eq(&self, other: &BigInteger) -> bool {
+                other.eq(self)
+            }
+        }
+
+        impl PartialOrd<$primitive> for BigInteger {
+            fn partial_cmp(&self, other: &$primitive) -> Option<Ordering> {
+                let (base, sign) = other.to_parts();
+                Some(self.cmp_signed(&base, sign))
+            }
+        }
+
+        impl PartialOrd<BigInteger> for $primitive {
+            fn partial_cmp(&self, other: &BigInteger) -> Option<Ordering> {
+                other.partial_cmp(self).map(Ordering::reverse)
+            }
+        }
+    )*};
+}
+
+primitive_cmp!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl Neg for BigInteger {
+    type Output = BigInteger;
+
+    fn neg(mut self) -> Self::Output {
+        self.sign = !self.sign;
+        self.normalize();
+        self
+    }
+}
+
+impl Neg for &BigInteger {
+    type Output = BigInteger;
+
+    fn neg(self) -> Self::Output {
+        -self.clone()
+    }
+}
+
+impl Product for BigInteger {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(BigInteger::from(1u8), |product, value| product * value)
+    }
+}
+
+impl<'a> Product<&'a BigInteger> for BigInteger {
+    fn product<I: Iterator<Item = &'a BigInteger>>(iter: I) -> Self {
+        iter.fold(BigInteger::from(1u8), |product, value| product * value)
+    }
+}
+
+impl Sum for BigInteger {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(BigInteger::default(), |sum, value| sum + value)
+    }
+}
+
+impl<'a> Sum<&'a BigInteger> for BigInteger {
+    fn sum<I: Iterator<Item = &'a BigInteger>>(iter: I) -> Self {
+        iter.fold(BigInteger::default(), |sum, value| sum + value)
+    }
+}
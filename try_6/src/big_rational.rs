@@ -0,0 +1,355 @@
+use crate::big_integer::{BigInteger, ConversionError};
+use core::{
+    cmp::Ordering,
+    fmt::{self, Debug, Display, Formatter},
+    mem,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign},
+};
+
+/// An arbitrary precision rational number.
+///
+/// The fraction is always kept in the lowest terms, and the denominator is
+/// always positive, so the sign lives on the numerator only. As a result,
+/// two equal rationals are also structurally equal.
+#[derive(Clone, Hash, PartialEq, Eq)]
+pub struct BigRational {
+    numer: BigInteger,
+    denom: BigInteger,
+}
+
+impl BigRational {
+    /// Creates the rational `numer / denom` in the lowest terms.
+    ///
+    /// Panics if `denom` is zero.
+    pub fn new(numer: BigInteger, denom: BigInteger) -> Self {
+        assert!(!denom.is_zero(), "denominator must be non-zero");
+        let mut this = Self { numer, denom };
+        this.reduce();
+        this
+    }
+
+    pub fn numer(&self) -> &BigInteger {
+        &self.numer
+    }
+
+    pub fn denom(&self) -> &BigInteger {
+        &self.denom
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numer.is_zero()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.numer.is_negative()
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denom == 1u8
+    }
+
+    pub fn abs(&self) -> Self {
+        Self {
+            numer: self.numer.abs(),
+            denom: self.denom.clone(),
+        }
+    }
+
+    /// Returns `1 / self`, panics if `self` is zero.
+    pub fn recip(&self) -> Self {
+        Self::new(self.denom.clone(), self.numer.clone())
+    }
+
+    /// The largest integer less than or equal to `self`.
+    pub fn floor(&self) -> BigInteger {
+        let (quo, rem) = self.numer.div_rem(&self.denom);
+        if rem.is_negative() {
+            quo - 1u8
+        } else {
+            quo
+        }
+    }
+
+    /// The smallest integer greater than or equal to `self`.
+    pub fn ceil(&self) -> BigInteger {
+        -(-self).floor()
+    }
+
+    /// The simple continued fraction `[a0; a1, a2, ...]` of `self`.
+    ///
+    /// Every rational has a finite expansion, where `a0` is the floor of
+    /// `self` and all the following terms are positive. The last term is
+    /// never `1` unless the expansion is `[1]`, so the expansion is unique.
+    pub fn continued_fraction(&self) -> Vec<BigInteger> {
+        let (mut numer, mut denom) = (self.numer.clone(), self.denom.clone());
+        let mut terms = Vec::new();
+        while !denom.is_zero() {
+            let term = Self::new(numer.clone(), denom.clone()).floor();
+            let rem = numer - &term * &denom;
+            numer = mem::replace(&mut denom, rem);
+            terms.push(term);
+        }
+        terms
+    }
+
+    /// Evaluates the continued fraction `[a0; a1, a2, ...]`.
+    ///
+    /// Panics if `terms` is empty or evaluates to a division by zero.
+    pub fn from_continued_fraction(terms: &[BigInteger]) -> Self {
+        let (last, terms) = terms.split_last().expect("empty continued fraction");
+        let (mut numer, mut denom) = (last.clone(), BigInteger::from(1u8));
+        for term in terms.iter().rev() {
+            let next = term * &numer + &denom;
+            denom = mem::replace(&mut numer, next);
+        }
+        Self::new(numer, denom)
+    }
+
+    /// The closest rational to `self` with a denominator not greater than
+    /// `max_denom`, ties are broken towards the smaller denominator.
+    ///
+    /// The candidates are the last convergent of the continued fraction
+    /// within the bound and the largest semiconvergent after it, one of
+    /// them is always the best approximation. Panics if `max_denom < 1`.
+    pub fn limit_denominator(&self, max_denom: &BigInteger) -> Self {
+        assert!(*max_denom >= 1u8, "max denominator must be positive");
+        if self.denom <= *max_denom {
+            return self.clone();
+        }
+        let (mut p0, mut q0) = (BigInteger::from(0u8), BigInteger::from(1u8));
+        let (mut p1, mut q1) = (BigInteger::from(1u8), BigInteger::from(0u8));
+        let (mut numer, mut denom) = (self.numer.clone(), self.denom.clone());
+        loop {
+            let term = Self::new(numer.clone(), denom.clone()).floor();
+            let q2 = &q0 + &term * &q1;
+            if q2 > *max_denom {
+                break;
+            }
+            let p2 = &p0 + &term * &p1;
+            (p0, q0) = (mem::replace(&mut p1, p2), mem::replace(&mut q1, q2));
+            let rem = numer - &term * &denom;
+            numer = mem::replace(&mut denom, rem);
+        }
+        let k = (max_denom - &q0) / &q1;
+        let semiconvergent = Self::new(&p0 + &k * &p1, &q0 + &k * &q1);
+        let convergent = Self::new(p1, q1);
+        if (&convergent - self).abs() <= (&semiconvergent - self).abs() {
+            convergent
+        } else {
+            semiconvergent
+        }
+    }
+
+    fn reduce(&mut self) {
+        let gcd = self.numer.gcd(&self.denom);
+        if gcd != 1u8 {
+            self.numer /= &gcd;
+            self.denom /= &gcd;
+        }
+        if self.denom.is_negative() {
+            self.numer = -mem::take(&mut self.numer);
+            self.denom = -mem::take(&mut self.denom);
+        }
+    }
+
+    fn add_ref(&self, rhs: &Self) -> Self {
+        Self::new(
+            &self.numer * &rhs.denom + &rhs.numer * &self.denom,
+            &self.denom * &rhs.denom,
+        )
+    }
+
+    fn sub_ref(&self, rhs: &Self) -> Self {
+        Self::new(
+            &self.numer * &rhs.denom - &rhs.numer * &self.denom,
+            &self.denom * &rhs.denom,
+        )
+    }
+
+    fn mul_ref(&self, rhs: &Self) -> Self {
+        Self::new(&self.numer * &rhs.numer, &self.denom * &rhs.denom)
+    }
+
+    fn div_ref(&self, rhs: &Self) -> Self {
+        assert!(!rhs.is_zero(), "attempt to divide by zero");
+        Self::new(&self.numer * &rhs.denom, &self.denom * &rhs.numer)
+    }
+
+    /// Truncated remainder `self - rhs * trunc(self / rhs)`, which has the
+    /// same sign as `self`, same as the primitive integers.
+    fn rem_ref(&self, rhs: &Self) -> Self {
+        assert!(
+            !rhs.is_zero(),
+            "attempt to calculate the remainder with a divisor of zero"
+        );
+        let rem = (&self.numer * &rhs.denom) % (&rhs.numer * &self.denom);
+        Self::new(rem, &self.denom * &rhs.denom)
+    }
+}
+
+macro_rules! forward_binop {
+    ($($Op:ident::$op:ident, $OpAssign:ident::$op_assign:ident => $method:ident;)*) => {$(
+        impl $Op for BigRational {
+            type Output = BigRational;
+
+            fn $op(self, rhs: BigRational) -> Self::Output {
+                self.$method(&rhs)
+            }
+        }
+
+        impl $Op<&BigRational> for BigRational {
+            type Output = BigRational;
+
+            fn $op(self, rhs: &BigRational) -> Self::Output {
+                self.$method(rhs)
+            }
+        }
+
+        impl $Op<BigRational> for &BigRational {
+            type Output = BigRational;
+
+            fn $op(self, rhs: BigRational) -> Self::Output {
+                self.$method(&rhs)
+            }
+        }
+
+        impl $Op<&BigRational> for &BigRational {
+            type Output = BigRational;
+
+            fn $op(self, rhs: &BigRational) -> Self::Output {
+                self.$method(rhs)
+            }
+        }
+
+        impl $OpAssign for BigRational {
+            fn $op_assign(&mut self, rhs: BigRational) {
+                *self = self.$method(&rhs);
+            }
+        }
+
+        impl $OpAssign<&BigRational> for BigRational {
+            fn $op_assign(&mut self, rhs: &BigRational) {
+                *self = self.$method(rhs);
+            }
+        }
+    )*};
+}
+
+forward_binop! {
+    Add::add, AddAssign::add_assign => add_ref;
+    Sub::sub, SubAssign::sub_assign => sub_ref;
+    Mul::mul, MulAssign::mul_assign => mul_ref;
+    Div::div, DivAssign::div_assign => div_ref;
+    Rem::rem, RemAssign::rem_assign => rem_ref;
+}
+
+impl Debug for BigRational {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:?}/{:?}", self.numer, self.denom)
+    }
+}
+
+impl Default for BigRational {
+    fn default() -> Self {
+        Self::from(BigInteger::default())
+    }
+}
+
+/// Writes `p/q`, or only `p` if the rational is an integer.
+impl Display for BigRational {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.is_integer() {
+            Display::fmt(&self.numer, f)
+        } else {
+            f.pad(&format!("{}/{}", self.numer, self.denom))
+        }
+    }
+}
+
+impl From<BigInteger> for BigRational {
+    fn from(numer: BigInteger) -> Self {
+        Self {
+            numer,
+            denom: BigInteger::from(1u8),
+        }
+    }
+}
+
+macro_rules! from_primitive {
+    ($($primitive:ty),*) => {$(
+        impl From<$primitive> for BigRational {
+            fn from(value: $primitive) -> Self {
+                Self::from(BigInteger::from(value))
+            }
+        }
+    )*};
+}
+
+from_primitive!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl Neg for BigRational {
+    type Output = BigRational;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            numer: -self.numer,
+            denom: self.denom,
+        }
+    }
+}
+
+impl Neg for &BigRational {
+    type Output = BigRational;
+
+    fn neg(self) -> Self::Output {
+        -self.clone()
+    }
+}
+
+impl Ord for BigRational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.numer * &other.denom).cmp(&(&other.numer * &self.denom))
+    }
+}
+
+impl PartialOrd for BigRational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl TryFrom<f32> for BigRational {
+    type Error = ConversionError;
+
+    fn try_from(value: f32) -> Result<Self, Self::Error> {
+        Self::try_from(value as f64)
+    }
+}
+
+impl TryFrom<f64> for BigRational {
+    type Error = ConversionError;
+
+    /// Converts the float exactly, every finite float is a dyadic rational.
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        if value.is_nan() {
+            return Err(ConversionError::NaN);
+        } else if value.is_infinite() {
+            return Err(ConversionError::Infinite);
+        }
+        let bits = value.to_bits();
+        let exp = (bits >> 52 & 0x7ff) as i32;
+        let mantissa = match exp {
+            0 => bits << 1 & ((1 << 53) - 1),
+            _ => bits & ((1 << 52) - 1) | 1 << 52,
+        };
+        let mut numer = BigInteger::from(mantissa);
+        if value.is_sign_negative() {
+            numer = -numer;
+        }
+        let two = BigInteger::from(2u8);
+        Ok(match exp - 1075 {
+            exp if exp >= 0 => Self::from(numer * two.pow(exp as u32)),
+            exp => Self::new(numer, two.pow(exp.unsigned_abs())),
+        })
+    }
+}
@@ -1,6 +1,5 @@
-#![feature(bigint_helper_methods)]
-
 pub mod big_integer;
+pub mod big_rational;
 
 pub struct Solution;
 
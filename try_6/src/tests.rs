@@ -1 +1,441 @@
+use crate::{
+    big_integer::{BigInteger, ConversionError},
+    big_rational::BigRational,
+};
 
+#[test]
+fn div_rem() {
+    let values = [
+        0i128,
+        1,
+        -1,
+        255,
+        256,
+        -65_537,
+        0x1234_5678_9abc_def0,
+        -0x7fff_ffff_ffff_ffff_ffff,
+        i128::MAX,
+        i128::MIN + 1,
+    ];
+    for &lhs in &values {
+        for &rhs in values.iter().filter(|&&v| v != 0) {
+            let (quo, rem) = BigInteger::from(lhs).div_rem(&BigInteger::from(rhs));
+            assert_eq!(quo, BigInteger::from(lhs / rhs), "{lhs} / {rhs}");
+            assert_eq!(rem, BigInteger::from(lhs % rhs), "{lhs} % {rhs}");
+            assert_eq!(
+                BigInteger::from(lhs).rem_euclid(&BigInteger::from(rhs)),
+                BigInteger::from(lhs.rem_euclid(rhs)),
+            );
+        }
+    }
+}
+
+#[test]
+fn pow() {
+    assert_eq!(
+        BigInteger::from(3u8).pow(80),
+        BigInteger::from(3u128.pow(80))
+    );
+    assert_eq!(
+        BigInteger::from(-7i8).pow(45),
+        BigInteger::from((-7i128).pow(45))
+    );
+    assert_eq!(BigInteger::from(-7i8).pow(0), BigInteger::from(1u8));
+    assert_eq!(BigInteger::from(0u8).pow(0), BigInteger::from(1u8));
+}
+
+#[test]
+fn modpow() {
+    let modpow = |base: u128, mut exp: u128, modulus: u128| {
+        let (mut base, mut result) = (base % modulus, 1 % modulus);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result * base % modulus;
+            }
+            base = base * base % modulus;
+            exp >>= 1;
+        }
+        result
+    };
+    for (base, exp, modulus) in [
+        (4u128, 13u128, 497u128),
+        (2, 1_000_000_007, 1_000_000_007),
+        (0xdead_beef, 0xffff_ffff, 0xffff_fffb),
+        (12345, 678_910, 1 << 32),
+        (7, 0, 13),
+        (7, 3, 1),
+    ] {
+        assert_eq!(
+            BigInteger::from(base).modpow(&BigInteger::from(exp), &BigInteger::from(modulus)),
+            BigInteger::from(modpow(base, exp, modulus)),
+        );
+    }
+    assert_eq!(
+        BigInteger::from(-2i8).modpow(&BigInteger::from(3u8), &BigInteger::from(7u8)),
+        BigInteger::from(6u8),
+    );
+}
+
+#[test]
+fn gcd() {
+    let gcd = |lhs: i64, rhs: i64| BigInteger::from(lhs).gcd(&BigInteger::from(rhs));
+    assert_eq!(gcd(0, 0), BigInteger::from(0u8));
+    assert_eq!(gcd(0, -12), BigInteger::from(12u8));
+    assert_eq!(gcd(48, 180), BigInteger::from(12u8));
+    assert_eq!(gcd(-1 << 40, 3 << 20), BigInteger::from(1u32 << 20));
+    assert_eq!(gcd(1_000_000_007, 998_244_353), BigInteger::from(1u8));
+}
+
+#[test]
+fn extended_gcd() {
+    for (lhs, rhs) in [
+        (240i64, 46i64),
+        (-240, 46),
+        (17, -5),
+        (0, 9),
+        (1 << 40, 3 << 30),
+    ] {
+        let (gcd, x, y) = BigInteger::from(lhs).extended_gcd(&BigInteger::from(rhs));
+        assert_eq!(gcd, BigInteger::from(lhs).gcd(&BigInteger::from(rhs)));
+        assert_eq!(BigInteger::from(lhs) * x + BigInteger::from(rhs) * y, gcd);
+    }
+}
+
+#[test]
+fn mod_inverse() {
+    let modulus = BigInteger::from(1_000_000_007u32);
+    let inverse = BigInteger::from(123_456_789u32)
+        .mod_inverse(&modulus)
+        .unwrap();
+    assert_eq!(
+        (inverse * BigInteger::from(123_456_789u32)).rem_euclid(&modulus),
+        BigInteger::from(1u8),
+    );
+    assert_eq!(
+        BigInteger::from(-3i8).mod_inverse(&BigInteger::from(7u8)),
+        Some(BigInteger::from(2u8)),
+    );
+    assert_eq!(
+        BigInteger::from(6u8).mod_inverse(&BigInteger::from(9u8)),
+        None
+    );
+}
+
+#[test]
+fn sqrt() {
+    for value in [
+        0u128,
+        1,
+        2,
+        3,
+        4,
+        15,
+        16,
+        17,
+        1 << 64,
+        (1 << 64) - 1,
+        u128::MAX,
+    ] {
+        let root = BigInteger::from(value).sqrt();
+        assert!(&root * &root <= value);
+        let next = root + BigInteger::from(1u8);
+        assert!(&next * &next > value);
+    }
+}
+
+#[test]
+fn nth_root() {
+    assert_eq!(
+        BigInteger::from(3u8).pow(100).nth_root(5),
+        BigInteger::from(3u8).pow(20)
+    );
+    assert_eq!(
+        BigInteger::from(-1_000_001i32).nth_root(3),
+        BigInteger::from(-100i8)
+    );
+    assert_eq!(
+        (BigInteger::from(10u8).pow(30) - BigInteger::from(1u8)).nth_root(3),
+        BigInteger::from(10u8).pow(10) - BigInteger::from(1u8),
+    );
+}
+
+#[test]
+fn is_probable_prime() {
+    let primes = [2u64, 3, 61, 67, 7919, 1_000_000_007, (1 << 61) - 1];
+    let composites = [0u64, 1, 4, 561, 3_215_031_751, 3_825_123_056_546_413_051];
+    for value in primes {
+        assert!(BigInteger::from(value).is_probable_prime(0), "{value}");
+    }
+    for value in composites {
+        assert!(!BigInteger::from(value).is_probable_prime(0), "{value}");
+    }
+    let mersenne = |exp| BigInteger::from(2u8).pow(exp) - BigInteger::from(1u8);
+    assert!(mersenne(89).is_probable_prime(16));
+    assert!(mersenne(127).is_probable_prime(16));
+    assert!(!mersenne(67).is_probable_prime(16));
+    assert!(!(mersenne(89) * mersenne(107)).is_probable_prime(16));
+}
+
+#[test]
+fn try_from_big_integer() {
+    assert_eq!(u8::try_from(&BigInteger::from(255u8)), Ok(255));
+    assert_eq!(
+        u8::try_from(&BigInteger::from(256u16)),
+        Err(ConversionError::PosOverflow)
+    );
+    assert_eq!(
+        u64::try_from(&BigInteger::from(-1i8)),
+        Err(ConversionError::NegOverflow)
+    );
+    assert_eq!(i8::try_from(&BigInteger::from(-128i16)), Ok(-128));
+    assert_eq!(
+        i8::try_from(&BigInteger::from(-129i16)),
+        Err(ConversionError::NegOverflow)
+    );
+    assert_eq!(i128::try_from(&BigInteger::from(i128::MIN)), Ok(i128::MIN));
+    assert_eq!(u128::try_from(&BigInteger::from(u128::MAX)), Ok(u128::MAX));
+    assert_eq!(
+        i128::try_from(&BigInteger::from(u128::MAX)),
+        Err(ConversionError::PosOverflow)
+    );
+    assert_eq!(
+        usize::try_from(&(BigInteger::from(u128::MAX) + BigInteger::from(1u8))),
+        Err(ConversionError::PosOverflow)
+    );
+    assert_eq!(isize::try_from(&BigInteger::from(0u8)), Ok(0));
+}
+
+#[test]
+fn to_float() {
+    let two = BigInteger::from(2u8);
+    assert_eq!(BigInteger::from(0u8).to_f64(), 0.0);
+    assert_eq!(BigInteger::from(-12345i16).to_f64(), -12345.0);
+    assert_eq!(BigInteger::from(u64::MAX).to_f64(), u64::MAX as f64);
+    assert_eq!(
+        BigInteger::from(i128::MIN + 1).to_f64(),
+        (i128::MIN + 1) as f64
+    );
+    // ties to even
+    let value = two.pow(53) + BigInteger::from(1u8);
+    assert_eq!(value.to_f64(), 2f64.powi(53));
+    let value = two.pow(53) + BigInteger::from(3u8);
+    assert_eq!(value.to_f64(), 2f64.powi(53) + 4.0);
+    let value = two.pow(200) + two.pow(147) + BigInteger::from(1u8);
+    assert_eq!(value.to_f64(), 2f64.powi(200) + 2f64.powi(148));
+    assert_eq!(two.pow(1023).to_f64(), 2f64.powi(1023));
+    assert_eq!(two.pow(1024).to_f64(), f64::INFINITY);
+    assert_eq!(
+        (BigInteger::from(0u8) - two.pow(1024)).to_f64(),
+        f64::NEG_INFINITY
+    );
+    assert_eq!(BigInteger::from(16_777_217u32).to_f32(), 16_777_216.0);
+    assert_eq!(BigInteger::from(u128::MAX).to_f32(), f32::INFINITY);
+    assert_eq!(BigInteger::from(i128::MIN + 1).to_f32(), -(2f32.powi(127)));
+}
+
+#[test]
+fn from_float() {
+    assert_eq!(BigInteger::try_from(-0.0), Ok(BigInteger::from(0u8)));
+    assert_eq!(BigInteger::try_from(0.999), Ok(BigInteger::from(0u8)));
+    assert_eq!(BigInteger::try_from(-2.5), Ok(BigInteger::from(-2i8)));
+    assert_eq!(
+        BigInteger::try_from(1e20),
+        Ok(BigInteger::from(100_000_000_000_000_000_000u128))
+    );
+    assert_eq!(
+        BigInteger::try_from(2f64.powi(200)),
+        Ok(BigInteger::from(2u8).pow(200))
+    );
+    assert_eq!(
+        BigInteger::try_from(-1.5e9f32),
+        Ok(BigInteger::from(-1_500_000_000i32))
+    );
+    assert_eq!(BigInteger::try_from(f64::NAN), Err(ConversionError::NaN));
+    assert_eq!(
+        BigInteger::try_from(f32::NEG_INFINITY),
+        Err(ConversionError::Infinite)
+    );
+    let value = BigInteger::try_from(f64::MAX).unwrap();
+    assert_eq!(value.to_f64(), f64::MAX);
+}
+
+#[test]
+fn to_i128_wrapping() {
+    let two = BigInteger::from(2u8);
+    assert_eq!(BigInteger::from(-42i8).to_i128_wrapping(), -42);
+    assert_eq!(BigInteger::from(u128::MAX).to_i128_wrapping(), -1);
+    assert_eq!((two.pow(130) + BigInteger::from(7u8)).to_i128_wrapping(), 7);
+    assert_eq!(
+        (BigInteger::from(0u8) - two.pow(127)).to_i128_wrapping(),
+        i128::MIN
+    );
+}
+
+#[test]
+fn operators() {
+    let (a, b) = (BigInteger::from(-1234567i32), BigInteger::from(89u8));
+    assert_eq!(&a + &b, BigInteger::from(-1234478i32));
+    assert_eq!(&a - &b, BigInteger::from(-1234656i32));
+    assert_eq!(&a * &b, BigInteger::from(-109876463i32));
+    assert_eq!(&a / &b, BigInteger::from(-13871i32));
+    assert_eq!(&a % &b, BigInteger::from(-48i32));
+    assert_eq!(a.clone() + &b, &a + b.clone());
+    let mut c = a.clone();
+    c += &b;
+    c -= 5u32;
+    c *= -2i64;
+    c /= 3usize;
+    c %= 1000i16;
+    assert_eq!(c, (-1234478i64 - 5) * -2 / 3 % 1000);
+    assert_eq!(&a + 5u32, -1234562);
+    assert_eq!(5i64 * &a, -6172835);
+    assert_eq!(10u8 - b.clone(), -79);
+    assert_eq!(1_000_000u32 / &b, 11235);
+    assert_eq!(-&a, 1234567u32);
+    assert_eq!(-BigInteger::from(0u8), BigInteger::from(0u8));
+}
+
+#[test]
+fn iterators() {
+    let values: Vec<_> = (1..=30u8).map(BigInteger::from).collect();
+    assert_eq!(values.iter().sum::<BigInteger>(), 465);
+    assert_eq!(
+        values.iter().product::<BigInteger>(),
+        BigInteger::from(265_252_859_812_191_058_636_308_480_000_000u128)
+    );
+    assert_eq!(
+        values.into_iter().rev().take(2).product::<BigInteger>(),
+        870
+    );
+}
+
+#[test]
+fn primitive_cmp() {
+    let value = BigInteger::from(-300i16);
+    assert!(value < 0u8);
+    assert!(value < -299i32);
+    assert!(value > i64::MIN);
+    assert!(value == -300isize);
+    assert!(-301i16 < value);
+    assert!(u128::MAX > value);
+    assert!(BigInteger::from(u128::MAX) + 1u8 > u128::MAX);
+    assert_eq!(BigInteger::from(0u8), 0i8);
+    assert_eq!(0u64, BigInteger::from(0i8));
+}
+
+#[test]
+fn display() {
+    assert_eq!(BigInteger::from(0u8).to_string(), "0");
+    assert_eq!(
+        BigInteger::from(i128::MIN).to_string(),
+        i128::MIN.to_string()
+    );
+    assert_eq!(BigInteger::from(u64::MAX).to_string(), u64::MAX.to_string());
+    assert_eq!(format!("{:+08}", BigInteger::from(1234u16)), "+0001234");
+    assert_eq!(
+        BigInteger::from(10u8).pow(40).to_string(),
+        format!("1{}", "0".repeat(40))
+    );
+}
+
+#[test]
+fn to_str_radix() {
+    let value = BigInteger::from(-0x1234_5678_9abc_def0_i64);
+    assert_eq!(value.to_str_radix(16), "-123456789abcdef0");
+    assert_eq!(
+        value.to_str_radix(2),
+        format!("-{:b}", 0x1234_5678_9abc_def0_i64)
+    );
+    assert_eq!(value.to_str_radix(36), "-9ys742pps3qo");
+    assert_eq!(BigInteger::from(0u8).to_str_radix(7), "0");
+    assert_eq!(
+        BigInteger::from(u128::MAX).to_str_radix(8),
+        format!("{:o}", u128::MAX)
+    );
+}
+
+#[test]
+fn big_rational() {
+    let ratio = |numer: i64, denom: i64| BigRational::new(numer.into(), denom.into());
+    assert_eq!(ratio(6, -4), ratio(-3, 2));
+    assert_eq!(ratio(6, -4).numer(), &BigInteger::from(-3i8));
+    assert_eq!(ratio(0, -4), BigRational::default());
+    assert_eq!(ratio(1, 2) + ratio(1, 3), ratio(5, 6));
+    assert_eq!(&ratio(1, 2) - &ratio(1, 3), ratio(1, 6));
+    assert_eq!(ratio(-2, 3) * ratio(9, 4), ratio(-3, 2));
+    assert_eq!(ratio(-2, 3) / ratio(-4, 9), ratio(3, 2));
+    assert_eq!(ratio(-7, 2) % ratio(3, 2), ratio(-1, 2));
+    assert_eq!(-ratio(1, 3), ratio(-1, 3));
+    assert_eq!(ratio(-7, 2).floor(), BigInteger::from(-4i8));
+    assert_eq!(ratio(-7, 2).ceil(), BigInteger::from(-3i8));
+    assert_eq!(ratio(5, 3).recip(), ratio(3, 5));
+    let mut value = ratio(1, 1);
+    value += ratio(1, 2);
+    value *= &ratio(2, 5);
+    assert_eq!(value, ratio(3, 5));
+    assert!(ratio(1, 3) < ratio(1, 2));
+    assert!(ratio(-1, 2) < ratio(-1, 3));
+    assert_eq!(ratio(-3, 6).to_string(), "-1/2");
+    assert_eq!(ratio(8, 4).to_string(), "2");
+}
+
+#[test]
+fn big_rational_from_float() {
+    assert_eq!(
+        BigRational::try_from(0.75),
+        Ok(BigRational::new(3u8.into(), 4u8.into()))
+    );
+    assert_eq!(
+        BigRational::try_from(-1e10),
+        Ok(BigRational::from(-10_000_000_000i64))
+    );
+    let value = BigRational::try_from(0.1).unwrap();
+    assert_eq!(value.numer(), &BigInteger::from(3_602_879_701_896_397u64));
+    assert_eq!(value.denom(), &BigInteger::from(1u64 << 55));
+    let value = BigRational::try_from(f64::MIN_POSITIVE / 4.0).unwrap();
+    assert_eq!(value.denom(), &BigInteger::from(2u8).pow(1024));
+    assert_eq!(BigRational::try_from(f64::NAN), Err(ConversionError::NaN));
+    assert_eq!(
+        BigRational::try_from(f32::INFINITY),
+        Err(ConversionError::Infinite)
+    );
+}
+
+#[test]
+fn continued_fraction() {
+    let terms = |values: &[i64]| {
+        values
+            .iter()
+            .map(|&v| BigInteger::from(v))
+            .collect::<Vec<_>>()
+    };
+    let value = BigRational::new(415u16.into(), 93u8.into());
+    assert_eq!(value.continued_fraction(), terms(&[4, 2, 6, 7]));
+    assert_eq!(
+        BigRational::from_continued_fraction(&terms(&[4, 2, 6, 7])),
+        value
+    );
+    let value = BigRational::new((-415i16).into(), 93u8.into());
+    assert_eq!(value.continued_fraction(), terms(&[-5, 1, 1, 6, 7]));
+    assert_eq!(
+        BigRational::from_continued_fraction(&value.continued_fraction()),
+        value
+    );
+    assert_eq!(BigRational::from(3u8).continued_fraction(), terms(&[3]));
+}
+
+#[test]
+fn limit_denominator() {
+    let pi = BigRational::try_from(core::f64::consts::PI).unwrap();
+    let limit = |max: u32| pi.limit_denominator(&max.into());
+    assert_eq!(limit(1), BigRational::from(3u8));
+    assert_eq!(limit(10), BigRational::new(22u8.into(), 7u8.into()));
+    assert_eq!(limit(100), BigRational::new(311u16.into(), 99u8.into()));
+    assert_eq!(limit(1000), BigRational::new(355u16.into(), 113u8.into()));
+    let value = BigRational::new((-1i8).into(), 3u8.into());
+    assert_eq!(
+        value.limit_denominator(&2u8.into()),
+        BigRational::new((-1i8).into(), 2u8.into())
+    );
+    assert_eq!(value.limit_denominator(&3u8.into()), value);
+}
@@ -0,0 +1,124 @@
+use try_6::big_integer::BigInteger;
+
+/// Factorial `n! = 1 * 2 * ... * n`, `0! = 1`.
+pub fn factorial(n: usize) -> BigInteger {
+    (2..=n).fold(BigInteger::from(1u8), |product, k| product * k)
+}
+
+/// Binomial coefficient `C(n, k) = n! / (k! * (n - k)!)`, zero if `k > n`.
+///
+/// C(n, i + 1) = C(n, i) * (n - i) / (i + 1), and every division is exact.
+pub fn binomial(n: usize, k: usize) -> BigInteger {
+    if k > n {
+        return BigInteger::default();
+    }
+    (0..k.min(n - k)).fold(BigInteger::from(1u8), |product, i| {
+        product * (n - i) / (i + 1)
+    })
+}
+
+/// Catalan Number, e.g. the number of binary trees with `n` nodes.
+///
+/// Each step multiplies before dividing, so that the division is exact.
+pub fn catalan(n: usize) -> BigInteger {
+    (1..=n).fold(BigInteger::from(1u8), |product, i| {
+        product * (4 * i - 2) / (i + 1)
+    })
+}
+
+/// Unsigned Stirling number of the first kind `[n, k]`, the number of
+/// permutations of `n` elements with exactly `k` cycles.
+///
+/// [n + 1, k] = n * [n, k] + [n, k - 1]
+pub fn stirling_first(n: usize, k: usize) -> BigInteger {
+    stirling(n, k, |i, _| i)
+}
+
+/// Stirling number of the second kind `{n, k}`, the number of ways to
+/// partition `n` elements into exactly `k` non-empty subsets.
+///
+/// {n + 1, k} = k * {n, k} + {n, k - 1}
+pub fn stirling_second(n: usize, k: usize) -> BigInteger {
+    stirling(n, k, |_, j| j)
+}
+
+/// Both kinds of Stirling numbers share the same triangle, except for the
+/// factor `factor(i, j)` of the term from the row above.
+fn stirling(n: usize, k: usize, factor: fn(usize, usize) -> usize) -> BigInteger {
+    if k > n {
+        return BigInteger::default();
+    }
+    let mut row = vec![BigInteger::default(); k + 1];
+    row[0] = BigInteger::from(1u8);
+    for i in 0..n {
+        for j in (1..=k.min(i + 1)).rev() {
+            row[j] = &row[j] * factor(i, j) + &row[j - 1];
+        }
+        row[0] = BigInteger::default();
+    }
+    row.swap_remove(k)
+}
+
+/// Bell Number, the number of partitions of a set of `n` elements.
+///
+/// The Bell triangle starts each row with the last element of the previous
+/// row, and each next element is the sum of its left and upper left ones.
+pub fn bell(n: usize) -> BigInteger {
+    let mut row = vec![BigInteger::from(1u8)];
+    for _ in 0..n {
+        let mut next = Vec::with_capacity(row.len() + 1);
+        next.push(row[row.len() - 1].clone());
+        for value in &row {
+            next.push(&next[next.len() - 1] + value);
+        }
+        row = next;
+    }
+    row.swap_remove(0)
+}
+
+/// Partition Number, the number of ways to write `n` as a sum of positive
+/// integers regardless of order.
+///
+/// By Euler's pentagonal number theorem, p(n) = sum of (-1)^(k+1) * (p(n -
+/// k(3k-1)/2) + p(n - k(3k+1)/2)) for k = 1, 2, ..., which is O(n^1.5).
+pub fn partition(n: usize) -> BigInteger {
+    let mut table = vec![BigInteger::from(1u8)];
+    for m in 1..=n {
+        let mut sum = BigInteger::default();
+        for k in 1.. {
+            let pentagonal = k * (3 * k - 1) / 2;
+            if pentagonal > m {
+                break;
+            }
+            let mut term = table[m - pentagonal].clone();
+            if pentagonal + k <= m {
+                term += &table[m - pentagonal - k];
+            }
+            if k & 1 == 1 {
+                sum += term;
+            } else {
+                sum -= term;
+            }
+        }
+        table.push(sum);
+    }
+    table.swap_remove(n)
+}
+
+/// Fibonacci Number, F(0) = 0, F(1) = 1.
+///
+/// The fast doubling method takes O(log n) multiplications by
+/// F(2k) = F(k) * (2 * F(k+1) - F(k)) and F(2k+1) = F(k)^2 + F(k+1)^2.
+pub fn fibonacci(n: usize) -> BigInteger {
+    let (mut curr, mut next) = (BigInteger::default(), BigInteger::from(1u8));
+    for i in (0..usize::BITS - n.leading_zeros()).rev() {
+        let double = &curr * (&next * 2u8 - &curr);
+        let square = &curr * &curr + &next * &next;
+        (curr, next) = if n >> i & 1 == 1 {
+            (square.clone(), double + square)
+        } else {
+            (double, square)
+        };
+    }
+    curr
+}
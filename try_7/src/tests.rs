@@ -1,4 +1,4 @@
-use super::Solution;
+use super::{combinatorics, Solution};
 
 #[test]
 fn catalan_number() {
@@ -20,3 +20,95 @@ fn catalan_number() {
     assert_eq!(Solution::catalan_number(15).to_string(), "9694845");
     assert_eq!(Solution::catalan_number(16).to_string(), "35357670");
 }
+
+#[test]
+fn catalan_number_big() {
+    let value = Solution::catalan_number(100);
+    assert_eq!(
+        value.to_string(),
+        "896519947090131496687170070074100632420837521538745909320"
+    );
+    assert_eq!(value, combinatorics::binomial(200, 100) / 101u8);
+}
+
+#[test]
+fn factorial() {
+    assert_eq!(combinatorics::factorial(0), 1u8);
+    assert_eq!(combinatorics::factorial(20), 2_432_902_008_176_640_000u64);
+    assert_eq!(
+        combinatorics::factorial(50).to_string(),
+        "30414093201713378043612608166064768844377641568960512000000000000"
+    );
+    assert_eq!(
+        combinatorics::factorial(30).to_str_radix(16),
+        "d13f6370f96865df5dd54000000"
+    );
+}
+
+#[test]
+fn binomial() {
+    assert_eq!(combinatorics::binomial(0, 0), 1u8);
+    assert_eq!(combinatorics::binomial(5, 6), 0u8);
+    assert_eq!(combinatorics::binomial(10, 3), 120u8);
+    assert_eq!(combinatorics::binomial(10, 7), 120u8);
+    assert_eq!(
+        combinatorics::binomial(100, 50).to_string(),
+        "100891344545564193334812497256"
+    );
+}
+
+#[test]
+fn stirling() {
+    let first = [0u64, 24, 50, 35, 10, 1];
+    let second = [0u64, 1, 15, 25, 10, 1];
+    for k in 0..=5 {
+        assert_eq!(combinatorics::stirling_first(k, k), 1u8);
+        assert_eq!(combinatorics::stirling_second(k, k), 1u8);
+        assert_eq!(combinatorics::stirling_first(5, k), first[k]);
+        assert_eq!(combinatorics::stirling_second(5, k), second[k]);
+    }
+    assert_eq!(combinatorics::stirling_second(3, 4), 0u8);
+    assert_eq!(
+        combinatorics::stirling_first(20, 1),
+        combinatorics::factorial(19)
+    );
+    assert_eq!(combinatorics::stirling_second(30, 2), (1u32 << 29) - 1);
+}
+
+#[test]
+fn bell() {
+    let expected = [1u64, 1, 2, 5, 15, 52, 203, 877, 4140, 21147, 115975];
+    for (n, &value) in expected.iter().enumerate() {
+        assert_eq!(combinatorics::bell(n), value);
+    }
+    let sum = (0..=25)
+        .map(|k| combinatorics::stirling_second(25, k))
+        .sum::<try_6::big_integer::BigInteger>();
+    assert_eq!(combinatorics::bell(25), sum);
+}
+
+#[test]
+fn partition() {
+    let expected = [1u64, 1, 2, 3, 5, 7, 11, 15, 22, 30, 42];
+    for (n, &value) in expected.iter().enumerate() {
+        assert_eq!(combinatorics::partition(n), value);
+    }
+    assert_eq!(combinatorics::partition(100), 190_569_292u32);
+    assert_eq!(
+        combinatorics::partition(1000).to_string(),
+        "24061467864032622473692149727991"
+    );
+}
+
+#[test]
+fn fibonacci() {
+    let (mut curr, mut next) = (0u128, 1u128);
+    for n in 0..=180 {
+        assert_eq!(combinatorics::fibonacci(n), curr);
+        (curr, next) = (next, curr + next);
+    }
+    assert_eq!(
+        combinatorics::fibonacci(300).to_string(),
+        "222232244629420445529739893461909967206666939096499764990979600"
+    );
+}